@@ -10,14 +10,14 @@ fn main() {
         let _ = registered.module_path();
 
         // Create an instance of the type if a constructor is registered
-        if let Some(instance) = registered.instanciate() {
+        if let Some(instance) = registered.instantiate() {
             // Use Debug, a supertrait of 'MyTrait'
             println!("Instance: {instance:#?}");
         }
     }
 
     // Create a new instance for every registered type with a constructor
-    let instances: Vec<Box<dyn MyTrait>> = MYTRAIT_REGISTRY.instanciate_all().collect();
+    let instances: Vec<Box<dyn MyTrait>> = MYTRAIT_REGISTRY.instantiate_all().collect();
 
     for instance in instances.iter() {
         // Call a trait method
@@ -0,0 +1,123 @@
+//! Instantiation of registered implementations in arenas.
+//!
+//! [RegisteredImplWrapper::instantiate_in](crate::RegisteredImplWrapper::instantiate_in)
+//! constructs an implementation in memory allocated from an [Arena] rather than the global heap,
+//! for example a per-frame or per-request arena. Any type can be used as an arena by implementing
+//! [Arena], the `bumpalo` feature implements it for `bumpalo::Bump`.
+//!
+//! ```rust
+//! use core::alloc::Layout;
+//! use core::cell::{Cell, UnsafeCell};
+//! use core::ptr::NonNull;
+//!
+//! trait Codec {
+//!     fn name(&self) -> &'static str;
+//! }
+//!
+//! #[derive(Default)]
+//! struct Gzip;
+//!
+//! #[traitreg::register(default)]
+//! impl Codec for Gzip {
+//!     fn name(&self) -> &'static str {
+//!         "gzip"
+//!     }
+//! }
+//!
+//! #[traitreg::registry(Codec)]
+//! static CODEC_REGISTRY: () = ();
+//!
+//! // Fixed size arena, which never frees memory
+//! #[repr(align(16))]
+//! struct FixedArena {
+//!     buffer: UnsafeCell<[u8; 256]>,
+//!     used: Cell<usize>,
+//! }
+//!
+//! unsafe impl traitreg::arena::Arena for FixedArena {
+//!     fn alloc(&self, layout: Layout) -> Option<NonNull<u8>> {
+//!         let start = self.used.get().next_multiple_of(layout.align());
+//!         let end = start.checked_add(layout.size())?;
+//!         if layout.align() > 16 || end > 256 {
+//!             return None;
+//!         }
+//!         self.used.set(end);
+//!         NonNull::new(unsafe { self.buffer.get().cast::<u8>().add(start) })
+//!     }
+//! }
+//!
+//! let arena = FixedArena { buffer: UnsafeCell::new([0; 256]), used: Cell::new(0) };
+//! for reg in CODEC_REGISTRY.iter() {
+//!     let codec = reg.instantiate_in(&arena).unwrap();
+//!     assert_eq!("gzip", codec.name());
+//! }
+//! ```
+//!
+//! Implementations registered with constructors taking a resolver with `inject` can not be
+//! instantiated in an arena.
+
+use core::alloc::Layout;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+/// Memory allocator for [ArenaInstance]s. Memory is never freed through the arena, instances
+/// are only dropped in place.
+///
+/// # Safety
+///
+/// Implementations must return memory which fits `layout`, and which is not used for anything else
+/// for as long as the arena is borrowed. For zero sized layouts a dangling pointer with the
+/// alignment of the layout can be returned.
+pub unsafe trait Arena {
+    /// Allocate memory for a value, or return `None` if no memory is available
+    fn alloc(&self, layout: Layout) -> Option<NonNull<u8>>;
+}
+
+// Safety: bumpalo allocations are unique, fit the layout and live as long as the `Bump` is borrowed
+#[cfg(feature = "bumpalo")]
+unsafe impl Arena for bumpalo::Bump {
+    fn alloc(&self, layout: Layout) -> Option<NonNull<u8>> {
+        self.try_alloc_layout(layout).ok()
+    }
+}
+
+/// Instance of a registered implementation in an [Arena]. The instance is dropped in place when
+/// the `ArenaInstance` is dropped, its memory is reclaimed by the arena.
+pub struct ArenaInstance<'a, T: ?Sized> {
+    instance: NonNull<T>,
+    arena: PhantomData<&'a mut T>,
+}
+
+impl<T: ?Sized> ArenaInstance<'_, T> {
+    // Safety: The pointer must be to a valid value, in memory which is not used for anything else
+    // for the lifetime
+    pub(crate) unsafe fn new(instance: *mut T) -> Self {
+        Self {
+            instance: NonNull::new_unchecked(instance),
+            arena: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> core::ops::Deref for ArenaInstance<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety: The instance is valid until it is dropped
+        unsafe { self.instance.as_ref() }
+    }
+}
+
+impl<T: ?Sized> core::ops::DerefMut for ArenaInstance<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: The instance is valid until it is dropped, and uniquely borrowed
+        unsafe { self.instance.as_mut() }
+    }
+}
+
+impl<T: ?Sized> Drop for ArenaInstance<'_, T> {
+    fn drop(&mut self) {
+        // Safety: The instance is valid, and never used again
+        unsafe { core::ptr::drop_in_place(self.instance.as_ptr()) }
+    }
+}
@@ -0,0 +1,70 @@
+//! Adapter turning registered bevy plugins into a `PluginGroup`. Requires the `bevy` feature.
+//!
+//! Plugins are registered as implementations of a trait with [RegisteredPlugin] as a supertrait,
+//! which is implemented for every `bevy::app::Plugin`.
+//!
+//! ```rust
+//! use bevy_app::{App, Plugin};
+//! use traitreg::bevy::{RegisteredPlugin, RegisteredPlugins};
+//!
+//! trait GamePlugin: RegisteredPlugin {}
+//!
+//! #[derive(Default)]
+//! struct PhysicsPlugin;
+//!
+//! impl Plugin for PhysicsPlugin {
+//!     fn build(&self, _app: &mut App) {}
+//! }
+//!
+//! #[traitreg::register(default, priority = 10)]
+//! impl GamePlugin for PhysicsPlugin {}
+//!
+//! #[traitreg::registry(GamePlugin)]
+//! static GAME_PLUGIN_REGISTRY: () = ();
+//!
+//! App::new().add_plugins(RegisteredPlugins::new(&GAME_PLUGIN_REGISTRY));
+//! ```
+
+use crate::TraitRegStorage;
+use bevy_app::{Plugin, PluginGroup, PluginGroupBuilder};
+
+/// Plugin which can be added to a [RegisteredPlugins] group, implemented for every
+/// `bevy::app::Plugin`
+pub trait RegisteredPlugin: Plugin {
+    /// Add the plugin to a plugin group
+    fn add_to_group(self: Box<Self>, group: PluginGroupBuilder) -> PluginGroupBuilder;
+}
+
+impl<P: Plugin> RegisteredPlugin for P {
+    fn add_to_group(self: Box<Self>, group: PluginGroupBuilder) -> PluginGroupBuilder {
+        group.add(*self)
+    }
+}
+
+/// Plugin group of every registered plugin which has a constructor
+pub struct RegisteredPlugins<T: ?Sized> {
+    plugins: Vec<Box<T>>,
+}
+
+impl<T: ?Sized + RegisteredPlugin> RegisteredPlugins<T> {
+    /// Instantiate all registered implementations which have a constructor, ordered by
+    /// [priority](crate::RegisteredImplWrapper::priority) with the highest first
+    pub fn new(registry: &TraitRegStorage<Box<T>>) -> Self {
+        Self {
+            plugins: registry
+                .iter_by_priority()
+                .filter_map(|item| item.instantiate())
+                .collect(),
+        }
+    }
+}
+
+impl<T: ?Sized + RegisteredPlugin> PluginGroup for RegisteredPlugins<T> {
+    fn build(self) -> PluginGroupBuilder {
+        self.plugins
+            .into_iter()
+            .fold(PluginGroupBuilder::start::<Self>(), |group, plugin| {
+                plugin.add_to_group(group)
+            })
+    }
+}
@@ -0,0 +1,116 @@
+//! Adapter building `clap` subcommands from a registry. Requires the `clap` feature.
+//!
+//! Subcommands are registered as implementations of a trait with [Subcommand] as a supertrait.
+//!
+//! ```rust
+//! use traitreg::clap::{RegisteredSubcommands, Subcommand};
+//!
+//! trait Plugin: Subcommand {}
+//!
+//! #[derive(Default)]
+//! struct Greet;
+//!
+//! impl Subcommand for Greet {
+//!     fn name(&self) -> &'static str {
+//!         "greet"
+//!     }
+//!
+//!     fn about(&self) -> Option<&'static str> {
+//!         Some("Print a greeting")
+//!     }
+//!
+//!     fn args(&self) -> Vec<clap::Arg> {
+//!         vec![clap::Arg::new("name").required(true)]
+//!     }
+//!
+//!     fn run(&self, matches: &clap::ArgMatches) {
+//!         println!("Hello {}", matches.get_one::<String>("name").unwrap());
+//!     }
+//! }
+//!
+//! #[traitreg::register(default)]
+//! impl Plugin for Greet {}
+//!
+//! #[traitreg::registry(Plugin)]
+//! static PLUGIN_REGISTRY: () = ();
+//!
+//! let subcommands = RegisteredSubcommands::new(&PLUGIN_REGISTRY);
+//! let command = subcommands.command(clap::Command::new("tool"));
+//! let matches = command.get_matches_from(["tool", "greet", "world"]);
+//! assert!(subcommands.dispatch(&matches));
+//! ```
+
+use crate::TraitRegStorage;
+
+/// Subcommand of a command line tool, built into a `clap::Command` by [RegisteredSubcommands]
+pub trait Subcommand {
+    /// Name of the subcommand
+    fn name(&self) -> &'static str;
+
+    /// Description of the subcommand shown in help
+    fn about(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Arguments of the subcommand
+    fn args(&self) -> Vec<clap::Arg> {
+        vec![]
+    }
+
+    /// Run the subcommand with the matched arguments
+    fn run(&self, matches: &clap::ArgMatches);
+}
+
+/// Subcommands instantiated from every registered [Subcommand] which has a constructor
+pub struct RegisteredSubcommands<T: ?Sized> {
+    subcommands: Vec<Box<T>>,
+}
+
+impl<T: ?Sized + Subcommand> RegisteredSubcommands<T> {
+    /// Instantiate all registered implementations which have a constructor, ordered by
+    /// [priority](crate::RegisteredImplWrapper::priority) with the highest first
+    pub fn new(registry: &TraitRegStorage<Box<T>>) -> Self {
+        Self {
+            subcommands: registry
+                .iter_by_priority()
+                .filter_map(|item| item.instantiate())
+                .collect(),
+        }
+    }
+
+    /// Instantiated subcommands
+    pub fn subcommands(&self) -> &[Box<T>] {
+        &self.subcommands
+    }
+
+    /// Add every subcommand to a command
+    pub fn command(&self, command: clap::Command) -> clap::Command {
+        command.subcommands(self.subcommands.iter().map(|subcommand| {
+            let mut built = clap::Command::new(subcommand.name()).args(subcommand.args());
+            if let Some(about) = subcommand.about() {
+                built = built.about(about);
+            }
+            built
+        }))
+    }
+
+    /// Run the subcommand matching the parsed arguments of a command built with
+    /// [command](Self::command). Returns `false` if no registered subcommand matched.
+    pub fn dispatch(&self, matches: &clap::ArgMatches) -> bool {
+        let Some((name, subcommand_matches)) = matches.subcommand() else {
+            return false;
+        };
+
+        match self
+            .subcommands
+            .iter()
+            .find(|subcommand| subcommand.name() == name)
+        {
+            Some(subcommand) => {
+                subcommand.run(subcommand_matches);
+                true
+            }
+            None => false,
+        }
+    }
+}
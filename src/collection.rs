@@ -0,0 +1,106 @@
+//! Collections of plain values submitted from any crate.
+//!
+//! Alongside registries of trait implementations, values of any type can be submitted with
+//! [submit](crate::submit), and gathered into a static declared with
+//! [collection](macro@crate::collection). Like registrations, values submitted in any crate linked
+//! into the binary are included.
+//!
+//! ```rust
+//! pub struct Route {
+//!     pub path: &'static str,
+//!     pub handler: fn() -> String,
+//! }
+//!
+//! fn index() -> String {
+//!     "index".to_string()
+//! }
+//!
+//! static INDEX: Route = Route {
+//!     path: "/",
+//!     handler: index,
+//! };
+//!
+//! traitreg::submit!(INDEX as Route);
+//! traitreg::submit!(Route { path: "/about", handler: || "about".to_string() } as Route);
+//!
+//! #[traitreg::collection(Route)]
+//! static ROUTES: () = ();
+//!
+//! let mut paths: Vec<_> = ROUTES.iter().map(|route| route.path).collect();
+//! paths.sort();
+//! assert_eq!(vec!["/", "/about"], paths);
+//! ```
+//!
+//! Submitted values are stored in statics, so must be `Sync`. Values are only collected by the
+//! collections of their exact type.
+
+use std::sync::Mutex;
+
+// Reference to a submitted value
+type SubmittedValue = fn() -> &'static dyn core::any::Any;
+
+// Every value submitted so far, in order of submission
+static SUBMITTED: Mutex<Vec<SubmittedValue>> = Mutex::new(vec![]);
+
+/// Values of a type submitted with [submit](crate::submit), see
+/// [collection](macro@crate::collection)
+pub struct Collection<T: 'static> {
+    values: Vec<&'static T>,
+}
+
+impl<T: Sync + 'static> Collection<T> {
+    pub(crate) fn build() -> Self {
+        use core::sync::atomic::Ordering;
+
+        let mut submitted = SUBMITTED.lock().expect("Traitreg internal mutex poisoned");
+
+        // Add pending submissions, in the order they were submitted
+        let mut pending = vec![];
+        let mut node = crate::__private::SUBMISSIONS.swap(core::ptr::null_mut(), Ordering::AcqRel);
+        while !node.is_null() {
+            // Safety: Nodes are only created from `&'static __private::Submission`
+            let submission: &'static crate::__private::Submission = unsafe { &*node };
+            pending.push(submission.value);
+            node = submission.next.load(Ordering::Relaxed);
+        }
+        submitted.extend(pending.into_iter().rev());
+
+        Self {
+            values: submitted
+                .iter()
+                .filter_map(|value| value().downcast_ref())
+                .collect(),
+        }
+    }
+}
+
+impl<T: 'static> Collection<T> {
+    /// Iterate over the submitted values
+    pub fn iter(&self) -> impl Iterator<Item = &'static T> + '_ {
+        self.values.iter().copied()
+    }
+
+    /// The submitted values as a slice
+    pub fn as_slice(&self) -> &[&'static T] {
+        &self.values
+    }
+
+    /// Number of submitted values
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Is the collection empty
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<'a, T: 'static> IntoIterator for &'a Collection<T> {
+    type Item = &'static T;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, &'static T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter().copied()
+    }
+}
@@ -0,0 +1,93 @@
+//! Enabling, disabling and reprioritizing registered implementations from configuration.
+//!
+//! A [RegistryConfig] is applied to a registry with
+//! [TraitRegStorage::apply_config](crate::TraitRegStorage::apply_config), which returns a copy of
+//! the registry with the configuration applied, so the set of implementations can be tuned
+//! without recompiling. Implementations are configured by
+//! [name](crate::RegisteredImplWrapper::name), names which are not in the registry are ignored.
+//! With the `serde` feature, configs can be deserialized, e.g. from TOML or JSON.
+//!
+//! ```rust
+//! use traitreg::config::{ImplConfig, RegistryConfig};
+//!
+//! trait Codec {}
+//!
+//! #[derive(Default)]
+//! struct Gzip;
+//!
+//! #[traitreg::register(default)]
+//! impl Codec for Gzip {}
+//!
+//! #[derive(Default)]
+//! struct Zstd;
+//!
+//! #[traitreg::register(default)]
+//! impl Codec for Zstd {}
+//!
+//! #[traitreg::registry(Codec)]
+//! static CODEC_REGISTRY: () = ();
+//!
+//! let mut config = RegistryConfig::default();
+//! config.implementations.insert(
+//!     "Gzip".to_string(),
+//!     ImplConfig {
+//!         enabled: Some(false),
+//!         priority: None,
+//!     },
+//! );
+//!
+//! let registry = CODEC_REGISTRY.apply_config(&config);
+//! assert_eq!(vec!["Zstd"], registry.iter_names().collect::<Vec<_>>());
+//! ```
+
+use std::collections::BTreeMap;
+
+/// Configuration of the implementations in a registry
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RegistryConfig {
+    /// Are implementations which are not configured, or do not set `enabled`, enabled. `true` by
+    /// default, so only listed implementations are used when `false`.
+    pub enabled: bool,
+    /// Configuration of implementations by name
+    pub implementations: BTreeMap<String, ImplConfig>,
+}
+
+impl Default for RegistryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            implementations: BTreeMap::new(),
+        }
+    }
+}
+
+impl RegistryConfig {
+    /// Is an implementation with this name enabled
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.implementations
+            .get(name)
+            .and_then(|config| config.enabled)
+            .unwrap_or(self.enabled)
+    }
+
+    /// The priority configured for an implementation with this name, if any
+    pub fn priority(&self, name: &str) -> Option<i32> {
+        self.implementations
+            .get(name)
+            .and_then(|config| config.priority)
+    }
+}
+
+/// Configuration of one implementation, see [RegistryConfig]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ImplConfig {
+    /// Enable or disable the implementation, otherwise [RegistryConfig::enabled] applies
+    pub enabled: Option<bool>,
+    /// Priority replacing the one registered, see
+    /// [RegisteredImplWrapper::priority](crate::RegisteredImplWrapper::priority)
+    pub priority: Option<i32>,
+}
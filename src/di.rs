@@ -0,0 +1,469 @@
+//! Dependency injection container built on registries. Requires the `di` feature.
+//!
+//! ```rust
+//! use traitreg::di::Container;
+//!
+//! trait Logger {
+//!     fn log(&self, message: &str) -> String;
+//! }
+//!
+//! #[derive(Default)]
+//! struct StdoutLogger;
+//!
+//! #[traitreg::register(default)]
+//! impl Logger for StdoutLogger {
+//!     fn log(&self, message: &str) -> String {
+//!         format!("stdout: {message}")
+//!     }
+//! }
+//!
+//! #[traitreg::registry(Logger)]
+//! static LOGGER_REGISTRY: () = ();
+//!
+//! let mut container = Container::new();
+//! container.add_registry(&LOGGER_REGISTRY);
+//!
+//! let logger = container.resolve::<dyn Logger>().unwrap();
+//! assert_eq!("stdout: hello", logger.log("hello"));
+//! ```
+//!
+//! Instances are resolved as `Arc<dyn Trait>`, since they may be shared depending on the
+//! [Lifetime] of the registered implementation. Lifetimes are set when registering an
+//! implementation, and default to transient.
+//!
+//! ```rust
+//! # use traitreg::di::Container;
+//! # trait Logger {}
+//! #[derive(Default)]
+//! struct StdoutLogger;
+//!
+//! #[traitreg::register(default, lifetime = singleton)]
+//! impl Logger for StdoutLogger {}
+//!
+//! # #[traitreg::registry(Logger)]
+//! # static LOGGER_REGISTRY: () = ();
+//! # let mut container = Container::new();
+//! # container.add_registry(&LOGGER_REGISTRY);
+//! let first = container.resolve::<dyn Logger>().unwrap();
+//! let second = container.resolve::<dyn Logger>().unwrap();
+//! assert!(std::sync::Arc::ptr_eq(&first, &second));
+//! ```
+
+use crate::{RegisteredImplWrapper, TraitRegStorage};
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Error resolving an instance from a [Container]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResolveError {
+    /// No registry or binding was added to the container for the trait object
+    MissingBinding {
+        /// The trait object type
+        trait_object: &'static str,
+    },
+    /// The registry for the trait object has no implementations with a constructor
+    NoImplementation {
+        /// The trait object type
+        trait_object: &'static str,
+    },
+    /// The registry for the trait object has more than one implementation with a constructor, and
+    /// no binding chooses between them
+    Ambiguous {
+        /// The trait object type
+        trait_object: &'static str,
+        /// Names of the implementations which could be resolved
+        candidates: Vec<&'static str>,
+    },
+    /// The implementation bound to the trait object is not in the registry, or has no constructor
+    UnknownImplementation {
+        /// The trait object type
+        trait_object: &'static str,
+        /// Name of the bound implementation
+        name: &'static str,
+    },
+    /// The implementation has the scoped lifetime, but was resolved outside of a [Scope]
+    MissingScope {
+        /// The trait object type
+        trait_object: &'static str,
+        /// Name of the implementation
+        name: &'static str,
+    },
+    /// The fallible constructor of the implementation failed
+    ConstructorFailed {
+        /// The trait object type
+        trait_object: &'static str,
+        /// Name of the implementation
+        name: &'static str,
+        /// The error returned by the constructor
+        message: String,
+    },
+    /// Constructors of implementations depend on each other in a cycle
+    Cycle {
+        /// Names of the implementations in the cycle, starting and ending with the same
+        /// implementation
+        cycle: Vec<&'static str>,
+    },
+}
+
+impl core::fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        match self {
+            Self::MissingBinding { trait_object } => {
+                write!(f, "No registry or binding for '{trait_object}'")
+            }
+            Self::NoImplementation { trait_object } => write!(
+                f,
+                "No implementations of '{trait_object}' were registered with a constructor"
+            ),
+            Self::Ambiguous {
+                trait_object,
+                candidates,
+            } => write!(
+                f,
+                "Multiple implementations of '{trait_object}' could be resolved, bind one of: {}",
+                candidates.join(", ")
+            ),
+            Self::UnknownImplementation { trait_object, name } => write!(
+                f,
+                "Implementation '{name}' bound to '{trait_object}' is not registered with a \
+                constructor"
+            ),
+            Self::MissingScope { trait_object, name } => write!(
+                f,
+                "Implementation '{name}' of '{trait_object}' is scoped and must be resolved \
+                through a scope"
+            ),
+            Self::ConstructorFailed {
+                trait_object,
+                name,
+                message,
+            } => write!(
+                f,
+                "Constructor of implementation '{name}' of '{trait_object}' failed: {message}"
+            ),
+            Self::Cycle { cycle } => {
+                write!(f, "Circular dependency between '{}'", cycle.join("' -> '"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+#[doc(hidden)]
+pub type __InjectFn<Trait> = fn(&Resolver) -> Result<Trait, ResolveError>;
+
+// Converts the return value of constructors registered with `inject`, which can return either
+// `Self` or `Result<Self, ResolveError>`
+#[doc(hidden)]
+pub trait __IntoResolved<T> {
+    fn __into_resolved(self) -> Result<T, ResolveError>;
+}
+
+impl<T> __IntoResolved<T> for T {
+    fn __into_resolved(self) -> Result<T, ResolveError> {
+        Ok(self)
+    }
+}
+
+impl<T> __IntoResolved<T> for Result<T, ResolveError> {
+    fn __into_resolved(self) -> Result<T, ResolveError> {
+        self
+    }
+}
+
+/// Lifetime of instances of a registered implementation resolved by a [Container], see
+/// [register](macro@crate::register) with the `lifetime` option.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Lifetime {
+    /// A new instance is constructed every time the implementation is resolved
+    #[default]
+    Transient,
+    /// One instance is constructed per container, and shared every time the implementation is
+    /// resolved
+    Singleton,
+    /// One instance is constructed per [Scope], and shared every time the implementation is
+    /// resolved through that scope
+    Scoped,
+}
+
+enum Binding<T: ?Sized> {
+    Implementation(&'static str),
+    Factory(Box<dyn Fn() -> Box<T>>),
+}
+
+// Shared instances are keyed by the trait object and registered type
+type Instances = RefCell<HashMap<(TypeId, TypeId), Box<dyn Any>>>;
+
+/// Dependency injection container. Resolves instances of trait objects from the registries and
+/// bindings which are added to it.
+///
+/// When a registry contains exactly one implementation with a constructor, that implementation
+/// is resolved. Otherwise an implementation must be chosen with
+/// [bind_implementation](Container::bind_implementation), or overridden entirely with
+/// [bind](Container::bind).
+#[derive(Default)]
+pub struct Container {
+    registries: HashMap<TypeId, &'static dyn Any>,
+    bindings: HashMap<TypeId, Box<dyn Any>>,
+    singletons: Instances,
+}
+
+impl Container {
+    /// Create an empty container
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a registry, which is used to resolve instances of its trait object
+    pub fn add_registry<T: ?Sized + 'static>(
+        &mut self,
+        registry: &'static TraitRegStorage<Box<T>>,
+    ) {
+        self.registries.insert(TypeId::of::<T>(), registry);
+    }
+
+    /// Resolve instances of a trait object with the registered implementation of the given name,
+    /// see [RegisteredImplWrapper::name](crate::RegisteredImplWrapper::name)
+    pub fn bind_implementation<T: ?Sized + 'static>(&mut self, name: &'static str) {
+        self.bindings.insert(
+            TypeId::of::<T>(),
+            Box::new(Binding::<T>::Implementation(name)),
+        );
+    }
+
+    /// Resolve instances of a trait object with a factory function, overriding any registry or
+    /// other binding. The factory is called every time the trait object is resolved.
+    pub fn bind<T: ?Sized + 'static>(&mut self, factory: impl Fn() -> Box<T> + 'static) {
+        self.bindings.insert(
+            TypeId::of::<T>(),
+            Box::new(Binding::<T>::Factory(Box::new(factory))),
+        );
+    }
+
+    /// Create a scope, which shares instances of implementations with the scoped lifetime
+    pub fn scope(&self) -> Scope<'_> {
+        Scope {
+            container: self,
+            instances: Instances::default(),
+        }
+    }
+
+    /// Resolve an instance of a trait object, e.g. `container.resolve::<dyn MyTrait>()`
+    pub fn resolve<T: ?Sized + 'static>(&self) -> Result<Arc<T>, ResolveError> {
+        self.resolver(None).resolve()
+    }
+
+    /// Resolve an instance of every implementation of a trait object which has a constructor
+    pub fn resolve_all<T: ?Sized + 'static>(&self) -> Result<Vec<Arc<T>>, ResolveError> {
+        self.resolver(None).resolve_all()
+    }
+
+    fn resolver<'a>(&'a self, scope: Option<&'a Scope<'a>>) -> Resolver<'a> {
+        Resolver {
+            container: self,
+            scope,
+            stack: RefCell::default(),
+        }
+    }
+
+    fn registry<T: ?Sized + 'static>(&self) -> Option<&'static TraitRegStorage<Box<T>>> {
+        self.registries
+            .get(&TypeId::of::<T>())
+            .and_then(|registry| registry.downcast_ref::<TraitRegStorage<Box<T>>>())
+    }
+}
+
+/// Scope of a [Container], created with [Container::scope]. Implementations with the scoped
+/// lifetime are constructed once per scope.
+pub struct Scope<'a> {
+    container: &'a Container,
+    instances: Instances,
+}
+
+impl Scope<'_> {
+    /// Resolve an instance of a trait object, e.g. `scope.resolve::<dyn MyTrait>()`
+    pub fn resolve<T: ?Sized + 'static>(&self) -> Result<Arc<T>, ResolveError> {
+        self.container.resolver(Some(self)).resolve()
+    }
+
+    /// Resolve an instance of every implementation of a trait object which has a constructor
+    pub fn resolve_all<T: ?Sized + 'static>(&self) -> Result<Vec<Arc<T>>, ResolveError> {
+        self.container.resolver(Some(self)).resolve_all()
+    }
+}
+
+/// Resolves dependencies of a constructor registered with `inject`, see
+/// [register](macro@crate::register). Constructors can return `Self`, or
+/// `Result<Self, ResolveError>` to pass on errors such as [ResolveError::Cycle] when the
+/// constructors of implementations depend on each other.
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use traitreg::di::{Container, Resolver};
+/// trait Database {}
+///
+/// #[derive(Default)]
+/// struct Postgres;
+///
+/// #[traitreg::register(default)]
+/// impl Database for Postgres {}
+///
+/// trait Service {}
+///
+/// struct UserService {
+///     database: Arc<dyn Database>,
+/// }
+///
+/// impl UserService {
+///     fn new(resolver: &Resolver) -> Self {
+///         Self {
+///             database: resolver.resolve().expect("Database is registered"),
+///         }
+///     }
+/// }
+///
+/// #[traitreg::register(new, inject)]
+/// impl Service for UserService {}
+///
+/// #[traitreg::registry(Database)]
+/// static DATABASE_REGISTRY: () = ();
+///
+/// #[traitreg::registry(Service)]
+/// static SERVICE_REGISTRY: () = ();
+///
+/// let mut container = Container::new();
+/// container.add_registry(&DATABASE_REGISTRY);
+/// container.add_registry(&SERVICE_REGISTRY);
+///
+/// assert!(container.resolve::<dyn Service>().is_ok());
+/// ```
+pub struct Resolver<'a> {
+    container: &'a Container,
+    scope: Option<&'a Scope<'a>>,
+    // Implementations currently being constructed, used to detect cycles
+    stack: RefCell<Vec<(TypeId, TypeId, &'static str)>>,
+}
+
+impl Resolver<'_> {
+    /// Resolve an instance of a trait object, e.g. `resolver.resolve::<dyn MyTrait>()`
+    pub fn resolve<T: ?Sized + 'static>(&self) -> Result<Arc<T>, ResolveError> {
+        let trait_object = core::any::type_name::<T>();
+
+        let binding = self
+            .container
+            .bindings
+            .get(&TypeId::of::<T>())
+            .and_then(|binding| binding.downcast_ref::<Binding<T>>());
+
+        if let Some(Binding::Factory(factory)) = binding {
+            return Ok(Arc::from(factory()));
+        }
+
+        let registry = self
+            .container
+            .registry::<T>()
+            .ok_or(ResolveError::MissingBinding { trait_object })?;
+
+        if let Some(Binding::Implementation(name)) = binding {
+            return registry
+                .iter()
+                .find(|item| item.name() == *name && item.has_constructor())
+                .map(|item| self.instantiate(item))
+                .unwrap_or(Err(ResolveError::UnknownImplementation {
+                    trait_object,
+                    name,
+                }));
+        }
+
+        let mut candidates = registry.iter().filter(|item| item.has_constructor());
+        match (candidates.next(), candidates.next()) {
+            (None, _) => Err(ResolveError::NoImplementation { trait_object }),
+            (Some(item), None) => self.instantiate(item),
+            (Some(_), Some(_)) => Err(ResolveError::Ambiguous {
+                trait_object,
+                candidates: registry
+                    .iter()
+                    .filter(|item| item.has_constructor())
+                    .map(|item| item.name())
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Resolve an instance of every implementation of a trait object which has a constructor
+    pub fn resolve_all<T: ?Sized + 'static>(&self) -> Result<Vec<Arc<T>>, ResolveError> {
+        let trait_object = core::any::type_name::<T>();
+
+        let registry = self
+            .container
+            .registry::<T>()
+            .ok_or(ResolveError::MissingBinding { trait_object })?;
+
+        registry
+            .iter()
+            .filter(|item| item.has_constructor())
+            .map(|item| self.instantiate(item))
+            .collect()
+    }
+
+    fn instantiate<T: ?Sized + 'static>(
+        &self,
+        item: &RegisteredImplWrapper<Box<T>>,
+    ) -> Result<Arc<T>, ResolveError> {
+        let trait_object = core::any::type_name::<T>();
+
+        let instances = match item.lifetime {
+            Lifetime::Transient => None,
+            Lifetime::Singleton => Some(&self.container.singletons),
+            Lifetime::Scoped => Some(
+                &self
+                    .scope
+                    .ok_or(ResolveError::MissingScope {
+                        trait_object,
+                        name: item.name(),
+                    })?
+                    .instances,
+            ),
+        };
+
+        let key = (TypeId::of::<T>(), item.type_id);
+        if let Some(instance) = instances.and_then(|instances| {
+            instances
+                .borrow()
+                .get(&key)?
+                .downcast_ref::<Arc<T>>()
+                .cloned()
+        }) {
+            return Ok(instance);
+        }
+
+        let mut stack = self.stack.borrow_mut();
+        if let Some(start) = stack.iter().position(|(t, i, _)| (*t, *i) == key) {
+            let mut cycle: Vec<&'static str> = stack[start..].iter().map(|(_, _, n)| *n).collect();
+            cycle.push(item.name());
+            return Err(ResolveError::Cycle { cycle });
+        }
+        stack.push((key.0, key.1, item.name()));
+        drop(stack);
+
+        // The instances are not borrowed while constructing, since constructors can resolve their
+        // own dependencies
+        let instance = item.instantiate_with(self, trait_object);
+        self.stack.borrow_mut().pop();
+
+        let instance: Arc<T> = instance?
+            .ok_or(ResolveError::NoImplementation { trait_object })?
+            .into();
+
+        if let Some(instances) = instances {
+            instances
+                .borrow_mut()
+                .insert(key, Box::new(instance.clone()));
+        }
+
+        Ok(instance)
+    }
+}
@@ -0,0 +1,129 @@
+//! Health checks of registered implementations.
+//!
+//! Implementations can be registered with a `health_check` function, which reports whether the
+//! implementation is working without instantiating it.
+//! [health_check_all](crate::TraitRegStorage::health_check_all) runs the health checks of every
+//! implementation in a registry.
+//!
+//! ```rust
+//! use traitreg::health::HealthStatus;
+//!
+//! trait Plugin {}
+//!
+//! struct Database;
+//!
+//! fn database_health() -> HealthStatus {
+//!     HealthStatus::Degraded("replica lagging".to_string())
+//! }
+//!
+//! #[traitreg::register(health_check = database_health)]
+//! impl Plugin for Database {}
+//!
+//! struct Cache;
+//!
+//! #[traitreg::register]
+//! impl Plugin for Cache {}
+//!
+//! #[traitreg::registry(Plugin)]
+//! static PLUGIN_REGISTRY: () = ();
+//!
+//! let report = PLUGIN_REGISTRY.health_check_all();
+//! assert_eq!(1, report.checks().len());
+//! assert!(report.status().is_degraded());
+//! ```
+
+/// Health of an implementation, returned by its `health_check` function
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum HealthStatus {
+    /// Working normally
+    Healthy,
+    /// Working, but with a problem described by the message
+    Degraded(String),
+    /// Not working, for the reason described by the message
+    Unhealthy(String),
+}
+
+impl HealthStatus {
+    /// Is the status [Healthy](HealthStatus::Healthy)
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Self::Healthy)
+    }
+
+    /// Is the status [Degraded](HealthStatus::Degraded)
+    pub fn is_degraded(&self) -> bool {
+        matches!(self, Self::Degraded(_))
+    }
+
+    /// Is the status [Unhealthy](HealthStatus::Unhealthy)
+    pub fn is_unhealthy(&self) -> bool {
+        matches!(self, Self::Unhealthy(_))
+    }
+
+    // Ordering from best to worst, for finding the worst status in a report
+    fn severity(&self) -> u8 {
+        match self {
+            Self::Healthy => 0,
+            Self::Degraded(_) => 1,
+            Self::Unhealthy(_) => 2,
+        }
+    }
+}
+
+/// Result of the health check of one implementation
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HealthCheck {
+    /// The type name, see [RegisteredImplWrapper::name](crate::RegisteredImplWrapper::name)
+    pub name: &'static str,
+    /// The type path, see [RegisteredImplWrapper::path](crate::RegisteredImplWrapper::path)
+    pub path: &'static str,
+    /// The status returned by the health check
+    pub status: HealthStatus,
+}
+
+/// Results of the health checks of every implementation in a registry which has one, in registry
+/// order
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HealthReport {
+    pub(crate) checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    /// Results of each health check
+    pub fn checks(&self) -> &[HealthCheck] {
+        &self.checks
+    }
+
+    /// The worst status of any health check, [Healthy](HealthStatus::Healthy) if there are none
+    pub fn status(&self) -> HealthStatus {
+        self.checks
+            .iter()
+            .map(|check| &check.status)
+            .max_by_key(|status| status.severity())
+            .cloned()
+            .unwrap_or(HealthStatus::Healthy)
+    }
+
+    /// Are all health checks [Healthy](HealthStatus::Healthy)
+    pub fn is_healthy(&self) -> bool {
+        self.checks.iter().all(|check| check.status.is_healthy())
+    }
+
+    /// Health checks which are not [Healthy](HealthStatus::Healthy)
+    pub fn failing(&self) -> impl Iterator<Item = &HealthCheck> {
+        self.checks
+            .iter()
+            .filter(|check| !check.status.is_healthy())
+    }
+}
+
+impl<'a> IntoIterator for &'a HealthReport {
+    type Item = &'a HealthCheck;
+    type IntoIter = core::slice::Iter<'a, HealthCheck>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.checks.iter()
+    }
+}
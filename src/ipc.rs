@@ -0,0 +1,416 @@
+//! Plugins hosted in a child process. Requires the `ipc` feature.
+//!
+//! Registered implementations of a trait with [Service] as a supertrait can be served from a child
+//! process with [serve]. The host spawns the child with [PluginProcess::spawn], which mirrors the
+//! registry of the child, and instantiates implementations as proxies which forward every call to
+//! the child. A plugin which crashes, panics or misbehaves only affects its own process, so this is
+//! suitable for plugins which are not trusted to be loaded into the host.
+//!
+//! ```rust,no_run
+//! use traitreg::ipc::{Error, PluginProcess, Service};
+//!
+//! trait Plugin: Service {}
+//!
+//! #[derive(Default)]
+//! struct Echo;
+//!
+//! impl Service for Echo {
+//!     fn call(&self, request: &[u8]) -> Result<Vec<u8>, Error> {
+//!         Ok(request.to_vec())
+//!     }
+//! }
+//!
+//! #[traitreg::register(default)]
+//! impl Plugin for Echo {}
+//!
+//! #[traitreg::registry(Plugin)]
+//! static PLUGIN_REGISTRY: () = ();
+//!
+//! // In the child process
+//! if std::env::args().any(|arg| arg == "--plugin") {
+//!     traitreg::ipc::serve(&PLUGIN_REGISTRY).unwrap();
+//!     return;
+//! }
+//!
+//! // In the host process
+//! let mut command = std::process::Command::new(std::env::current_exe().unwrap());
+//! command.arg("--plugin");
+//! let plugins = PluginProcess::spawn(command).unwrap();
+//! for entry in plugins.iter() {
+//!     let echo = entry.instantiate().unwrap();
+//!     assert_eq!(b"hello".to_vec(), echo.call(b"hello").unwrap());
+//! }
+//! ```
+//!
+//! Requests and responses are bytes, how they are encoded is up to the trait. The host and child
+//! communicate over the standard input and output of the child, so the child must not write
+//! anything else to its standard output.
+
+use crate::TraitRegStorage;
+use std::collections::HashMap;
+use std::io::{BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+const OP_LIST: u8 = 0;
+const OP_INSTANTIATE: u8 = 1;
+const OP_CALL: u8 = 2;
+const OP_DROP: u8 = 3;
+
+const STATUS_OK: u8 = 0;
+const STATUS_FAILED: u8 = 1;
+
+const FIELD_SEPARATOR: char = '\x1f';
+const RECORD_TERMINATOR: char = '\x1e';
+// Longest message which is read, so a misbehaving process can not exhaust the memory of the other
+const MAX_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+/// Error handling a request through a [Service]
+#[derive(Debug)]
+pub enum Error {
+    /// The service failed to handle the request
+    Failed(String),
+    /// Communication with the plugin process failed
+    Io(std::io::Error),
+    /// The plugin process sent an invalid message
+    Protocol(&'static str),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        match self {
+            Self::Failed(message) => write!(f, "{message}"),
+            Self::Io(error) => write!(f, "Failed to communicate with plugin process: {error}"),
+            Self::Protocol(message) => write!(f, "Invalid message from plugin process: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Failed(_) | Self::Protocol(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Service which can be called from another process
+pub trait Service {
+    /// Handle a request
+    fn call(&self, request: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+fn write_message(writer: &mut impl Write, kind: u8, payload: &[&[u8]]) -> std::io::Result<()> {
+    let len: usize = payload.iter().map(|part| part.len()).sum();
+    let len = u32::try_from(len).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+    writer.write_all(&[kind])?;
+    writer.write_all(&len.to_le_bytes())?;
+    for part in payload {
+        writer.write_all(part)?;
+    }
+    writer.flush()
+}
+
+// Returns `None` at the end of the stream
+fn read_message(reader: &mut impl Read) -> Result<Option<(u8, Vec<u8>)>, Error> {
+    let mut kind = [0];
+    if reader.read(&mut kind)? == 0 {
+        return Ok(None);
+    }
+
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_MESSAGE_LEN {
+        return Err(Error::Protocol("message too long"));
+    }
+
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload)?;
+    Ok(Some((kind[0], payload)))
+}
+
+// Split the instance id, or the index of an implementation in the registry, from a payload
+fn split_id(payload: &[u8]) -> Result<(u64, &[u8]), Error> {
+    if payload.len() < 8 {
+        return Err(Error::Protocol("missing id"));
+    }
+    let (id, rest) = payload.split_at(8);
+    Ok((u64::from_le_bytes(id.try_into().unwrap()), rest))
+}
+
+fn panic_message(payload: Box<dyn core::any::Any + Send>) -> String {
+    match payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or(payload.downcast_ref::<String>().map(String::as_str))
+    {
+        Some(message) => format!("Plugin panicked: {message}"),
+        None => "Plugin panicked".to_string(),
+    }
+}
+
+/// Serve the implementations in a registry to the host process over standard input and output,
+/// until the host closes standard input
+pub fn serve<T: ?Sized + Service>(registry: &TraitRegStorage<Box<T>>) -> std::io::Result<()> {
+    serve_on(registry, std::io::stdin().lock(), std::io::stdout().lock())
+}
+
+/// Serve the implementations in a registry to a host, reading requests from `reader` and writing
+/// responses to `writer`, until `reader` is closed
+pub fn serve_on<T: ?Sized + Service>(
+    registry: &TraitRegStorage<Box<T>>,
+    mut reader: impl Read,
+    mut writer: impl Write,
+) -> std::io::Result<()> {
+    let mut instances: HashMap<u64, Box<T>> = HashMap::new();
+    let mut next_id = 0u64;
+
+    loop {
+        let (op, payload) = match read_message(&mut reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => return Ok(()),
+            Err(Error::Io(error)) => return Err(error),
+            Err(error) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
+        };
+
+        let response: Result<Vec<u8>, String> = match op {
+            OP_LIST => Ok(registry
+                .iter()
+                .map(|item| {
+                    format!(
+                        "{}{FIELD_SEPARATOR}{}{FIELD_SEPARATOR}{}{FIELD_SEPARATOR}{}{RECORD_TERMINATOR}",
+                        item.name(),
+                        item.path(),
+                        item.trait_name(),
+                        u8::from(item.has_constructor()),
+                    )
+                })
+                .collect::<String>()
+                .into_bytes()),
+            // Implementations are requested by their index in the registry, since neither names
+            // nor paths are unique
+            OP_INSTANTIATE => match split_id(&payload) {
+                Ok((index, _)) => match registry.iter().nth(index as usize) {
+                    Some(item) => {
+                        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                            item.instantiate()
+                        })) {
+                            Ok(Some(instance)) => {
+                                next_id += 1;
+                                instances.insert(next_id, instance);
+                                Ok(next_id.to_le_bytes().to_vec())
+                            }
+                            Ok(None) => Err(format!(
+                                "Implementation '{}' has no constructor",
+                                item.path()
+                            )),
+                            Err(payload) => Err(panic_message(payload)),
+                        }
+                    }
+                    None => Err(format!("Unknown implementation {index}")),
+                },
+                Err(error) => Err(error.to_string()),
+            },
+            OP_CALL => match split_id(&payload) {
+                Ok((id, request)) => match instances.get(&id) {
+                    Some(instance) => {
+                        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                            instance.call(request)
+                        })) {
+                            Ok(response) => response.map_err(|error| error.to_string()),
+                            Err(payload) => Err(panic_message(payload)),
+                        }
+                    }
+                    None => Err(format!("Unknown instance {id}")),
+                },
+                Err(error) => Err(error.to_string()),
+            },
+            OP_DROP => match split_id(&payload) {
+                Ok((id, _)) => {
+                    instances.remove(&id);
+                    Ok(vec![])
+                }
+                Err(error) => Err(error.to_string()),
+            },
+            _ => Err(format!("Unknown request {op}")),
+        };
+
+        match response {
+            Ok(response) => write_message(&mut writer, STATUS_OK, &[&response])?,
+            Err(message) => write_message(&mut writer, STATUS_FAILED, &[message.as_bytes()])?,
+        }
+    }
+}
+
+struct Connection {
+    child: Mutex<Child>,
+    pipes: Mutex<(ChildStdin, BufReader<ChildStdout>)>,
+}
+
+impl Connection {
+    fn request(&self, op: u8, payload: &[&[u8]]) -> Result<Vec<u8>, Error> {
+        let mut pipes = self
+            .pipes
+            .lock()
+            .map_err(|_| Error::Protocol("connection poisoned"))?;
+        let (stdin, stdout) = &mut *pipes;
+        write_message(stdin, op, payload)?;
+
+        match read_message(stdout)? {
+            Some((STATUS_OK, response)) => Ok(response),
+            Some((STATUS_FAILED, message)) => Err(Error::Failed(
+                String::from_utf8_lossy(&message).into_owned(),
+            )),
+            Some(_) => Err(Error::Protocol("unknown status")),
+            None => Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into())),
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        if let Ok(child) = self.child.get_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Child process serving registered implementations, see [serve]. The process is killed once the
+/// `PluginProcess` and every instance created from it have been dropped.
+pub struct PluginProcess {
+    entries: Vec<RemoteEntry>,
+}
+
+impl PluginProcess {
+    /// Spawn a child process, and read the implementations in its registry. Standard input and
+    /// output of the command are replaced with pipes to the host.
+    pub fn spawn(mut command: Command) -> Result<Self, Error> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().ok_or(Error::Protocol("missing stdin"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or(Error::Protocol("missing stdout"))?;
+        let connection = Arc::new(Connection {
+            child: Mutex::new(child),
+            pipes: Mutex::new((stdin, BufReader::new(stdout))),
+        });
+
+        let list = connection.request(OP_LIST, &[])?;
+        let list = String::from_utf8(list).map_err(|_| Error::Protocol("invalid registry"))?;
+        let entries = list
+            .split_terminator(RECORD_TERMINATOR)
+            .enumerate()
+            .map(|(index, record)| {
+                let mut fields = record.split(FIELD_SEPARATOR).map(str::to_string);
+                let mut field = || fields.next().ok_or(Error::Protocol("invalid registry"));
+                Ok(RemoteEntry {
+                    index: index as u64,
+                    name: field()?,
+                    path: field()?,
+                    trait_name: field()?,
+                    has_constructor: field()? == "1",
+                    connection: connection.clone(),
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self { entries })
+    }
+
+    /// Iterate over implementations registered in the child process
+    pub fn iter(&self) -> core::slice::Iter<'_, RemoteEntry> {
+        self.entries.iter()
+    }
+}
+
+/// Implementation registered in a [PluginProcess]
+pub struct RemoteEntry {
+    // Index of the implementation in the registry of the child process
+    index: u64,
+    name: String,
+    path: String,
+    trait_name: String,
+    has_constructor: bool,
+    connection: Arc<Connection>,
+}
+
+impl RemoteEntry {
+    /// Instantiate the implementation in the child process, returning a proxy which forwards calls
+    /// to the instance
+    pub fn instantiate(&self) -> Result<Box<dyn Service + Send + Sync>, Error> {
+        let id = self
+            .connection
+            .request(OP_INSTANTIATE, &[&self.index.to_le_bytes()])?;
+        let id = u64::from_le_bytes(
+            id.try_into()
+                .map_err(|_| Error::Protocol("invalid instance id"))?,
+        );
+
+        Ok(Box::new(RemoteInstance {
+            id,
+            connection: self.connection.clone(),
+        }))
+    }
+
+    /// Was this type registered with a constructor
+    pub fn has_constructor(&self) -> bool {
+        self.has_constructor
+    }
+
+    /// The type name, see [RegisteredImplWrapper::name](crate::RegisteredImplWrapper::name)
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The type path, see [RegisteredImplWrapper::path](crate::RegisteredImplWrapper::path)
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The trait name
+    pub fn trait_name(&self) -> &str {
+        &self.trait_name
+    }
+}
+
+impl core::fmt::Debug for RemoteEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        f.debug_struct("RemoteEntry")
+            .field("Type Name", &self.name)
+            .field("Type Path", &self.path)
+            .field("Trait Name", &self.trait_name)
+            .field("Has Constructor", &self.has_constructor)
+            .finish()
+    }
+}
+
+struct RemoteInstance {
+    id: u64,
+    connection: Arc<Connection>,
+}
+
+impl Service for RemoteInstance {
+    fn call(&self, request: &[u8]) -> Result<Vec<u8>, Error> {
+        self.connection
+            .request(OP_CALL, &[&self.id.to_le_bytes(), request])
+    }
+}
+
+impl Drop for RemoteInstance {
+    fn drop(&mut self) {
+        let _ = self.connection.request(OP_DROP, &[&self.id.to_le_bytes()]);
+    }
+}
@@ -57,11 +57,93 @@
 //! for reg in MYTRAIT_REGISTRY.iter() {
 //!     println!("{reg:#?}");
 //!
-//!     // Instanciate
-//!     let instance: Option<Box<dyn MyTrait>> = reg.instanciate();
+//!     // Instantiate
+//!     let instance: Option<Box<dyn MyTrait>> = reg.instantiate();
 //! }
 //! ```
 //!
+//! Types which already implement a trait can be registered together with [register_many!].
+//!
+//! ```rust
+//! # trait Codec {}
+//! # #[derive(Default)]
+//! # struct Gzip;
+//! # impl Codec for Gzip {}
+//! # #[derive(Default)]
+//! # struct Zstd;
+//! # impl Codec for Zstd {}
+//! traitreg::register_many!(Codec[default]: Gzip, Zstd);
+//! # #[traitreg::registry(Codec)]
+//! # static CODEC_REGISTRY: () = ();
+//! # fn main() { assert_eq!(2, CODEC_REGISTRY.len()); }
+//! ```
+//!
+//! Registries can be declared `pub` in a library and used by any crate which depends on it.
+//! Implementations registered in those crates are included in the registry.
+//!
+//! ```rust,ignore
+//! // In the library crate
+//! pub trait Plugin {}
+//!
+//! #[traitreg::registry(Plugin)]
+//! pub static PLUGIN_REGISTRY: () = ();
+//!
+//! // In a crate which depends on the library
+//! struct MyPlugin;
+//!
+//! #[traitreg::register]
+//! impl library::Plugin for MyPlugin {}
+//!
+//! fn main() {
+//!     for plugin in library::PLUGIN_REGISTRY.iter() {
+//!         println!("{}", plugin.name());
+//!     }
+//! }
+//! ```
+//!
+//! Plain values can be collected across crates in the same way, with [submit!] and a
+//! [collection](macro@collection).
+//!
+//! ```rust
+//! struct Route(&'static str);
+//!
+//! traitreg::submit!(Route("/") as Route);
+//!
+//! #[traitreg::collection(Route)]
+//! static ROUTES: () = ();
+//!
+//! for route in ROUTES.iter() {
+//!     println!("{}", route.0);
+//! }
+//! ```
+//!
+//! ### Optional Features
+//!
+//! * `dyn-clone`: Clone instances of registered implementations of traits with
+//!   [`DynClone`](https://docs.rs/dyn-clone) as a supertrait.
+//! * `di`: A dependency injection container in `traitreg::di` which resolves trait
+//!   objects from registries, with bindings to choose or override implementations.
+//! * `tower`: Compose registered layers into a `tower::Layer` stack in order of priority with
+//!   `traitreg::tower::RegisteredLayers`, for assembling axum or tonic middleware from plugins.
+//! * `clap`: Build `clap` subcommands from registered implementations and dispatch to them with
+//!   `traitreg::clap::RegisteredSubcommands`.
+//! * `bevy`: Turn registered bevy plugins into a `PluginGroup` ordered by priority with
+//!   `traitreg::bevy::RegisteredPlugins`.
+//! * `hot-reload`: Load registrations from `cdylib` plugins into a
+//!   `traitreg::reload::ReloadableRegistry`, and reload them when the plugin is rebuilt.
+//! * `ipc`: Host plugins in a child process with `traitreg::ipc::serve`, and call them from the
+//!   host through proxies created by `traitreg::ipc::PluginProcess`.
+//! * `metrics`: Count instantiations of each registered implementation, read with
+//!   `RegisteredImplWrapper::instantiation_count` or `traitreg::manifest::instantiation_counts`.
+//! * `bumpalo`: Instantiate registered implementations in a `bumpalo::Bump` arena with
+//!   `RegisteredImplWrapper::instantiate_in`.
+//! * `rayon`: Instantiate every registered implementation in parallel with
+//!   `TraitRegStorage::par_instantiate_all`.
+//! * `env-filter`: Leave implementations out of registries when they are built, by listing them in
+//!   the `TRAITREG_DISABLE` environment variable, e.g. `MyTrait::NoisyPlugin,OtherTrait::*`.
+//! * `serde`: Serialize the metadata of registered implementations and manifest entries, and
+//!   deserialize `traitreg::config::RegistryConfig`.
+//!
 //! ### Implementation Details
 //!
 //! The registry is built during startup by methods called by the linker, before `main()` is
@@ -71,7 +153,12 @@
 //! Notably multiple crates (i.e. compilation units) can register implementations independently,
 //! the registry will pick up all of the impls automatically at runtime. This can be useful for a
 //! plugin system where shared libraries (`cdylib` crates) are loaded. Currently loading shared
-//! libraries manually after `main()` is called will not update the registry.
+//! libraries manually after `main()` is called will not update the registry, the `hot-reload`
+//! feature can load and reload registrations from plugins separately.
+//!
+//! Registrations are pushed to a lock-free list as they run, and added to the registry in a single
+//! batch when the first registry is built, so startup time does not suffer from lock contention with
+//! many registered implementations.
 //!
 //! It is possible to build a registry like this purely at compile time using procedural macros
 //! but as far as I am aware this is unsound. Each proc macro invocation currently reuses the same
@@ -99,144 +186,2478 @@
 // https://docs.rs/bevy_type_registry/0.3.0/bevy_type_registry/
 // https://github.com/DouglasDwyer/wings/tree/master
 
-pub use traitreg_macros::{register, registry};
+pub use traitreg_macros::{
+    collection, for_each_registered, register, register_many, registry, static_dispatch, submit,
+};
+
+#[doc(hidden)]
+#[path = "private.rs"]
+pub mod __private;
+pub mod arena;
+#[cfg(feature = "bevy")]
+pub mod bevy;
+#[cfg(feature = "clap")]
+pub mod clap;
+pub mod collection;
+pub mod config;
+#[cfg(feature = "di")]
+pub mod di;
+pub mod health;
+#[cfg(feature = "ipc")]
+pub mod ipc;
+pub mod manifest;
+pub mod pipeline;
+pub mod query;
+#[cfg(feature = "hot-reload")]
+pub mod reload;
+pub mod select;
+pub mod snapshot;
+pub mod stats;
+#[cfg(feature = "tower")]
+pub mod tower;
 
 static __TRAITREG_REGISTRY: std::sync::Mutex<Vec<RegisteredImplWrapper<Box<u32>>>> =
     std::sync::Mutex::new(vec![]);
 
-#[doc(hidden)]
-pub trait RegisteredImpl<Trait> {
-    const INSTANCIATE: fn() -> Option<Trait>;
-    const HAS_CONSTRUCTOR: bool;
-    const NAME: &'static str;
-    const PATH: &'static str;
-    const FILE: &'static str;
-    const MODULE_PATH: &'static str;
-    const TRAIT_NAME: &'static str;
+// The registry once `freeze` has been called, read without locking. Set while holding the lock of
+// `__TRAITREG_REGISTRY`, which is left empty.
+static __TRAITREG_FROZEN: std::sync::OnceLock<Vec<RegisteredImplWrapper<Box<u32>>>> =
+    std::sync::OnceLock::new();
+
+// Registrations which panicked while being added to the registry, see `registration_panics`, and
+// whether each has been handled by the failure policy of a registry
+static __TRAITREG_PANICS: std::sync::Mutex<Vec<(RegistrationPanic, bool)>> =
+    std::sync::Mutex::new(vec![]);
+
+// The global failure policy, see `set_failure_policy`
+static __TRAITREG_FAILURE_POLICY: core::sync::atomic::AtomicU8 =
+    core::sync::atomic::AtomicU8::new(FailurePolicy::Log as u8);
+
+// Trait names and required auto traits of the registries declared with `registry` which have been
+// built. Locked after `__TRAITREG_REGISTRY` when both are held.
+static __TRAITREG_DECLARED: std::sync::Mutex<Vec<DeclaredRegistry>> = std::sync::Mutex::new(vec![]);
+
+// Trait name, namespace, required auto traits, generic arguments and associated type bindings of a
+// registry, see `RegisteredImplWrapper::belongs_in`
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct DeclaredRegistry {
+    trait_: &'static str,
+    namespace: Option<&'static str>,
+    auto_traits: __private::AutoTraits,
+    bindings: Option<core::any::TypeId>,
 }
 
-#[doc(hidden)]
-pub fn __register_impl<Trait, Type: RegisteredImpl<Trait>>() {
-    let wrapper = RegisteredImplWrapper::<Trait> {
-        instanciate: Type::INSTANCIATE,
-        has_constructor: Type::HAS_CONSTRUCTOR,
-        name: Type::NAME,
-        path: Type::PATH,
-        file: Type::FILE,
-        module_path: Type::MODULE_PATH,
-        trait_name: Type::TRAIT_NAME,
+// Implementations of the registries which have been built, shared by every registry of the same
+// trait and instance type, including those from `dynamic_registry`. Entries are leaked, so
+// registries hold `&'static` references to them rather than copies. Locked after
+// `__TRAITREG_REGISTRY` when both are held.
+static __TRAITREG_SHARED: std::sync::Mutex<Vec<SharedImpls>> = std::sync::Mutex::new(vec![]);
+
+// Hooks installed with `on_register`. Locked after `__TRAITREG_REGISTRY` when both are held.
+static __TRAITREG_REGISTER_HOOKS: std::sync::Mutex<RegisterHooks> =
+    std::sync::Mutex::new(RegisterHooks {
+        hooks: vec![],
+        notified: 0,
+    });
+
+// Set once the first hook is installed with `on_register`, so registrations made without any hooks
+// do not lock `__TRAITREG_REGISTER_HOOKS`
+static __TRAITREG_HAS_REGISTER_HOOKS: core::sync::atomic::AtomicBool =
+    core::sync::atomic::AtomicBool::new(false);
+
+type RegisterHook = std::sync::Arc<dyn Fn(&RegisteredImplWrapper<MetaOnly>) + Send + Sync>;
+
+struct RegisterHooks {
+    hooks: Vec<RegisterHook>,
+    // Number of registrations every hook has been called for, registrations are only ever added
+    notified: usize,
+}
+
+// Conflicts found when a registry was built, and whether each has been handled by the failure
+// policy of a registry, so each is only handled once
+static __TRAITREG_CONFLICTS: std::sync::Mutex<Vec<(Conflict, bool)>> =
+    std::sync::Mutex::new(vec![]);
+
+// Implementations shared between registries, with the type of the instances erased
+struct SharedImpls {
+    key: SharedKey,
+    impls: &'static [RegisteredImplWrapper<Box<u32>>],
+}
+
+// Registries built with the same key contain the same implementations
+#[derive(PartialEq, Eq)]
+struct SharedKey {
+    registry: DeclaredRegistry,
+    instance_type: core::any::TypeId,
+    sorted: bool,
+    // Conflicting implementations are left out with the `Skip` failure policy
+    skip_conflicts: bool,
+    // Registrations are only ever added, so a registry built after more registrations are added,
+    // e.g. when a library is loaded, is not shared with those built before
+    registrations: usize,
+}
+
+/// Conversion of a trait object into a trait object of a supertrait, implemented by the `upcast`
+/// option on [registry](macro@registry)
+pub trait Upcast<Base: ?Sized> {
+    /// Convert the trait object
+    fn upcast(self: Box<Self>) -> Box<Base>;
+}
+
+/// Signature of the factory functions of a registry, implemented for the trait object type by the
+/// `factory` option on [registry](macro@registry)
+pub trait Factory {
+    /// Function pointer type of the factories
+    type Fn: Copy;
+
+    #[doc(hidden)]
+    fn __erase(factory: Self::Fn) -> __private::ErasedFactory;
+
+    #[doc(hidden)]
+    unsafe fn __restore(factory: __private::ErasedFactory) -> Self::Fn;
+}
+
+/// Arguments of the constructors in a registry, implemented for the trait object type by the
+/// `args` option on [registry](macro@registry)
+pub trait ConstructorArgs {
+    /// Tuple of the arguments
+    type Args<'a>;
+
+    #[doc(hidden)]
+    fn __erase(constructor: __private::ArgsConstructor<Self>) -> __private::ErasedFactory;
+
+    #[doc(hidden)]
+    unsafe fn __call(
+        constructor: __private::ErasedFactory,
+        args: Self::Args<'_>,
+    ) -> Option<Result<Box<Self>, ConstructError>>;
+}
+
+/// Asynchronous initialization of a registered type after it is constructed, for implementations
+/// registered with the `async_init` option on [register](macro@register). Run for every
+/// implementation in a registry by [TraitRegStorage::init_all].
+pub trait AsyncInit {
+    /// Error returned when initialization fails
+    type Error: Into<Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Initialize the instance, e.g. opening connections or loading assets
+    fn init(&mut self) -> impl core::future::Future<Output = Result<(), Self::Error>>;
+}
+
+// Every registration, either frozen or locked with pending registrations added
+enum Registrations {
+    Frozen(&'static [RegisteredImplWrapper<Box<u32>>]),
+    Locked(std::sync::MutexGuard<'static, Vec<RegisteredImplWrapper<Box<u32>>>>),
+}
+
+impl core::ops::Deref for Registrations {
+    type Target = [RegisteredImplWrapper<Box<u32>>];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Frozen(frozen) => frozen,
+            Self::Locked(registry_ref) => registry_ref,
+        }
+    }
+}
+
+// Access every registration, only locking the registry if it has not been frozen
+fn registrations() -> Registrations {
+    if let Some(frozen) = __TRAITREG_FROZEN.get() {
+        return Registrations::Frozen(frozen);
+    }
+
+    let mut registry_ref = __TRAITREG_REGISTRY
+        .lock()
+        .expect("Traitreg internal mutex poisoned");
+    // The registry may have been frozen while waiting for the lock
+    if let Some(frozen) = __TRAITREG_FROZEN.get() {
+        return Registrations::Frozen(frozen);
+    }
+    drain_pending(&mut registry_ref);
+    Registrations::Locked(registry_ref)
+}
+
+// Add pending registrations to the registry, in the order they were submitted
+fn drain_pending(registry: &mut Vec<RegisteredImplWrapper<Box<u32>>>) {
+    use core::sync::atomic::Ordering;
+
+    let mut pending = vec![];
+    let mut node = __private::PENDING.swap(core::ptr::null_mut(), Ordering::AcqRel);
+    while !node.is_null() {
+        // Safety: Nodes are only created from `&'static __private::Registration`
+        let registration: &'static __private::Registration = unsafe { &*node };
+        pending.push(registration);
+        node = registration.next.load(Ordering::Relaxed);
+    }
+
+    for registration in pending.into_iter().rev() {
+        registration.register_catching(registry);
+    }
+}
+
+/// Run the shutdown hooks of registered implementations, declared with `shutdown` on
+/// [register](macro@register). Hooks run in order of priority, lowest first, and implementations
+/// with the same priority in reverse order of registration, so teardown is the reverse of startup
+/// through [TraitRegStorage::iter_by_priority]. Each hook runs at most once, calling this again
+/// only runs the hooks of implementations registered since.
+pub fn shutdown_all() {
+    // Hooks of a frozen registry cannot be taken, they all run the first time this is called after
+    // freezing, apart from those which already ran before
+    static SHUTDOWN_FROZEN: core::sync::atomic::AtomicBool =
+        core::sync::atomic::AtomicBool::new(false);
+
+    let mut hooks = match registrations() {
+        Registrations::Frozen(frozen) => {
+            if SHUTDOWN_FROZEN.swap(true, core::sync::atomic::Ordering::AcqRel) {
+                return;
+            }
+            frozen
+                .iter()
+                .rev()
+                .filter_map(|item| Some((item.priority, item.shutdown?)))
+                .collect::<Vec<_>>()
+        }
+        Registrations::Locked(mut registry_ref) => registry_ref
+            .iter_mut()
+            .rev()
+            .filter_map(|item| Some((item.priority, item.shutdown.take()?)))
+            .collect::<Vec<_>>(),
     };
 
-    // Safety: Access to this type would be UB, but we only access this value after transmuting it
-    // back to the original type. In the mean time storing a fn ptr with a different signature will
-    // not modify the memory layout of RegisteredImplWrapper, so it is safe to store in a Vec.
-    let wrapper: RegisteredImplWrapper<Box<u32>> = unsafe { core::mem::transmute(wrapper) };
+    // Hooks run without holding the lock, so they can use registries
+    hooks.sort_by_key(|(priority, _)| *priority);
+    for (_, hook) in hooks {
+        hook();
+    }
+}
+
+/// Install a hook called for every registration, e.g. to wire plugins into another framework as
+/// they appear. The hook is called straight away for every registration made so far, then for each
+/// one made later, for example by a library loaded at runtime, as it is added. Registrations made
+/// after [freeze] are not added, so are never passed to hooks.
+///
+/// Hooks receive the metadata of the implementation, as in [dynamic_registry], and are called
+/// without holding any lock, so they can build registries.
+///
+/// ```rust
+/// use std::sync::Mutex;
+///
+/// trait Plugin {}
+///
+/// struct MyPlugin;
+///
+/// #[traitreg::register]
+/// impl Plugin for MyPlugin {}
+///
+/// static SEEN: Mutex<Vec<&str>> = Mutex::new(vec![]);
+///
+/// traitreg::on_register(|item| {
+///     if item.trait_name() == "Plugin" {
+///         SEEN.lock().unwrap().push(item.name());
+///     }
+/// });
+/// assert_eq!(vec!["MyPlugin"], *SEEN.lock().unwrap());
+/// ```
+pub fn on_register(hook: impl Fn(&RegisteredImplWrapper<MetaOnly>) + Send + Sync + 'static) {
+    let hook: RegisterHook = std::sync::Arc::new(hook);
+    let registrations = registrations();
+    let mut hooks = __TRAITREG_REGISTER_HOOKS
+        .lock()
+        .expect("Traitreg internal mutex poisoned");
+    // Registrations the other hooks have already been called for, the rest are passed to every
+    // hook by `notify_register_hooks`
+    let replayed: Vec<RegisteredImplWrapper<MetaOnly>> = registrations[..hooks.notified]
+        .iter()
+        .map(|item| item.without_constructor())
+        .collect();
+    hooks.hooks.push(hook.clone());
+    __TRAITREG_HAS_REGISTER_HOOKS.store(true, core::sync::atomic::Ordering::Release);
+    drop(hooks);
+    drop(registrations);
+
+    for item in &replayed {
+        hook(item);
+    }
+    notify_register_hooks();
+}
+
+// Call every hook installed with `on_register` for the registrations added since last called
+pub(crate) fn notify_register_hooks() {
+    let registrations = registrations();
+    let mut hooks = __TRAITREG_REGISTER_HOOKS
+        .lock()
+        .expect("Traitreg internal mutex poisoned");
+    if hooks.hooks.is_empty() || hooks.notified == registrations.len() {
+        return;
+    }
+    let added: Vec<RegisteredImplWrapper<MetaOnly>> = registrations[hooks.notified..]
+        .iter()
+        .map(|item| item.without_constructor())
+        .collect();
+    hooks.notified = registrations.len();
+    let installed = hooks.hooks.clone();
+    drop(hooks);
+    drop(registrations);
+
+    for item in &added {
+        for hook in &installed {
+            hook(item);
+        }
+    }
+}
+
+// Are any hooks installed with `on_register`
+pub(crate) fn has_register_hooks() -> bool {
+    __TRAITREG_HAS_REGISTER_HOOKS.load(core::sync::atomic::Ordering::Acquire)
+}
 
+/// Make the registry immutable. Every registration made so far is moved out of the mutex guarding
+/// the registry, so building registries, [dynamic_registry] and the other functions reading every
+/// registration no longer lock it. Registries declared with [registry](macro@registry) are built
+/// before `main()` and never lock when read, whether or not the registry is frozen.
+///
+/// Registrations made after freezing, for example by a library loaded at runtime, are not added
+/// to any registry, and print an error to stderr naming the implementation. Calling this again
+/// has no effect.
+///
+/// ```rust
+/// trait Plugin {}
+///
+/// struct MyPlugin;
+///
+/// #[traitreg::register]
+/// impl Plugin for MyPlugin {}
+///
+/// traitreg::freeze();
+/// assert!(traitreg::is_frozen());
+/// assert_eq!(1, traitreg::dynamic_registry("Plugin").len());
+/// ```
+pub fn freeze() {
     let mut registry_ref = __TRAITREG_REGISTRY
         .lock()
         .expect("Traitreg internal mutex poisoned");
-    registry_ref.push(wrapper);
+    if __TRAITREG_FROZEN.get().is_some() {
+        return;
+    }
+    drain_pending(&mut registry_ref);
+    let _ = __TRAITREG_FROZEN.set(core::mem::take(&mut registry_ref));
 }
 
-/// Trait registry storage. Contains methods to access the registry.
-pub struct TraitRegStorage<Trait> {
-    impls: Vec<RegisteredImplWrapper<Trait>>,
+/// Has the registry been made immutable with [freeze]
+pub fn is_frozen() -> bool {
+    __TRAITREG_FROZEN.get().is_some()
 }
 
-impl<Trait> TraitRegStorage<Trait> {
-    #[doc(hidden)]
-    pub fn __new(trait_: &'static str) -> Self {
-        let registry_ref = __TRAITREG_REGISTRY
-            .lock()
-            .expect("Traitreg internal mutex poisoned");
+/// Build a registry at runtime for a trait named by a string, e.g. from configuration. Entries are
+/// type erased, they have the metadata of each registered implementation, and can be instantiated
+/// with [instantiate_any](RegisteredImplWrapper::instantiate_any) if registered with `any`.
+///
+/// Registrations record the name of the trait rather than its path, so only the last segment of a
+/// path is compared, `"my::Trait"` and `"Trait"` find the same implementations. Every registration
+/// is included, whatever auto traits it implements, except those registered in a `namespace`.
+///
+/// ```rust
+/// mod my {
+///     pub trait Trait {}
+/// }
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// #[traitreg::register(default, any)]
+/// impl my::Trait for MyType {}
+///
+/// let registry = traitreg::dynamic_registry("my::Trait");
+/// let item = registry.iter().next().unwrap();
+/// assert_eq!("MyType", item.name());
+/// assert!(item.instantiate_downcast::<MyType>().is_some());
+/// ```
+pub fn dynamic_registry(trait_: &str) -> AnyRegistry {
+    let trait_ = trait_.rsplit("::").next().unwrap_or_default().trim();
+    let registrations = registrations();
+    // Registries are shared by the static name of the trait, a trait with no registrations has an
+    // empty registry
+    let impls = match registrations.iter().find(|item| item.trait_name == trait_) {
+        Some(item) => Impls::Shared(shared_impls::<MetaOnly>(
+            &registrations,
+            DeclaredRegistry {
+                trait_: item.trait_name,
+                namespace: None,
+                auto_traits: __private::AutoTraits::default(),
+                bindings: None,
+            },
+            false,
+            failure_policy() == FailurePolicy::Skip,
+            |item| item.without_constructor(),
+        )),
+        None => Impls::Owned(vec![]),
+    };
+    drop(registrations);
+    handle_failures(trait_, None);
 
-        let impls = registry_ref
-            .iter()
-            .filter(|item| item.trait_name == trait_)
-            .cloned()
-            .map(|item| {
-                // Safety: Since we check the trait name before transmuting back we cannot accidentally
-                // construct a trait object pointing to a different vtable in memory
-                let item: RegisteredImplWrapper<Trait> = unsafe { core::mem::transmute(item) };
-                item
+    TraitRegStorage {
+        impls,
+        instances: Instances::default(),
+    }
+}
+
+// The shared implementations of a registry, built from the registrations the first time a
+// registry with the same trait, instance type and options is built
+fn shared_impls<Trait: 'static>(
+    registrations: &[RegisteredImplWrapper<Box<u32>>],
+    registry: DeclaredRegistry,
+    sorted: bool,
+    skip_conflicts: bool,
+    convert: impl Fn(RegisteredImplWrapper<Box<u32>>) -> RegisteredImplWrapper<Trait>,
+) -> &'static [RegisteredImplWrapper<Box<u32>>] {
+    let key = SharedKey {
+        registry,
+        instance_type: core::any::TypeId::of::<Trait>(),
+        sorted,
+        skip_conflicts,
+        registrations: registrations.len(),
+    };
+
+    let mut shared = __TRAITREG_SHARED
+        .lock()
+        .expect("Traitreg internal mutex poisoned");
+    if let Some(shared) = shared.iter().find(|shared| shared.key == key) {
+        return shared.impls;
+    }
+
+    let mut impls: Vec<_> = registrations
+        .iter()
+        .filter(|item| item.belongs_in(&registry))
+        .filter(|item| !disabled_by_env(item))
+        .cloned()
+        .map(convert)
+        .collect();
+    if sorted {
+        impls.sort_by_key(|item| item.canonical_key());
+    }
+    let conflicts = record_conflicts(&impls);
+    if skip_conflicts {
+        impls.retain(|item| {
+            !conflicts
+                .iter()
+                .any(|conflict| conflict.path == item.path || conflict.conflicts_with == item.path)
+        });
+    }
+    let impls: &'static [_] = impls.leak();
+
+    // Safety: The layout of `RegisteredImplWrapper` does not depend on the instance type, and
+    // these are only converted back to the same type
+    let impls = unsafe { core::slice::from_raw_parts(impls.as_ptr().cast(), impls.len()) };
+    shared.push(SharedImpls { key, impls });
+    impls
+}
+
+// Is a registration disabled with the `TRAITREG_DISABLE` environment variable, a comma separated
+// list of `Trait::Type`, where either can be `*`, or just `Type` for any trait. The variable is
+// read when the first registry is built.
+#[cfg(feature = "env-filter")]
+fn disabled_by_env<Trait>(item: &RegisteredImplWrapper<Trait>) -> bool {
+    static DISABLED: std::sync::OnceLock<Vec<(String, String)>> = std::sync::OnceLock::new();
+
+    let disabled = DISABLED.get_or_init(|| {
+        let Ok(disable) = std::env::var("TRAITREG_DISABLE") else {
+            return vec![];
+        };
+        disable
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| match entry.rsplit_once("::") {
+                // Only the last segment of the trait path is compared, as in `dynamic_registry`
+                Some((trait_, name)) => {
+                    let trait_ = trait_.rsplit("::").next().unwrap_or_default();
+                    (trait_.trim().to_string(), name.trim().to_string())
+                }
+                None => ("*".to_string(), entry.to_string()),
             })
-            .collect();
+            .collect()
+    });
+
+    disabled.iter().any(|(trait_, name)| {
+        (trait_ == "*" || trait_ == item.trait_name) && (name == "*" || name == item.name)
+    })
+}
+
+#[cfg(not(feature = "env-filter"))]
+fn disabled_by_env<Trait>(_item: &RegisteredImplWrapper<Trait>) -> bool {
+    false
+}
 
-        Self { impls }
+// Record the conflicts between the implementations of a registry, which are handled by the failure
+// policy of the registry once the registrations are no longer locked, see `handle_failures`
+fn record_conflicts<Trait>(impls: &[RegisteredImplWrapper<Trait>]) -> Vec<Conflict> {
+    let conflicts = find_conflicts(impls);
+    if conflicts.is_empty() {
+        return conflicts;
     }
 
-    /// Iterate over registered implementations
-    pub fn iter(&self) -> core::slice::Iter<RegisteredImplWrapper<Trait>> {
-        self.impls.iter()
+    let mut recorded = __TRAITREG_CONFLICTS
+        .lock()
+        .expect("Traitreg internal mutex poisoned");
+    for conflict in &conflicts {
+        if !recorded.iter().any(|(recorded, _)| recorded == conflict) {
+            recorded.push((*conflict, false));
+        }
     }
+    conflicts
+}
 
-    /// Instanciate all registered implementations which have a constuctor
-    pub fn instanciate_all(&self) -> impl Iterator<Item = Trait> + '_ {
-        self.impls.iter().filter_map(|item| item.instanciate())
+// Pairs of implementations where one is declared to conflict with the other, each pair once
+fn find_conflicts<Trait>(impls: &[RegisteredImplWrapper<Trait>]) -> Vec<Conflict> {
+    let mut conflicts: Vec<Conflict> = vec![];
+    for item in impls {
+        for other in impls {
+            let named = item
+                .conflicts_with
+                .iter()
+                .any(|path| same_path(path, other.path));
+            if core::ptr::eq(item, other) || !named {
+                continue;
+            }
+            // Both may declare the conflict
+            let declared = conflicts.iter().any(|conflict| {
+                conflict.path == other.path && conflict.conflicts_with == item.path
+            });
+            if !declared {
+                conflicts.push(Conflict {
+                    path: item.path,
+                    conflicts_with: other.path,
+                    trait_name: item.trait_name,
+                });
+            }
+        }
     }
+    conflicts
 }
 
-/// Registered implementation
-#[derive(Clone)]
-pub struct RegisteredImplWrapper<Trait> {
-    instanciate: fn() -> Option<Trait>,
-    has_constructor: bool,
-    name: &'static str,
-    path: &'static str,
-    file: &'static str,
-    module_path: &'static str,
-    trait_name: &'static str,
+// Are two type paths the same, ignoring whitespace, which `stringify!` adds around `::`
+fn same_path(path: &str, other: &str) -> bool {
+    let path = path.chars().filter(|c| !c.is_whitespace());
+    path.eq(other.chars().filter(|c| !c.is_whitespace()))
 }
 
-impl<Trait> RegisteredImplWrapper<Trait> {
-    /// Instanciate type if a constructor has been registered
-    ///
-    /// Returns a heap allocated trait object, `Box<dyn Trait>`, rather than a
-    /// concrete type.
-    pub fn instanciate(&self) -> Option<Trait> {
-        (self.instanciate)()
+/// Two implementations in the same registry where one is declared to conflict with the other
+/// with `conflicts_with`, see [TraitRegStorage::conflicts]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// The type path of the implementation declaring the conflict, see [RegisteredImplWrapper::path]
+    pub path: &'static str,
+    /// The type path of the implementation it conflicts with
+    pub conflicts_with: &'static str,
+    /// The name of the trait both implement
+    pub trait_name: &'static str,
+}
+
+impl core::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "`{}` conflicts with `{}`, but both are registered as implementations of `{}`",
+            self.path, self.conflicts_with, self.trait_name
+        )
     }
+}
 
-    /// Was this type registered with a constructor
-    pub fn has_constructor(&self) -> bool {
-        self.has_constructor
+impl std::error::Error for Conflict {}
+
+/// Registration which is not in any registry, see [unclaimed_registrations]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UnclaimedRegistration {
+    /// The type name, see [RegisteredImplWrapper::name]
+    pub name: &'static str,
+    /// The type path, see [RegisteredImplWrapper::path]
+    pub path: &'static str,
+    /// The trait name
+    pub trait_name: &'static str,
+    /// The module containing the implementation of the trait
+    pub module_path: &'static str,
+    /// The file containing the implementation of the trait
+    pub file: &'static str,
+}
+
+/// Registrations which are not in any registry in the binary, either because no registry has been
+/// declared for their trait, or because the registered type does not implement the auto traits
+/// required by the registries which have.
+///
+/// Registrations are matched to registries by the name of the trait as written, so registering
+/// against a trait imported under another name, or declaring the registry for a different trait
+/// with the same name, shows up here. Registries are built before `main()`, so this is only
+/// complete once `main()` has started.
+///
+/// ```rust
+/// trait Plugin {}
+///
+/// struct Orphan;
+///
+/// #[traitreg::register]
+/// impl Plugin for Orphan {}
+///
+/// let unclaimed = traitreg::unclaimed_registrations();
+/// assert_eq!("Orphan", unclaimed[0].name);
+/// assert_eq!("Plugin", unclaimed[0].trait_name);
+/// ```
+pub fn unclaimed_registrations() -> Vec<UnclaimedRegistration> {
+    let registry_ref = registrations();
+    let declared = __TRAITREG_DECLARED
+        .lock()
+        .expect("Traitreg internal mutex poisoned");
+
+    registry_ref
+        .iter()
+        .filter(|item| !declared.iter().any(|registry| item.belongs_in(registry)))
+        .map(|item| UnclaimedRegistration {
+            name: item.name,
+            path: item.path,
+            trait_name: item.trait_name,
+            module_path: item.module_path,
+            file: item.file,
+        })
+        .collect()
+}
+
+/// Registration which panicked while being added to the registry, see [registration_panics]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RegistrationPanic {
+    /// The type name, see [RegisteredImplWrapper::name]
+    pub name: &'static str,
+    /// The type path, see [RegisteredImplWrapper::path]
+    pub path: &'static str,
+    /// The trait name
+    pub trait_name: &'static str,
+    /// The panic message
+    pub message: String,
+}
+
+/// Registrations which panicked while being added to the registry, usually in a `guard` function
+/// declared on [register](macro@register). Registrations are added before `main()`, where a panic
+/// would abort the process, so the panic is caught, the implementation is left out of every
+/// registry, and the failure is handled by the [FailurePolicy] of the first registry of the trait
+/// to be built, by default printing an error to stderr. Panics cannot be caught when building with
+/// `panic = "abort"`.
+///
+/// ```rust
+/// trait Plugin {}
+///
+/// struct Gpu;
+///
+/// fn gpu_available() -> bool {
+///     panic!("driver not loaded")
+/// }
+///
+/// #[traitreg::register(guard = gpu_available)]
+/// impl Plugin for Gpu {}
+///
+/// #[traitreg::registry(Plugin)]
+/// static PLUGIN_REGISTRY: () = ();
+///
+/// assert!(PLUGIN_REGISTRY.is_empty());
+/// let panics = traitreg::registration_panics();
+/// assert_eq!("Gpu", panics[0].name);
+/// assert_eq!("driver not loaded", panics[0].message);
+/// ```
+pub fn registration_panics() -> Vec<RegistrationPanic> {
+    // Make sure registrations are added, as they are when any registry is built
+    drop(registrations());
+    __TRAITREG_PANICS
+        .lock()
+        .expect("Traitreg internal mutex poisoned")
+        .iter()
+        .map(|(failure, _)| failure.clone())
+        .collect()
+}
+
+/// Count the registrations made so far, by crate and by trait, see [stats](mod@stats). Includes
+/// every registration, whether or not it is in a registry.
+pub fn stats() -> stats::Stats {
+    let failed = registration_panics().len();
+    stats::Stats::new(registrations().iter(), failed)
+}
+
+/// What happens when a registration fails, see [registration_panics], or when a registry has
+/// conflicting implementations, see [TraitRegStorage::conflicts]. Set for every registry with
+/// [set_failure_policy], or for one registry with the `on_failure` option on
+/// [registry](macro@registry).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum FailurePolicy {
+    /// Panic when the registry is built. Registries are built before `main()`, where this aborts
+    /// the process.
+    Panic,
+    /// Leave the implementation out of the registry, only recording the failure in
+    /// [registration_panics]. Both of two conflicting implementations are left out, see
+    /// [TraitRegStorage::conflicts].
+    Skip,
+    /// Leave the implementation out of the registry, and print an error to stderr. Conflicting
+    /// implementations are kept, so they can be checked with [TraitRegStorage::check_conflicts].
+    #[default]
+    Log,
+}
+
+/// Set the failure policy of registries which are built without an `on_failure` option, `Log` by
+/// default. Registries declared with [registry](macro@registry) are built before `main()`, so this
+/// applies to those built afterwards, such as with [dynamic_registry].
+pub fn set_failure_policy(policy: FailurePolicy) {
+    __TRAITREG_FAILURE_POLICY.store(policy as u8, core::sync::atomic::Ordering::Relaxed);
+}
+
+/// The failure policy of registries which are built without an `on_failure` option, see
+/// [set_failure_policy]
+pub fn failure_policy() -> FailurePolicy {
+    match __TRAITREG_FAILURE_POLICY.load(core::sync::atomic::Ordering::Relaxed) {
+        0 => FailurePolicy::Panic,
+        1 => FailurePolicy::Skip,
+        _ => FailurePolicy::Log,
     }
+}
 
-    /// The type name
-    pub fn name(&self) -> &'static str {
-        self.name
+// Poll every future until all have completed, on the task awaiting the returned future
+async fn join_all<'a, T>(
+    futures: Vec<core::pin::Pin<Box<dyn core::future::Future<Output = T> + 'a>>>,
+) -> Vec<T> {
+    enum Slot<'a, T> {
+        Pending(core::pin::Pin<Box<dyn core::future::Future<Output = T> + 'a>>),
+        Ready(T),
     }
 
-    /// The type path. This differs from name when the implementation block is in a different crate
-    /// of module than the type itself. e.g. `MyType` vs `other::module::OtherType`.
-    pub fn path(&self) -> &'static str {
-        self.path
+    let mut slots: Vec<_> = futures.into_iter().map(Slot::Pending).collect();
+    core::future::poll_fn(|context| {
+        let mut ready = true;
+        for slot in &mut slots {
+            if let Slot::Pending(future) = slot {
+                match future.as_mut().poll(context) {
+                    core::task::Poll::Ready(output) => *slot = Slot::Ready(output),
+                    core::task::Poll::Pending => ready = false,
+                }
+            }
+        }
+        if ready {
+            core::task::Poll::Ready(())
+        } else {
+            core::task::Poll::Pending
+        }
+    })
+    .await;
+
+    slots
+        .into_iter()
+        .map(|slot| match slot {
+            Slot::Ready(output) => output,
+            Slot::Pending(_) => unreachable!("Every future has completed"),
+        })
+        .collect()
+}
+
+// Apply a failure policy to the failed registrations and the conflicting implementations of a
+// trait which no registry has handled yet
+fn handle_failures(trait_: &str, on_failure: Option<FailurePolicy>) {
+    let failures: Vec<_> = __TRAITREG_PANICS
+        .lock()
+        .expect("Traitreg internal mutex poisoned")
+        .iter_mut()
+        .filter(|(failure, handled)| !*handled && failure.trait_name == trait_)
+        .map(|(failure, handled)| {
+            *handled = true;
+            failure.clone()
+        })
+        .collect();
+
+    for failure in failures {
+        match on_failure.unwrap_or_else(failure_policy) {
+            FailurePolicy::Panic => panic!(
+                "traitreg: registration of `{}` as an implementation of `{}` panicked: {}",
+                failure.path, failure.trait_name, failure.message
+            ),
+            FailurePolicy::Skip => {}
+            FailurePolicy::Log => eprintln!(
+                "traitreg: error: registration of `{}` as an implementation of `{}` panicked, it \
+                is not added to any registry: {}",
+                failure.path, failure.trait_name, failure.message
+            ),
+        }
     }
 
-    /// The file containing the implementation of the trait
-    pub fn file(&self) -> &'static str {
-        self.file
+    let conflicts: Vec<_> = __TRAITREG_CONFLICTS
+        .lock()
+        .expect("Traitreg internal mutex poisoned")
+        .iter_mut()
+        .filter(|(conflict, handled)| !*handled && conflict.trait_name == trait_)
+        .map(|(conflict, handled)| {
+            *handled = true;
+            *conflict
+        })
+        .collect();
+
+    for conflict in conflicts {
+        match on_failure.unwrap_or_else(failure_policy) {
+            FailurePolicy::Panic => panic!("traitreg: {conflict}"),
+            FailurePolicy::Skip => {}
+            FailurePolicy::Log => eprintln!("traitreg: error: {conflict}"),
+        }
+    }
+}
+
+// Trait names of the registries which have been built with no implementations
+fn empty_registries() -> Vec<&'static str> {
+    let registry_ref = registrations();
+    let declared = __TRAITREG_DECLARED
+        .lock()
+        .expect("Traitreg internal mutex poisoned");
+
+    declared
+        .iter()
+        .filter(|registry| !registry_ref.iter().any(|item| item.belongs_in(registry)))
+        .map(|registry| registry.trait_)
+        .collect()
+}
+
+// Number of instantiations of a registration, shared by every copy of the registration in every
+// registry. Stored as a pointer rather than a reference so wrappers are not considered to have
+// interior mutability when used as keys.
+#[cfg(feature = "metrics")]
+#[derive(Clone, Copy)]
+struct InstantiationCounter(core::ptr::NonNull<core::sync::atomic::AtomicUsize>);
+
+// Safety: The counter is only accessed through a shared reference to an atomic
+#[cfg(feature = "metrics")]
+unsafe impl Send for InstantiationCounter {}
+#[cfg(feature = "metrics")]
+unsafe impl Sync for InstantiationCounter {}
+
+#[cfg(feature = "metrics")]
+impl InstantiationCounter {
+    fn new() -> Self {
+        Self(core::ptr::NonNull::from(Box::leak(Box::default())))
     }
 
-    /// The module containing the implementation of the trait
-    pub fn module_path(&self) -> &'static str {
-        self.module_path
+    fn get(&self) -> &'static core::sync::atomic::AtomicUsize {
+        // Safety: The counter is leaked when created, so is never freed
+        unsafe { self.0.as_ref() }
     }
+}
 
-    /// The trait name
-    pub fn trait_name(&self) -> &'static str {
-        self.trait_name
+/// Instance type of registries for traits which are not dyn compatible, see
+/// [register](macro@register) and [registry](macro@registry) with the `meta_only` option.
+///
+/// This type has no values, so implementations in these registries can never be instantiated.
+pub enum MetaOnly {}
+
+/// Registry of any trait, with type erased entries, see [TraitRegStorage::to_any]. Generic tools,
+/// such as debug UIs, can handle registries of different traits uniformly through this type.
+///
+/// Entries have the metadata of each registered implementation, and can be instantiated with
+/// [instantiate_any](RegisteredImplWrapper::instantiate_any) if registered with `any`, but not with
+/// [instantiate](RegisteredImplWrapper::instantiate), since the trait object type is unknown.
+pub type AnyRegistry = TraitRegStorage<MetaOnly>;
+
+fn no_constructor<Trait>() -> Option<Result<Trait, ConstructError>> {
+    None
+}
+
+/// Error returned by a fallible constructor, see [register](macro@register)
+#[derive(Debug)]
+pub struct ConstructError(Box<dyn std::error::Error + Send + Sync>);
+
+impl ConstructError {
+    /// Create an error from any error type, or a message
+    pub fn new(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self(error.into())
+    }
+
+    /// The error returned by the constructor
+    pub fn into_inner(self) -> Box<dyn std::error::Error + Send + Sync> {
+        self.0
+    }
+}
+
+impl core::fmt::Display for ConstructError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for ConstructError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+/// Error instantiating a registered implementation, see
+/// [RegisteredImplWrapper::try_instantiate]
+#[derive(Debug)]
+pub enum InstantiateError {
+    /// The type was registered without a constructor, or with one which is not called through
+    /// `instantiate`, such as constructors registered with `inject` or `args`
+    NoConstructor {
+        /// The type name
+        name: &'static str,
+        /// The trait name
+        trait_name: &'static str,
+    },
+    /// The fallible constructor of the type returned an error
+    ConstructorFailed {
+        /// The type name
+        name: &'static str,
+        /// The trait name
+        trait_name: &'static str,
+        /// The error returned by the constructor
+        error: ConstructError,
+    },
+}
+
+impl core::fmt::Display for InstantiateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        match self {
+            Self::NoConstructor { name, trait_name } => write!(
+                f,
+                "Implementation '{name}' of '{trait_name}' was not registered with a constructor"
+            ),
+            Self::ConstructorFailed {
+                name,
+                trait_name,
+                error,
+            } => write!(
+                f,
+                "Constructor of implementation '{name}' of '{trait_name}' failed: {error}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InstantiateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NoConstructor { .. } => None,
+            Self::ConstructorFailed { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Errors of every implementation which failed to construct or initialize, see
+/// [TraitRegStorage::init_all]
+#[derive(Debug)]
+pub struct InitErrors(Vec<InstantiateError>);
+
+impl InitErrors {
+    /// The errors, in registry order
+    pub fn errors(&self) -> &[InstantiateError] {
+        &self.0
+    }
+
+    /// The errors, in registry order
+    pub fn into_errors(self) -> Vec<InstantiateError> {
+        self.0
+    }
+}
+
+impl core::fmt::Display for InitErrors {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(f, "{} implementations failed to initialize", self.0.len())?;
+        for error in &self.0 {
+            write!(f, "\n{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InitErrors {}
+
+/// Implementations which depend on each other, declared with `after`, so they cannot be ordered,
+/// see [TraitRegStorage::iter_topological]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DependencyCycle(Vec<&'static str>);
+
+impl DependencyCycle {
+    /// Names of the implementations in the cycle, each depending on the next, starting and ending
+    /// with the same implementation
+    pub fn names(&self) -> &[&'static str] {
+        &self.0
+    }
+}
+
+impl core::fmt::Display for DependencyCycle {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "dependency cycle between implementations: {}",
+            self.0.join(" -> ")
+        )
+    }
+}
+
+impl std::error::Error for DependencyCycle {}
+
+/// Trait registry storage. Contains methods to access the registry.
+///
+/// Registries only hold constructors and metadata, and the instances of `eager` registries, so they
+/// are `Send` and `Sync` whether or not the trait objects they construct are, and can be used from
+/// any thread or async task. The instances of an `eager` registry are only accessible when they
+/// are `Sync`, see [instances](Self::instances).
+/// To move the instances themselves to another thread, declare the registry with the `send` option,
+/// which constructs `Box<dyn Trait + Send>`.
+pub struct TraitRegStorage<Trait> {
+    impls: Impls<Trait>,
+    instances: Instances<Trait>,
+}
+
+// Instances constructed when an `eager` registry is built. They are leaked, so never dropped, and
+// only accessible through `TraitRegStorage::instances` when `Trait: Sync`
+struct Instances<Trait>(core::mem::ManuallyDrop<Vec<Trait>>);
+
+// Safety: See above, the instances are never dropped on, or accessed from, another thread unless
+// they are Sync
+unsafe impl<Trait> Send for Instances<Trait> {}
+unsafe impl<Trait> Sync for Instances<Trait> {}
+
+impl<Trait> Default for Instances<Trait> {
+    fn default() -> Self {
+        Self(core::mem::ManuallyDrop::new(Vec::new()))
+    }
+}
+
+// Implementations in a registry, either shared by the registries of a trait, or owned by a
+// registry converted with `to_any`. Shared implementations have the type of the instances erased, as
+// registries do not require it to be 'static
+enum Impls<Trait> {
+    Shared(&'static [RegisteredImplWrapper<Box<u32>>]),
+    Owned(Vec<RegisteredImplWrapper<Trait>>),
+}
+
+impl<Trait> core::ops::Deref for Impls<Trait> {
+    type Target = [RegisteredImplWrapper<Trait>];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            // Safety: The implementations were converted to `RegisteredImplWrapper<Trait>` when
+            // the registry was built
+            Self::Shared(impls) => unsafe {
+                core::slice::from_raw_parts(impls.as_ptr().cast(), impls.len())
+            },
+            Self::Owned(impls) => impls,
+        }
+    }
+}
+
+impl<Trait> FromIterator<RegisteredImplWrapper<Trait>> for Impls<Trait> {
+    fn from_iter<I: IntoIterator<Item = RegisteredImplWrapper<Trait>>>(iter: I) -> Self {
+        Self::Owned(iter.into_iter().collect())
+    }
+}
+
+impl<Trait> TraitRegStorage<Trait> {
+    // Registries with the same trait, instance type and options share their implementations, so
+    // the registrations are only filtered and converted once, e.g. for a registry declared in
+    // both a library and its tests
+    fn build(
+        registry: DeclaredRegistry,
+        on_failure: Option<FailurePolicy>,
+        sorted: bool,
+        eager: bool,
+        convert: impl Fn(RegisteredImplWrapper<Box<u32>>) -> RegisteredImplWrapper<Trait>,
+    ) -> Self
+    where
+        Trait: 'static,
+    {
+        let registrations = registrations();
+        let skip_conflicts = on_failure.unwrap_or_else(failure_policy) == FailurePolicy::Skip;
+        let impls = shared_impls(&registrations, registry, sorted, skip_conflicts, convert);
+        drop(registrations);
+        handle_failures(registry.trait_, on_failure);
+
+        let mut storage = Self {
+            impls: Impls::Shared(impls),
+            instances: Instances::default(),
+        };
+        if eager {
+            let instances = storage.instantiate_all().collect::<Vec<_>>();
+            storage.instances = Instances(core::mem::ManuallyDrop::new(instances));
+        }
+        storage
+    }
+
+    /// Iterate over registered implementations
+    pub fn iter(&self) -> core::slice::Iter<'_, RegisteredImplWrapper<Trait>> {
+        self.impls.iter()
+    }
+
+    /// Registered implementations as a slice. Registries are stored in statics, so the slice of a
+    /// registry static is `&'static`, and can be held without borrowing through the registry.
+    ///
+    /// Registries of the same trait with the same instance type and options, and the registries
+    /// returned by [dynamic_registry], share their implementations rather than each holding a copy,
+    /// so an implementation is at the same address in each and can be compared with
+    /// [core::ptr::eq].
+    ///
+    /// ```rust
+    /// # trait MyTrait {}
+    /// #[traitreg::registry(MyTrait)]
+    /// static MYTRAIT_REGISTRY: () = ();
+    ///
+    /// let impls: &'static [traitreg::RegisteredImplWrapper<Box<dyn MyTrait>>] =
+    ///     MYTRAIT_REGISTRY.as_slice();
+    /// ```
+    pub fn as_slice(&self) -> &[RegisteredImplWrapper<Trait>] {
+        &self.impls
+    }
+
+    /// Iterate over the type names of registered implementations, in registry order, see
+    /// [RegisteredImplWrapper::name]. Nothing is constructed or allocated.
+    ///
+    /// ```rust
+    /// # trait MyTrait {}
+    /// struct MyType;
+    ///
+    /// #[traitreg::register]
+    /// impl MyTrait for MyType {}
+    ///
+    /// #[traitreg::registry(MyTrait)]
+    /// static MYTRAIT_REGISTRY: () = ();
+    ///
+    /// assert!(MYTRAIT_REGISTRY.iter_names().any(|name| name == "MyType"));
+    /// ```
+    pub fn iter_names(&self) -> impl ExactSizeIterator<Item = &'static str> + '_ {
+        self.impls.iter().map(|item| item.name)
+    }
+
+    /// Iterate over the type paths of registered implementations, in registry order, see
+    /// [RegisteredImplWrapper::path]. Nothing is constructed or allocated.
+    pub fn iter_paths(&self) -> impl ExactSizeIterator<Item = &'static str> + '_ {
+        self.impls.iter().map(|item| item.path)
+    }
+
+    /// Find the implementation with an id
+    pub fn get_by_id(&self, id: ImplId) -> Option<&RegisteredImplWrapper<Trait>> {
+        self.impls.get(id.0 as usize)
+    }
+
+    /// The id of an implementation in this registry, or `None` if the implementation is from
+    /// another registry. Registries of the same trait with the same instance type and options
+    /// share their implementations, and the ids of them.
+    pub fn id_of(&self, item: &RegisteredImplWrapper<Trait>) -> Option<ImplId> {
+        let range = self.impls.as_ptr_range();
+        let item: *const RegisteredImplWrapper<Trait> = item;
+        if !range.contains(&item) {
+            return None;
+        }
+        // Safety: The pointer is to an element of the slice
+        let index = unsafe { item.offset_from(range.start) };
+        Some(ImplId(index as u32))
+    }
+
+    /// Iterate over registered implementations with their ids
+    pub fn iter_with_ids(&self) -> impl Iterator<Item = (ImplId, &RegisteredImplWrapper<Trait>)> {
+        self.impls
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (ImplId(index as u32), item))
+    }
+
+    /// Find the implementation on a type, by [TypeId](core::any::TypeId)
+    pub fn get_by_type_id(
+        &self,
+        type_id: core::any::TypeId,
+    ) -> Option<&RegisteredImplWrapper<Trait>> {
+        self.impls.iter().find(|item| item.type_id == type_id)
+    }
+
+    /// Iterate over types registered in both this registry and another registry, usually of a
+    /// different trait. Types are matched by [TypeId](core::any::TypeId) rather than by name or
+    /// path, so re-exported types are matched correctly. Each type is returned with its
+    /// implementation from both registries, so it can be instantiated as either trait.
+    pub fn common_types<'a, Other>(
+        &'a self,
+        other: &'a TraitRegStorage<Other>,
+    ) -> impl Iterator<
+        Item = (
+            &'a RegisteredImplWrapper<Trait>,
+            &'a RegisteredImplWrapper<Other>,
+        ),
+    > + 'a {
+        self.impls
+            .iter()
+            .filter_map(|item| Some((item, other.get_by_type_id(item.type_id)?)))
+    }
+
+    /// Iterate over registered implementations which provide every required capability, see
+    /// [RegisteredImplWrapper::capabilities]
+    pub fn compatible_with<'a>(
+        &'a self,
+        requirements: &'a [&str],
+    ) -> impl Iterator<Item = &'a RegisteredImplWrapper<Trait>> + 'a {
+        self.impls
+            .iter()
+            .filter(move |item| item.provides(requirements))
+    }
+
+    /// Iterate over registered implementations in an initialization phase, see
+    /// [RegisteredImplWrapper::phase]
+    pub fn iter_phase<'a>(
+        &'a self,
+        phase: &'a str,
+    ) -> impl Iterator<Item = &'a RegisteredImplWrapper<Trait>> + 'a {
+        self.impls
+            .iter()
+            .filter(move |item| item.phase == Some(phase))
+    }
+
+    /// Iterate over registered implementations so that each comes after the implementations it
+    /// depends on, declared with `after`. Dependencies are matched by name, and those which are not
+    /// in the registry are ignored. Otherwise implementations keep their order in the registry.
+    /// Fails if implementations depend on each other.
+    ///
+    /// ```rust
+    /// trait Plugin {}
+    ///
+    /// struct Metrics;
+    ///
+    /// #[traitreg::register(after("Logger"))]
+    /// impl Plugin for Metrics {}
+    ///
+    /// struct Logger;
+    ///
+    /// #[traitreg::register]
+    /// impl Plugin for Logger {}
+    ///
+    /// #[traitreg::registry(Plugin)]
+    /// static PLUGIN_REGISTRY: () = ();
+    ///
+    /// let names: Vec<_> = PLUGIN_REGISTRY
+    ///     .iter_topological()
+    ///     .unwrap()
+    ///     .map(|item| item.name())
+    ///     .collect();
+    /// assert_eq!(vec!["Logger", "Metrics"], names);
+    /// ```
+    pub fn iter_topological(
+        &self,
+    ) -> Result<impl Iterator<Item = &RegisteredImplWrapper<Trait>>, DependencyCycle> {
+        let depends_on = |item: &RegisteredImplWrapper<Trait>,
+                          other: &RegisteredImplWrapper<Trait>| {
+            item.after.contains(&other.name)
+        };
+
+        let mut remaining: Vec<_> = self.impls.iter().collect();
+        let mut ordered = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            // The first implementation in registry order with no remaining dependencies
+            let ready = remaining
+                .iter()
+                .position(|item| !remaining.iter().any(|other| depends_on(item, other)));
+            let Some(ready) = ready else {
+                // Every remaining implementation depends on another, follow dependencies until
+                // one repeats
+                let mut cycle = vec![remaining[0]];
+                loop {
+                    let last = cycle[cycle.len() - 1];
+                    let next = remaining
+                        .iter()
+                        .find(|other| depends_on(last, other))
+                        .expect("Every remaining implementation has a remaining dependency");
+                    if let Some(start) = cycle.iter().position(|item| core::ptr::eq(*item, *next)) {
+                        let mut names: Vec<_> =
+                            cycle[start..].iter().map(|item| item.name).collect();
+                        names.push(next.name);
+                        return Err(DependencyCycle(names));
+                    }
+                    cycle.push(next);
+                }
+            };
+            ordered.push(remaining.remove(ready));
+        }
+        Ok(ordered.into_iter())
+    }
+
+    /// Implementations in the registry which conflict with each other, declared with
+    /// `conflicts_with`, e.g. two allocators. Conflicts are matched on the type path as written in
+    /// the impl block, see [RegisteredImplWrapper::path], each pair is only included once.
+    /// Registries handle each conflict with their [FailurePolicy] when they are built.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        find_conflicts(&self.impls)
+    }
+
+    /// Check that no implementations in the registry conflict with each other, see
+    /// [TraitRegStorage::conflicts]. Returns the first conflict otherwise.
+    ///
+    /// ```rust
+    /// trait Allocator {}
+    ///
+    /// struct Bump;
+    ///
+    /// #[traitreg::register(conflicts_with("Slab"))]
+    /// impl Allocator for Bump {}
+    ///
+    /// struct Slab;
+    ///
+    /// #[traitreg::register]
+    /// impl Allocator for Slab {}
+    ///
+    /// #[traitreg::registry(Allocator)]
+    /// static ALLOCATOR_REGISTRY: () = ();
+    ///
+    /// let conflict = ALLOCATOR_REGISTRY.check_conflicts().unwrap_err();
+    /// assert_eq!("Slab", conflict.conflicts_with);
+    /// ```
+    pub fn check_conflicts(&self) -> Result<(), Conflict> {
+        match self.conflicts().into_iter().next() {
+            Some(conflict) => Err(conflict),
+            None => Ok(()),
+        }
+    }
+
+    /// Iterate over registered implementations in a source file, see
+    /// [RegisteredImplWrapper::is_from_file]
+    ///
+    /// ```rust
+    /// # trait Plugin {}
+    /// struct Audio;
+    ///
+    /// #[traitreg::register]
+    /// impl Plugin for Audio {}
+    ///
+    /// #[traitreg::registry(Plugin)]
+    /// static PLUGIN_REGISTRY: () = ();
+    ///
+    /// let audio = PLUGIN_REGISTRY.iter().next().unwrap();
+    /// assert_eq!(1, PLUGIN_REGISTRY.iter_from_file(audio.file()).count());
+    /// assert_eq!(0, PLUGIN_REGISTRY.iter_from_file("src/plugins/video.rs").count());
+    /// ```
+    pub fn iter_from_file<'a>(
+        &'a self,
+        file: &'a str,
+    ) -> impl Iterator<Item = &'a RegisteredImplWrapper<Trait>> + 'a {
+        self.impls
+            .iter()
+            .filter(move |item| item.is_from_file(file))
+    }
+
+    /// View of the registered implementations, for set operations with other views, see
+    /// [View](query::View)
+    pub fn view(&self) -> query::View<'_, Trait> {
+        query::View::new(self.impls.iter().collect())
+    }
+
+    /// Query registered implementations, see [Query](query::Query)
+    pub fn query(&self) -> query::Query<'_, Trait> {
+        query::Query::new(self)
+    }
+
+    /// Copy of the registry with type erased entries, see [AnyRegistry]
+    ///
+    /// ```rust
+    /// trait Shape {}
+    /// trait Codec {}
+    ///
+    /// #[derive(Default)]
+    /// struct Square;
+    ///
+    /// #[traitreg::register(default, any)]
+    /// impl Shape for Square {}
+    ///
+    /// #[traitreg::registry(Shape)]
+    /// static SHAPE_REGISTRY: () = ();
+    ///
+    /// #[traitreg::registry(Codec)]
+    /// static CODEC_REGISTRY: () = ();
+    ///
+    /// let registries: Vec<traitreg::AnyRegistry> =
+    ///     vec![SHAPE_REGISTRY.to_any(), CODEC_REGISTRY.to_any()];
+    /// let square = registries[0].iter().next().unwrap();
+    /// assert_eq!("Square", square.name());
+    /// assert!(square.instantiate_any().unwrap().is::<Square>());
+    /// ```
+    pub fn to_any(&self) -> AnyRegistry {
+        TraitRegStorage {
+            impls: self
+                .impls
+                .iter()
+                .map(|item| item.without_constructor())
+                .collect(),
+            instances: Instances::default(),
+        }
+    }
+
+    /// Owned copy of the registered implementations, unaffected by later changes to the registry,
+    /// see [Snapshot](snapshot::Snapshot)
+    pub fn snapshot(&self) -> snapshot::Snapshot<Trait> {
+        snapshot::Snapshot::new(self.impls.iter().map(|item| item.duplicate()).collect())
+    }
+
+    /// Copy of the registry with a configuration applied, leaving out disabled implementations and
+    /// replacing configured priorities, see [config]. Implementations keep their order in the
+    /// registry.
+    pub fn apply_config(&self, config: &config::RegistryConfig) -> Self {
+        TraitRegStorage {
+            impls: self
+                .impls
+                .iter()
+                .filter(|item| config.is_enabled(item.name))
+                .map(|item| {
+                    let mut item = item.duplicate();
+                    if let Some(priority) = config.priority(item.name) {
+                        item.priority = priority;
+                    }
+                    item
+                })
+                .collect(),
+            instances: Instances::default(),
+        }
+    }
+
+    /// Search for implementations by type name or path, ignoring case. Exact matches come first,
+    /// then names starting with the pattern, names containing it, and finally names containing
+    /// the characters of the pattern in order, e.g. `jsc` matches `JsonCodec`.
+    pub fn search(&self, pattern: &str) -> Vec<&RegisteredImplWrapper<Trait>> {
+        let mut matches: Vec<_> = self
+            .impls
+            .iter()
+            .filter_map(|item| Some((query::match_rank(pattern, item)?, item)))
+            .collect();
+        matches.sort_by_key(|(rank, _)| *rank);
+        matches.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Hash of the implementations in the registry, which is the same between runs and builds
+    /// while the same implementations are registered from the same crate versions, and
+    /// independent of registry order. Can be stored to detect when the set of registered
+    /// implementations changes, e.g. to invalidate a cache.
+    ///
+    /// The hash is FNV-1a over the crate name and version, module path, type path and trait
+    /// name of each implementation, sorted.
+    pub fn fingerprint(&self) -> u64 {
+        let mut keys: Vec<_> = self.impls.iter().map(|item| item.canonical_key()).collect();
+        keys.sort_unstable();
+
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for key in keys {
+            for field in key {
+                // Each field is terminated, so fields cannot run into each other
+                for byte in field.bytes().chain([0]) {
+                    hash ^= u64::from(byte);
+                    hash = hash.wrapping_mul(0x100000001b3);
+                }
+            }
+        }
+        hash
+    }
+
+    /// Number of registered implementations. This is only known at runtime, enums generated by
+    /// [static_dispatch](macro@static_dispatch) have a `COUNT` constant for use at compile time,
+    /// which counts the implementations listed in the enum and must be kept in sync with the
+    /// registrations.
+    pub fn len(&self) -> usize {
+        self.impls.len()
+    }
+
+    /// Are there no registered implementations
+    pub fn is_empty(&self) -> bool {
+        self.impls.is_empty()
+    }
+
+    /// Panic unless the registry has at least `min` implementations, e.g. at startup, so a binary
+    /// fails fast when registrations were left out by a feature flag, a guard or the linker.
+    ///
+    /// ```rust,should_panic
+    /// trait Plugin {}
+    ///
+    /// struct Audio;
+    ///
+    /// #[traitreg::register]
+    /// impl Plugin for Audio {}
+    ///
+    /// #[traitreg::registry(Plugin)]
+    /// static PLUGIN_REGISTRY: () = ();
+    ///
+    /// PLUGIN_REGISTRY.assert_min_impls(1);
+    /// // Panics, only one implementation is registered
+    /// PLUGIN_REGISTRY.assert_min_impls(2);
+    /// ```
+    #[track_caller]
+    pub fn assert_min_impls(&self, min: usize) {
+        if self.impls.len() < min {
+            panic!(
+                "traitreg: expected at least {min} implementations in the registry of `{}`, found \
+                {}: [{}]. Registrations may have been left out by a feature flag, a guard or the \
+                linker.",
+                core::any::type_name::<Trait>(),
+                self.impls.len(),
+                self.iter_names().collect::<Vec<_>>().join(", ")
+            );
+        }
+    }
+
+    /// Panic unless an implementation on `T` is in the registry, e.g. at startup, see
+    /// [TraitRegStorage::assert_min_impls]
+    ///
+    /// ```rust
+    /// trait Plugin {}
+    ///
+    /// struct Audio;
+    ///
+    /// #[traitreg::register]
+    /// impl Plugin for Audio {}
+    ///
+    /// #[traitreg::registry(Plugin)]
+    /// static PLUGIN_REGISTRY: () = ();
+    ///
+    /// PLUGIN_REGISTRY.assert_contains::<Audio>();
+    /// ```
+    #[track_caller]
+    pub fn assert_contains<T: 'static>(&self) {
+        if self.get_by_type_id(core::any::TypeId::of::<T>()).is_none() {
+            panic!(
+                "traitreg: expected `{}` in the registry of `{}`, found [{}]. The registration may \
+                have been left out by a feature flag, a guard or the linker.",
+                core::any::type_name::<T>(),
+                core::any::type_name::<Trait>(),
+                self.iter_names().collect::<Vec<_>>().join(", ")
+            );
+        }
+    }
+
+    /// Iterate over registered implementations ordered by
+    /// [priority](RegisteredImplWrapper::priority), highest first. Implementations with the same
+    /// priority keep their order in the registry.
+    pub fn iter_by_priority(&self) -> impl Iterator<Item = &RegisteredImplWrapper<Trait>> {
+        let mut impls: Vec<_> = self.impls.iter().collect();
+        impls.sort_by_key(|item| core::cmp::Reverse(item.priority));
+        impls.into_iter()
+    }
+
+    /// Run the health checks of registered implementations, declared with `health_check` on
+    /// [register](macro@register). Implementations without a health check are not included in
+    /// the report, see [health].
+    pub fn health_check_all(&self) -> health::HealthReport {
+        health::HealthReport {
+            checks: self
+                .impls
+                .iter()
+                .filter_map(|item| {
+                    Some(health::HealthCheck {
+                        name: item.name,
+                        path: item.path,
+                        status: item.health_check()?,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Select one implementation with a strategy, see the [select module](crate::select)
+    pub fn select(
+        &self,
+        strategy: impl select::SelectionStrategy,
+    ) -> Option<&RegisteredImplWrapper<Trait>> {
+        strategy.select(&self.impls)
+    }
+
+    /// Instantiate the first implementation in order of [priority](RegisteredImplWrapper::priority),
+    /// highest first, whose constructor succeeds. Implementations without a constructor, or whose
+    /// fallible constructor fails, are skipped, e.g. to fall back from a hardware accelerated
+    /// implementation to a software one.
+    ///
+    /// ```rust
+    /// trait Renderer {}
+    ///
+    /// struct Gpu;
+    ///
+    /// impl Gpu {
+    ///     fn new() -> Result<Self, &'static str> {
+    ///         Err("No GPU available")
+    ///     }
+    /// }
+    ///
+    /// #[traitreg::register(new, priority = 10)]
+    /// impl Renderer for Gpu {}
+    ///
+    /// #[derive(Default)]
+    /// struct Software;
+    ///
+    /// #[traitreg::register(default)]
+    /// impl Renderer for Software {}
+    ///
+    /// #[traitreg::registry(Renderer)]
+    /// static RENDERER_REGISTRY: () = ();
+    ///
+    /// assert!(RENDERER_REGISTRY.instantiate_first_ok().is_some());
+    /// ```
+    pub fn instantiate_first_ok(&self) -> Option<Trait> {
+        self.iter_by_priority().find_map(|item| item.instantiate())
+    }
+
+    /// Instances constructed when the registry was built, for registries declared with `eager`,
+    /// see [registry](macro@registry). Empty for other registries, and for copies of a registry
+    /// such as [apply_config](Self::apply_config). Instances are shared by every thread using the
+    /// registry, so must be `Sync`, e.g. with a `Sync` supertrait or a registry of
+    /// `dyn Trait + Sync`.
+    ///
+    /// ```rust
+    /// trait Plugin: Sync {
+    ///     fn name(&self) -> &str;
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct Audio;
+    ///
+    /// #[traitreg::register(default)]
+    /// impl Plugin for Audio {
+    ///     fn name(&self) -> &str {
+    ///         "audio"
+    ///     }
+    /// }
+    ///
+    /// #[traitreg::registry(Plugin, eager)]
+    /// static PLUGIN_REGISTRY: () = ();
+    ///
+    /// let names: Vec<&str> = PLUGIN_REGISTRY.instances().iter().map(|p| p.name()).collect();
+    /// assert_eq!(vec!["audio"], names);
+    /// ```
+    pub fn instances(&self) -> &[Trait]
+    where
+        Trait: Sync,
+    {
+        &self.instances.0
+    }
+
+    /// Instantiate all registered implementations which have a constructor
+    pub fn instantiate_all(&self) -> impl Iterator<Item = Trait> + '_ {
+        self.impls.iter().filter_map(|item| item.instantiate())
+    }
+
+    /// Instantiate registered implementations phase by phase, in the order the phases are given,
+    /// e.g. so infrastructure is constructed before the plugins which depend on it. Yields the
+    /// instances of each phase which have a constructor, and only instantiates a phase when it is
+    /// reached, so the application can start the previous phase first. Implementations in no
+    /// given phase are not instantiated.
+    ///
+    /// ```rust
+    /// trait Plugin {}
+    ///
+    /// #[derive(Default)]
+    /// struct Database;
+    ///
+    /// #[traitreg::register(default, phase = core)]
+    /// impl Plugin for Database {}
+    ///
+    /// #[derive(Default)]
+    /// struct Search;
+    ///
+    /// #[traitreg::register(default, phase = extensions)]
+    /// impl Plugin for Search {}
+    ///
+    /// #[traitreg::registry(Plugin)]
+    /// static PLUGIN_REGISTRY: () = ();
+    ///
+    /// for (phase, plugins) in PLUGIN_REGISTRY.instantiate_phases(&["core", "extensions"]) {
+    ///     assert_eq!(1, plugins.len());
+    ///     // Start the plugins of this phase before the next is constructed
+    /// }
+    /// ```
+    pub fn instantiate_phases<'a>(
+        &'a self,
+        phases: &'a [&'a str],
+    ) -> impl Iterator<Item = (&'a str, Vec<Trait>)> + 'a {
+        phases.iter().map(move |phase| {
+            let instances = self
+                .iter_phase(phase)
+                .filter_map(|item| item.instantiate())
+                .collect();
+            (*phase, instances)
+        })
+    }
+
+    /// Instantiate all registered implementations which have a constructor, initializing those
+    /// registered with `async_init` concurrently, see [AsyncInit]. Returns the instances in
+    /// registry order, or the errors of every implementation which failed to construct or
+    /// initialize.
+    ///
+    /// Initialization runs on the task awaiting this future, which is not `Send`, so it should be
+    /// awaited rather than spawned onto a multi-threaded runtime.
+    ///
+    /// ```rust
+    /// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    /// trait Service {}
+    ///
+    /// #[derive(Default)]
+    /// struct Database {
+    ///     connected: bool,
+    /// }
+    ///
+    /// impl traitreg::AsyncInit for Database {
+    ///     type Error = std::io::Error;
+    ///
+    ///     async fn init(&mut self) -> Result<(), Self::Error> {
+    ///         self.connected = true;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// #[traitreg::register(default, async_init)]
+    /// impl Service for Database {}
+    ///
+    /// #[traitreg::registry(Service)]
+    /// static SERVICE_REGISTRY: () = ();
+    ///
+    /// let services = block_on(SERVICE_REGISTRY.init_all()).unwrap();
+    /// assert_eq!(1, services.len());
+    /// ```
+    pub async fn init_all(&self) -> Result<Vec<Trait>, InitErrors> {
+        let results = join_all(
+            self.impls
+                .iter()
+                .map(|item| Box::pin(item.instantiate_async()) as _)
+                .collect(),
+        )
+        .await;
+
+        let mut instances = vec![];
+        let mut errors = vec![];
+        for result in results {
+            match result {
+                Ok(instance) => instances.push(instance),
+                Err(InstantiateError::NoConstructor { .. }) => {}
+                Err(error) => errors.push(error),
+            }
+        }
+        if errors.is_empty() {
+            Ok(instances)
+        } else {
+            Err(InitErrors(errors))
+        }
+    }
+
+    /// Instantiate all registered implementations which have a constructor in parallel, on the
+    /// rayon thread pool. Requires the `rayon` feature, and instances which are `Send`, e.g. from
+    /// a registry declared with the `send` option. Collecting into a `Vec` keeps registry order.
+    ///
+    /// ```rust
+    /// use rayon::iter::ParallelIterator;
+    ///
+    /// trait Shader {}
+    ///
+    /// #[derive(Default)]
+    /// struct Blur;
+    ///
+    /// #[traitreg::register(default)]
+    /// impl Shader for Blur {}
+    ///
+    /// #[traitreg::registry(Shader, send)]
+    /// static SHADER_REGISTRY: () = ();
+    ///
+    /// let shaders: Vec<Box<dyn Shader + Send>> = SHADER_REGISTRY.par_instantiate_all().collect();
+    /// assert_eq!(1, shaders.len());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_instantiate_all(&self) -> impl rayon::iter::ParallelIterator<Item = Trait> + '_
+    where
+        Trait: Send,
+    {
+        use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+        self.impls.par_iter().filter_map(|item| item.instantiate())
+    }
+
+    /// Instantiate all registered implementations which have a constructor
+    #[deprecated(note = "Renamed to `instantiate_all`")]
+    pub fn instanciate_all(&self) -> impl Iterator<Item = Trait> + '_ {
+        self.instantiate_all()
+    }
+}
+
+impl<'a, Trait> IntoIterator for &'a TraitRegStorage<Trait> {
+    type Item = &'a RegisteredImplWrapper<Trait>;
+    type IntoIter = core::slice::Iter<'a, RegisteredImplWrapper<Trait>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.impls.iter()
+    }
+}
+
+#[cfg(feature = "dyn-clone")]
+impl<T: ?Sized + dyn_clone::DynClone> TraitRegStorage<Box<T>> {
+    /// Instantiate all registered implementations which have a constructor once, then clone each
+    /// instance so there are `count` instances of every implementation. Requires the `dyn-clone`
+    /// feature, and `DynClone` as a supertrait of the trait.
+    pub fn instantiate_all_cloned(&self, count: usize) -> impl Iterator<Item = Vec<Box<T>>> + '_ {
+        self.instantiate_all().map(move |template| {
+            let mut instances: Vec<Box<T>> = (1..count)
+                .map(|_| dyn_clone::clone_box(&*template))
+                .collect();
+            if count > 0 {
+                instances.push(template);
+            }
+            instances
+        })
+    }
+}
+
+// Registries are shared between threads through statics, fail to compile if a field ever makes
+// them or their entries `!Send` or `!Sync` for trait objects which are not
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    type NotSendSync = Box<dyn core::any::Any>;
+    assert_send_sync::<TraitRegStorage<NotSendSync>>();
+    assert_send_sync::<TraitRegStorage<MetaOnly>>();
+    assert_send_sync::<RegisteredImplWrapper<NotSendSync>>();
+    assert_send_sync::<snapshot::Snapshot<NotSendSync>>();
+    assert_send_sync::<query::View<'static, NotSendSync>>();
+};
+
+/// Identifier of an implementation within a registry, see [TraitRegStorage::get_by_id]
+///
+/// Ids are small integers, and are stable for as long as the program runs. Registration order
+/// depends on how the program is linked, so ids may change when the program is rebuilt, unless the
+/// registry is declared with `sorted` on [registry](macro@registry). Use the
+/// [name](RegisteredImplWrapper::name) or [path](RegisteredImplWrapper::path) of an implementation
+/// to refer to it between builds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ImplId(u32);
+
+impl ImplId {
+    /// Create an id from its raw value, see [ImplId::to_raw]
+    pub const fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    /// The raw value of the id, e.g. for using it as an index or sending it to another process
+    pub const fn to_raw(self) -> u32 {
+        self.0
+    }
+}
+
+/// Registered implementation
+///
+/// Like [TraitRegStorage], always `Send` and `Sync`.
+#[derive(Clone)]
+pub struct RegisteredImplWrapper<Trait> {
+    instantiate: fn() -> Option<Result<Trait, ConstructError>>,
+    // Set in registries declared with `wrap`, where `instantiate` is then the constructor of the
+    // unwrapped instance, and is only called through this
+    wrap: Option<__private::WrapFn<Trait>>,
+    has_constructor: bool,
+    name: &'static str,
+    path: &'static str,
+    signature: &'static str,
+    file: &'static str,
+    module_path: &'static str,
+    crate_name: &'static str,
+    crate_version: &'static str,
+    trait_name: &'static str,
+    auto_traits: __private::AutoTraits,
+    // Type id of the trait object with the generic arguments and associated types of the
+    // implementation, if the trait has any
+    bindings: Option<core::any::TypeId>,
+    type_id: core::any::TypeId,
+    priority: i32,
+    capabilities: &'static [&'static str],
+    tags: &'static [&'static str],
+    phase: Option<&'static str>,
+    namespace: Option<&'static str>,
+    after: &'static [&'static str],
+    conflicts_with: &'static [&'static str],
+    flags: &'static [(&'static str, bool)],
+    exposed: &'static [__private::Exposed],
+    shutdown: Option<fn()>,
+    health_check: Option<fn() -> health::HealthStatus>,
+    factory: Option<__private::ErasedFactory>,
+    instantiate_args: Option<__private::ErasedFactory>,
+    instantiate_any: Option<__private::InstantiateAny>,
+    emplace: Option<__private::Emplace>,
+    init_async: Option<__private::InitAsync<Trait>>,
+    #[cfg(feature = "metrics")]
+    instantiations: InstantiationCounter,
+    #[cfg(feature = "di")]
+    inject: Option<di::__InjectFn<Trait>>,
+    #[cfg(feature = "di")]
+    lifetime: di::Lifetime,
+}
+
+impl<Trait> RegisteredImplWrapper<Trait> {
+    /// Instantiate type if a constructor has been registered
+    ///
+    /// Returns a heap allocated trait object, `Box<dyn Trait>`, rather than a concrete type, or
+    /// its wrapper in registries declared with `wrap`. Constructors registered with `inject` can
+    /// only be called through a `traitreg::di::Container`, this returns `None` for them, as it does
+    /// for types registered with `async_init`, which are instantiated with
+    /// [instantiate_async](Self::instantiate_async). Also returns `None` when a fallible
+    /// constructor fails, see
+    /// [try_instantiate](Self::try_instantiate) to tell these apart.
+    pub fn instantiate(&self) -> Option<Trait> {
+        self.record_instantiation(self.construct()?.ok())
+    }
+
+    /// Instantiate type, or report why it could not be instantiated
+    ///
+    /// ```rust
+    /// use traitreg::InstantiateError;
+    ///
+    /// trait Storage {}
+    ///
+    /// struct Disk;
+    ///
+    /// impl Disk {
+    ///     fn mount() -> Result<Self, std::io::Error> {
+    ///         Err(std::io::Error::other("no disk"))
+    ///     }
+    /// }
+    ///
+    /// #[traitreg::register(mount)]
+    /// impl Storage for Disk {}
+    ///
+    /// struct Memory;
+    ///
+    /// #[traitreg::register]
+    /// impl Storage for Memory {}
+    ///
+    /// #[traitreg::registry(Storage)]
+    /// static STORAGE_REGISTRY: () = ();
+    ///
+    /// for item in STORAGE_REGISTRY.iter() {
+    ///     match item.try_instantiate() {
+    ///         Ok(_) => unreachable!(),
+    ///         Err(InstantiateError::NoConstructor { name, .. }) => assert_eq!("Memory", name),
+    ///         Err(InstantiateError::ConstructorFailed { error, .. }) => {
+    ///             assert_eq!("no disk", error.to_string())
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn try_instantiate(&self) -> Result<Trait, InstantiateError> {
+        self.finish_instantiate(self.construct())
+    }
+
+    /// Instantiate type, and initialize it if registered with `async_init`, see [AsyncInit].
+    /// Otherwise the same as [try_instantiate](Self::try_instantiate).
+    pub async fn instantiate_async(&self) -> Result<Trait, InstantiateError> {
+        match self.init_async {
+            Some(init_async) => self.finish_instantiate(Some(init_async().await)),
+            None => self.try_instantiate(),
+        }
+    }
+
+    fn finish_instantiate(
+        &self,
+        constructed: Option<Result<Trait, ConstructError>>,
+    ) -> Result<Trait, InstantiateError> {
+        match constructed {
+            Some(Ok(instance)) => {
+                self.count_instantiation();
+                Ok(instance)
+            }
+            Some(Err(error)) => Err(InstantiateError::ConstructorFailed {
+                name: self.name,
+                trait_name: self.trait_name,
+                error,
+            }),
+            None => Err(InstantiateError::NoConstructor {
+                name: self.name,
+                trait_name: self.trait_name,
+            }),
+        }
+    }
+
+    fn construct(&self) -> Option<Result<Trait, ConstructError>> {
+        match self.wrap {
+            // Safety: `instantiate` is the constructor expected by `wrap`, see
+            // `__private::build_wrapped_registry`
+            Some(wrap) => unsafe { wrap(self.instantiate as *const ()) },
+            None => (self.instantiate)(),
+        }
+    }
+
+    /// Instantiate type with a resolver if a constructor has been registered
+    #[cfg(feature = "di")]
+    pub(crate) fn instantiate_with(
+        &self,
+        resolver: &di::Resolver,
+        trait_object: &'static str,
+    ) -> Result<Option<Trait>, di::ResolveError> {
+        match self.inject {
+            Some(inject) => {
+                inject(resolver).map(|instance| self.record_instantiation(Some(instance)))
+            }
+            None => match self.construct() {
+                Some(Ok(instance)) => Ok(self.record_instantiation(Some(instance))),
+                Some(Err(error)) => Err(di::ResolveError::ConstructorFailed {
+                    trait_object,
+                    name: self.name,
+                    message: error.to_string(),
+                }),
+                None => Ok(None),
+            },
+        }
+    }
+
+    /// Instantiate type as `Box<dyn Any>`, if registered with `any` and a constructor
+    pub fn instantiate_any(&self) -> Option<Box<dyn core::any::Any>> {
+        self.record_instantiation(self.instantiate_any.and_then(|instantiate| instantiate()))
+    }
+
+    /// Instantiate type as the concrete type `T`, if registered with `any` and a constructor, and
+    /// the registered type is `T`
+    pub fn instantiate_downcast<T: 'static>(&self) -> Option<Box<T>> {
+        if self.type_id != core::any::TypeId::of::<T>() {
+            return None;
+        }
+        self.instantiate_any()?.downcast().ok()
+    }
+
+    // Does the implementation belong in the registry of a trait, in the same namespace, requiring
+    // auto traits and, for generic traits or traits with associated types, the same generic
+    // arguments and associated types. Registries without bindings, such as those from
+    // `dynamic_registry`, include implementations with any.
+    fn belongs_in(&self, registry: &DeclaredRegistry) -> bool {
+        self.trait_name == registry.trait_
+            && self.namespace == registry.namespace
+            && self.auto_traits.satisfies(&registry.auto_traits)
+            && (registry.bindings.is_none() || self.bindings == registry.bindings)
+    }
+
+    // Copy of the implementation with no constructor, for a registry of a different instance type
+    fn without_constructor<Other>(&self) -> RegisteredImplWrapper<Other> {
+        RegisteredImplWrapper {
+            instantiate: no_constructor::<Other>,
+            wrap: None,
+            has_constructor: self.has_constructor,
+            name: self.name,
+            path: self.path,
+            signature: self.signature,
+            file: self.file,
+            module_path: self.module_path,
+            crate_name: self.crate_name,
+            crate_version: self.crate_version,
+            trait_name: self.trait_name,
+            auto_traits: self.auto_traits,
+            bindings: self.bindings,
+            type_id: self.type_id,
+            priority: self.priority,
+            capabilities: self.capabilities,
+            tags: self.tags,
+            phase: self.phase,
+            namespace: self.namespace,
+            after: self.after,
+            conflicts_with: self.conflicts_with,
+            flags: self.flags,
+            exposed: self.exposed,
+            shutdown: self.shutdown,
+            health_check: self.health_check,
+            factory: self.factory,
+            instantiate_args: None,
+            instantiate_any: self.instantiate_any,
+            emplace: self.emplace,
+            init_async: None,
+            #[cfg(feature = "metrics")]
+            instantiations: self.instantiations,
+            #[cfg(feature = "di")]
+            inject: None,
+            #[cfg(feature = "di")]
+            lifetime: self.lifetime,
+        }
+    }
+
+    // Identifies the implementation independently of how the program is linked
+    fn canonical_key(&self) -> [&'static str; 5] {
+        [
+            self.crate_name,
+            self.crate_version,
+            self.module_path,
+            self.path,
+            self.trait_name,
+        ]
+    }
+
+    // Copy of the implementation for a snapshot, sharing the instantiation count
+    fn duplicate(&self) -> Self {
+        Self {
+            instantiate: self.instantiate,
+            wrap: self.wrap,
+            has_constructor: self.has_constructor,
+            name: self.name,
+            path: self.path,
+            signature: self.signature,
+            file: self.file,
+            module_path: self.module_path,
+            crate_name: self.crate_name,
+            crate_version: self.crate_version,
+            trait_name: self.trait_name,
+            auto_traits: self.auto_traits,
+            bindings: self.bindings,
+            type_id: self.type_id,
+            priority: self.priority,
+            capabilities: self.capabilities,
+            tags: self.tags,
+            phase: self.phase,
+            namespace: self.namespace,
+            after: self.after,
+            conflicts_with: self.conflicts_with,
+            flags: self.flags,
+            exposed: self.exposed,
+            shutdown: self.shutdown,
+            health_check: self.health_check,
+            factory: self.factory,
+            instantiate_args: self.instantiate_args,
+            instantiate_any: self.instantiate_any,
+            emplace: self.emplace,
+            init_async: self.init_async,
+            #[cfg(feature = "metrics")]
+            instantiations: self.instantiations,
+            #[cfg(feature = "di")]
+            inject: self.inject,
+            #[cfg(feature = "di")]
+            lifetime: self.lifetime,
+        }
+    }
+
+    fn record_instantiation<Instance>(&self, instance: Option<Instance>) -> Option<Instance> {
+        if instance.is_some() {
+            self.count_instantiation();
+        }
+        instance
+    }
+
+    fn count_instantiation(&self) {
+        #[cfg(feature = "metrics")]
+        self.instantiations
+            .get()
+            .fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Number of times the type has been instantiated, through any registry. Requires the
+    /// `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn instantiation_count(&self) -> usize {
+        self.instantiations
+            .get()
+            .load(core::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Instantiate type if a constructor has been registered
+    #[deprecated(note = "Renamed to `instantiate`")]
+    pub fn instanciate(&self) -> Option<Trait> {
+        self.instantiate()
+    }
+
+    /// Was this type registered with a constructor. This is recorded when the type is registered,
+    /// so nothing is constructed, and is `false` for metadata only registrations, which
+    /// [instantiate](Self::instantiate) always returns `None` for.
+    pub fn has_constructor(&self) -> bool {
+        self.has_constructor
+    }
+
+    /// The type name, including any generic arguments. e.g. `MyType` or `Block<16>`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The type path. This differs from name when the implementation block is in a different crate
+    /// of module than the type itself. e.g. `MyType` vs `other::module::OtherType`.
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// The header of the implementation block as written, including any generic parameters and
+    /// bounds, e.g. `impl<T: Codec> Codec for Compressed<T>`. For registered functions, the
+    /// signature of the function, e.g. `fn ping()`.
+    pub fn signature(&self) -> &'static str {
+        self.signature
+    }
+
+    /// The file containing the implementation of the trait
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// The module containing the implementation of the trait
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// The package name of the crate containing the implementation of the trait
+    pub fn crate_name(&self) -> &'static str {
+        self.crate_name
+    }
+
+    /// The package version of the crate containing the implementation of the trait
+    pub fn crate_version(&self) -> &'static str {
+        self.crate_version
+    }
+
+    /// The [TypeId](core::any::TypeId) of the type
+    pub fn type_id(&self) -> core::any::TypeId {
+        self.type_id
+    }
+
+    /// The trait name
+    pub fn trait_name(&self) -> &'static str {
+        self.trait_name
+    }
+
+    /// The priority of the implementation, `0` unless registered with a `priority`. Higher
+    /// priorities come first in a [Pipeline](pipeline::Pipeline).
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Capabilities provided by the implementation, declared with `capabilities`
+    pub fn capabilities(&self) -> &'static [&'static str] {
+        self.capabilities
+    }
+
+    /// Tags of the implementation, declared with `tags`
+    pub fn tags(&self) -> &'static [&'static str] {
+        self.tags
+    }
+
+    /// The initialization phase of the implementation, declared with `phase`, see
+    /// [TraitRegStorage::instantiate_phases]
+    pub fn phase(&self) -> Option<&'static str> {
+        self.phase
+    }
+
+    /// The namespace of the implementation, declared with `namespace`. Only registries declared
+    /// with the same namespace include it, see [registry](macro@registry).
+    pub fn namespace(&self) -> Option<&'static str> {
+        self.namespace
+    }
+
+    /// Names of the implementations this one depends on, declared with `after`, see
+    /// [TraitRegStorage::iter_topological]
+    pub fn after(&self) -> &'static [&'static str] {
+        self.after
+    }
+
+    /// Type paths of the implementations this one cannot be registered with, declared with
+    /// `conflicts_with`, see [TraitRegStorage::conflicts]
+    pub fn conflicts_with(&self) -> &'static [&'static str] {
+        self.conflicts_with
+    }
+
+    /// Flags of the implementation, the names and values of the associated consts declared with
+    /// `flags`
+    pub fn flags(&self) -> &'static [(&'static str, bool)] {
+        self.flags
+    }
+
+    /// The value of a flag declared with `flags`, or `None` if the implementation does not declare
+    /// it
+    pub fn flag(&self, name: &str) -> Option<bool> {
+        self.flags
+            .iter()
+            .find(|(flag, _)| *flag == name)
+            .map(|(_, value)| *value)
+    }
+
+    /// The value of an associated const recorded with `expose`, or `None` if the implementation
+    /// does not record it or it is not a `T`
+    pub fn exposed<T: 'static>(&self, name: &str) -> Option<&'static T> {
+        let (_, value) = self.exposed.iter().find(|(exposed, _)| *exposed == name)?;
+        value.as_any().downcast_ref()
+    }
+
+    /// Names and values of the associated consts recorded with `expose`, which can be formatted
+    /// without knowing their types
+    pub fn exposed_values(
+        &self,
+    ) -> impl Iterator<Item = (&'static str, &'static dyn core::fmt::Debug)> {
+        self.exposed
+            .iter()
+            .map(|(name, value)| (*name, value.as_debug()))
+    }
+
+    /// Run the health check of the implementation, if registered with `health_check`
+    pub fn health_check(&self) -> Option<health::HealthStatus> {
+        self.health_check.map(|health_check| health_check())
+    }
+
+    /// Does the implementation provide every required capability
+    pub fn provides(&self, requirements: &[&str]) -> bool {
+        requirements
+            .iter()
+            .all(|requirement| self.capabilities.contains(requirement))
+    }
+
+    /// Is the implementation of the trait in a file. [file](Self::file) is relative to the
+    /// directory the crate was compiled from, usually the workspace root, so paths match when
+    /// they are equal or name the same trailing path components, e.g. `src/plugins/audio.rs`
+    /// matches `crates/app/src/plugins/audio.rs`. `/` and `\` are both path separators.
+    pub fn is_from_file(&self, file: &str) -> bool {
+        let mut own = self.file.rsplit(['/', '\\']);
+        file.rsplit(['/', '\\'])
+            .all(|component| own.next() == Some(component))
+    }
+}
+
+#[cfg(feature = "dyn-clone")]
+impl<T: ?Sized + dyn_clone::DynClone> RegisteredImplWrapper<Box<T>> {
+    /// Clone an instance of a registered implementation. Requires the `dyn-clone` feature, and
+    /// `DynClone` as a supertrait of the trait.
+    pub fn clone_instance(&self, instance: &T) -> Box<T> {
+        dyn_clone::clone_box(instance)
+    }
+}
+
+impl<T: ?Sized> RegisteredImplWrapper<Box<T>> {
+    /// Instantiate type in memory allocated from an arena rather than the global heap, if a
+    /// constructor has been registered. Returns `None` when the arena fails to allocate, or a
+    /// fallible constructor fails. See [arena].
+    pub fn instantiate_in<'a, A: arena::Arena + ?Sized>(
+        &self,
+        arena: &'a A,
+    ) -> Option<arena::ArenaInstance<'a, T>> {
+        let emplace = self.emplace?;
+        let ptr = arena.alloc(emplace.layout)?;
+
+        let mut object = core::mem::MaybeUninit::<*mut T>::uninit();
+        // Safety: The memory was allocated with the layout of the registered type. The trait
+        // object pointer written has the vtable of the registered trait, which is `T` up to auto
+        // traits, which do not change the vtable, as when instantiating a `Box<T>`
+        let object = unsafe {
+            if !(emplace.write)(ptr.as_ptr(), object.as_mut_ptr() as *mut ()) {
+                return None;
+            }
+            object.assume_init()
+        };
+
+        // Safety: The pointer was just initialised with a valid value
+        self.record_instantiation(Some(unsafe { arena::ArenaInstance::new(object) }))
+    }
+
+    /// Instantiate type with arguments, if registered with a constructor taking `args`. Requires the
+    /// `args` option on [registry](macro@registry). Returns `None` when a fallible constructor
+    /// fails.
+    pub fn instantiate_args(&self, args: T::Args<'_>) -> Option<Box<T>>
+    where
+        T: ConstructorArgs,
+    {
+        let constructor = self.instantiate_args?;
+        // Safety: The constructor was erased by the implementation of `ConstructorArgs` for the
+        // trait object of the registered trait, which has the same arguments as the
+        // implementation for `T`
+        self.record_instantiation(unsafe { T::__call(constructor, args) }?.ok())
+    }
+
+    /// The factory function of the implementation, if registered with `factory`. Requires the
+    /// `factory` option on [registry](macro@registry).
+    pub fn factory(&self) -> Option<T::Fn>
+    where
+        T: Factory,
+    {
+        // Safety: The factory was erased by the implementation of `Factory` for the trait object
+        // of the registered trait, which has the same signature as the implementation for `T`
+        self.factory.map(|factory| unsafe { T::__restore(factory) })
+    }
+
+    /// Instantiate type as a trait object of a supertrait, if a constructor has been registered.
+    /// Requires the `upcast` option on [registry](macro@registry).
+    pub fn instantiate_as<Base: ?Sized>(&self) -> Option<Box<Base>>
+    where
+        T: Upcast<Base>,
+    {
+        self.instantiate().map(T::upcast)
+    }
+}
+
+/// Registered implementations are equal when they are implementations of the same trait on the same
+/// type, even if they are from different registries.
+impl<Trait, OtherTrait> PartialEq<RegisteredImplWrapper<OtherTrait>>
+    for RegisteredImplWrapper<Trait>
+{
+    fn eq(&self, other: &RegisteredImplWrapper<OtherTrait>) -> bool {
+        self.type_id == other.type_id && self.trait_name == other.trait_name
+    }
+}
+
+impl<Trait> Eq for RegisteredImplWrapper<Trait> {}
+
+impl<Trait> core::hash::Hash for RegisteredImplWrapper<Trait> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.type_id.hash(state);
+        self.trait_name.hash(state);
+    }
+}
+
+/// Serializes the metadata of the registered implementation. Requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl<Trait> serde::Serialize for RegisteredImplWrapper<Trait> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let len = if cfg!(feature = "metrics") { 18 } else { 17 };
+        let mut state = serializer.serialize_struct("RegisteredImpl", len)?;
+        state.serialize_field("name", self.name)?;
+        state.serialize_field("path", self.path)?;
+        state.serialize_field("signature", self.signature)?;
+        state.serialize_field("trait_name", self.trait_name)?;
+        state.serialize_field("has_constructor", &self.has_constructor)?;
+        state.serialize_field("priority", &self.priority)?;
+        state.serialize_field("capabilities", self.capabilities)?;
+        state.serialize_field("tags", self.tags)?;
+        state.serialize_field("phase", &self.phase)?;
+        state.serialize_field("namespace", &self.namespace)?;
+        state.serialize_field("after", self.after)?;
+        state.serialize_field("conflicts_with", self.conflicts_with)?;
+        state.serialize_field("flags", self.flags)?;
+        state.serialize_field("module_path", self.module_path)?;
+        state.serialize_field("file", self.file)?;
+        state.serialize_field("crate_name", self.crate_name)?;
+        state.serialize_field("crate_version", self.crate_version)?;
+        #[cfg(feature = "metrics")]
+        state.serialize_field("instantiation_count", &self.instantiation_count())?;
+        state.end()
+    }
+}
+
+/// One line summary of the registration, `module::path: Type implements Trait (ctor: yes)`, with
+/// the module containing the implementation
+impl<Trait> core::fmt::Display for RegisteredImplWrapper<Trait> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "{}: {} implements {} (ctor: {})",
+            self.module_path,
+            self.name,
+            self.trait_name,
+            if self.has_constructor { "yes" } else { "no" }
+        )
     }
 }
 
+// Only formats metadata recorded at registration, so logging a registration never runs its
+// constructor
 impl<Trait> core::fmt::Debug for RegisteredImplWrapper<Trait> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
         f.debug_struct("RegisteredImpl")
             .field("Type Name", &self.name)
             .field("Type Path", &self.path)
+            .field("Signature", &self.signature)
             .field("Trait Name", &self.trait_name)
             .field("Has Constructor", &self.has_constructor)
+            .field("Priority", &self.priority)
+            .field("Capabilities", &self.capabilities)
+            .field("Tags", &self.tags)
+            .field("Phase", &self.phase)
+            .field("Namespace", &self.namespace)
+            .field("After", &self.after)
+            .field("Conflicts With", &self.conflicts_with)
+            .field("Flags", &self.flags)
+            .field("Exposed", &self.exposed)
             .field("Module Path", &self.module_path)
+            .field("Crate", &self.crate_name)
             .field("File", &self.file)
             .finish()
     }
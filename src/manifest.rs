@@ -0,0 +1,133 @@
+//! Manifest of registrations embedded in binaries.
+//!
+//! Every registration writes a compact record to a dedicated section of the binary, named
+//! `traitreg_manifest` on ELF platforms, `__DATA,__traitreg` on apple platforms and `.trgman` on
+//! windows. Records can be read from a plugin or executable without loading it or running any of
+//! its code, so a host can inspect what a plugin offers before loading it.
+//!
+//! ```rust,no_run
+//! let data = std::fs::read("target/debug/libmy_plugin.so").unwrap();
+//! for entry in traitreg::manifest::entries(&data) {
+//!     println!("{} implements {}", entry.name, entry.trait_name);
+//! }
+//! ```
+//!
+//! Each record starts with `traitreg-manifest`, followed by the fields of [ManifestEntry] in order,
+//! separated by `0x1f` and terminated by `0x1e`, with `has_constructor` written as `1` or `0`.
+//! Records are found by scanning for the prefix rather than parsing the binary format, so
+//! [entries] works on any binary, or on the contents of just the manifest section.
+//!
+//! The manifest is written when the binary is built, so it can not include anything only known at
+//! runtime. With the `metrics` feature, [instantiation_counts] reports how often each registered
+//! implementation has been instantiated in the running process.
+
+const PREFIX: &[u8] = b"traitreg-manifest\x1f";
+const FIELD_SEPARATOR: char = '\x1f';
+const RECORD_TERMINATOR: u8 = 0x1e;
+// Longest record which is parsed, longer matches of the prefix are not records
+const MAX_RECORD_LEN: usize = 4096;
+
+/// Registration read from a manifest
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ManifestEntry<'a> {
+    /// The type name, see [RegisteredImplWrapper::name](crate::RegisteredImplWrapper::name)
+    pub name: &'a str,
+    /// The type path, see [RegisteredImplWrapper::path](crate::RegisteredImplWrapper::path)
+    pub path: &'a str,
+    /// The trait name
+    pub trait_name: &'a str,
+    /// The module containing the implementation of the trait
+    pub module_path: &'a str,
+    /// The crate containing the implementation of the trait
+    pub crate_name: &'a str,
+    /// The version of the crate containing the implementation of the trait
+    pub crate_version: &'a str,
+    /// Was the type registered with a constructor, see
+    /// [RegisteredImplWrapper::has_constructor](crate::RegisteredImplWrapper::has_constructor).
+    /// `None` for registrations in binaries built with versions of traitreg before this was
+    /// recorded.
+    pub has_constructor: Option<bool>,
+}
+
+impl<'a> ManifestEntry<'a> {
+    fn parse(record: &'a [u8]) -> Option<Self> {
+        let record = core::str::from_utf8(record).ok()?;
+        let mut fields = record.split(FIELD_SEPARATOR);
+        let entry = Self {
+            name: fields.next()?,
+            path: fields.next()?,
+            trait_name: fields.next()?,
+            module_path: fields.next()?,
+            crate_name: fields.next()?,
+            crate_version: fields.next()?,
+            has_constructor: match fields.next() {
+                Some("1") => Some(true),
+                Some("0") => Some(false),
+                Some(_) => return None,
+                None => None,
+            },
+        };
+
+        fields.next().is_none().then_some(entry)
+    }
+}
+
+/// Read the manifest entries in a binary, or in the contents of its manifest section
+pub fn entries(data: &[u8]) -> impl Iterator<Item = ManifestEntry<'_>> {
+    let mut remaining = data;
+    core::iter::from_fn(move || loop {
+        let start = remaining
+            .windows(PREFIX.len())
+            .position(|window| window == PREFIX)?;
+        remaining = &remaining[start + PREFIX.len()..];
+
+        let Some(len) = remaining
+            .iter()
+            .take(MAX_RECORD_LEN)
+            .position(|byte| *byte == RECORD_TERMINATOR)
+        else {
+            continue;
+        };
+
+        let (record, rest) = remaining.split_at(len);
+        if let Some(entry) = ManifestEntry::parse(record) {
+            remaining = &rest[1..];
+            return Some(entry);
+        }
+    })
+}
+
+/// Number of instantiations of a registered implementation in the running process. Requires the
+/// `metrics` feature.
+#[cfg(feature = "metrics")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InstantiationCount {
+    /// The type name, see [RegisteredImplWrapper::name](crate::RegisteredImplWrapper::name)
+    pub name: &'static str,
+    /// The type path, see [RegisteredImplWrapper::path](crate::RegisteredImplWrapper::path)
+    pub path: &'static str,
+    /// The trait name
+    pub trait_name: &'static str,
+    /// The module containing the implementation of the trait
+    pub module_path: &'static str,
+    /// Number of times the type has been instantiated, through any registry
+    pub count: usize,
+}
+
+/// Instantiation counts of every registered implementation in the running process, including
+/// implementations which have never been instantiated. Requires the `metrics` feature.
+#[cfg(feature = "metrics")]
+pub fn instantiation_counts() -> Vec<InstantiationCount> {
+    crate::registrations()
+        .iter()
+        .map(|item| InstantiationCount {
+            name: item.name,
+            path: item.path,
+            trait_name: item.trait_name,
+            module_path: item.module_path,
+            count: item.instantiation_count(),
+        })
+        .collect()
+}
@@ -0,0 +1,132 @@
+//! Middleware pipelines built from registries.
+//!
+//! Middleware is registered as implementations of a trait with [Middleware] as a supertrait, and
+//! run in order of priority, highest first. Each middleware can modify the request, pass it on to
+//! the rest of the pipeline with [Next::run], and modify the response.
+//!
+//! ```rust
+//! use traitreg::pipeline::{Middleware, Next, Pipeline};
+//!
+//! trait RequestMiddleware: Middleware<String, String> {}
+//!
+//! #[derive(Default)]
+//! struct Trim;
+//!
+//! impl Middleware<String, String> for Trim {
+//!     fn handle(&self, request: String, next: Next<'_, String, String>) -> String {
+//!         next.run(request.trim().to_string())
+//!     }
+//! }
+//!
+//! #[traitreg::register(default, priority = 10)]
+//! impl RequestMiddleware for Trim {}
+//!
+//! #[derive(Default)]
+//! struct Exclaim;
+//!
+//! impl Middleware<String, String> for Exclaim {
+//!     fn handle(&self, request: String, next: Next<'_, String, String>) -> String {
+//!         next.run(request) + "!"
+//!     }
+//! }
+//!
+//! #[traitreg::register(default)]
+//! impl RequestMiddleware for Exclaim {}
+//!
+//! #[traitreg::registry(RequestMiddleware)]
+//! static MIDDLEWARE_REGISTRY: () = ();
+//!
+//! let pipeline = Pipeline::new(&MIDDLEWARE_REGISTRY);
+//! let response = pipeline.handle(" hello ".to_string(), |request| request.to_uppercase());
+//! assert_eq!("HELLO!", response);
+//! ```
+
+use crate::TraitRegStorage;
+
+/// Middleware in a [Pipeline]
+pub trait Middleware<Request, Response> {
+    /// Handle a request, usually by passing it on to the rest of the pipeline with [Next::run]
+    fn handle(&self, request: Request, next: Next<'_, Request, Response>) -> Response;
+}
+
+/// The rest of a [Pipeline] after the current middleware
+pub struct Next<'a, Request, Response> {
+    chain: &'a dyn Chain<Request, Response>,
+    index: usize,
+}
+
+impl<Request, Response> Next<'_, Request, Response> {
+    /// Pass a request on to the rest of the pipeline, returning the response
+    pub fn run(self, request: Request) -> Response {
+        self.chain.run(self.index, request)
+    }
+}
+
+// Runs the middleware in a pipeline from an index, then the endpoint
+trait Chain<Request, Response> {
+    fn run(&self, index: usize, request: Request) -> Response;
+}
+
+struct PipelineChain<'a, T: ?Sized, Endpoint> {
+    middleware: &'a [Box<T>],
+    endpoint: &'a Endpoint,
+}
+
+impl<T, Endpoint, Request, Response> Chain<Request, Response> for PipelineChain<'_, T, Endpoint>
+where
+    T: ?Sized + Middleware<Request, Response>,
+    Endpoint: Fn(Request) -> Response,
+{
+    fn run(&self, index: usize, request: Request) -> Response {
+        match self.middleware.get(index) {
+            Some(middleware) => middleware.handle(
+                request,
+                Next {
+                    chain: self,
+                    index: index + 1,
+                },
+            ),
+            None => (self.endpoint)(request),
+        }
+    }
+}
+
+/// Pipeline of middleware instantiated from a registry, in order of priority
+pub struct Pipeline<T: ?Sized> {
+    middleware: Vec<Box<T>>,
+}
+
+impl<T: ?Sized> Pipeline<T> {
+    /// Instantiate all registered implementations which have a constructor, ordered by
+    /// [priority](crate::RegisteredImplWrapper::priority) with the highest first. Implementations
+    /// with the same priority keep their order in the registry.
+    pub fn new(registry: &TraitRegStorage<Box<T>>) -> Self {
+        Self {
+            middleware: registry
+                .iter_by_priority()
+                .filter_map(|item| item.instantiate())
+                .collect(),
+        }
+    }
+
+    /// Middleware in the pipeline, in the order it is run
+    pub fn middleware(&self) -> &[Box<T>] {
+        &self.middleware
+    }
+
+    /// Pass a request through every middleware in the pipeline, and finally to the endpoint
+    pub fn handle<Request, Response>(
+        &self,
+        request: Request,
+        endpoint: impl Fn(Request) -> Response,
+    ) -> Response
+    where
+        T: Middleware<Request, Response>,
+    {
+        PipelineChain {
+            middleware: &self.middleware,
+            endpoint: &endpoint,
+        }
+        .run(0, request)
+    }
+}
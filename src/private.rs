@@ -0,0 +1,625 @@
+//! Items used by code generated by the macros of this crate. These are not part of the public API,
+//! and may change in any release.
+
+#[cfg(feature = "metrics")]
+use crate::InstantiationCounter;
+use crate::{RegisteredImplWrapper, TraitRegStorage};
+
+/// Metadata and constructors of a registered implementation, read when it is added to the registry
+///
+/// # Safety
+///
+/// Only implemented by [register](macro@crate::register). The constants are trusted to describe
+/// the implementing type, in particular `TRAIT_NAME` and `AUTO_TRAITS` are relied on when
+/// converting type erased constructors back to trait objects.
+pub unsafe trait RegisteredImpl<Trait> {
+    const INSTANTIATE: fn() -> Option<Result<Trait, crate::ConstructError>>;
+    const HAS_CONSTRUCTOR: bool;
+    const NAME: &'static str;
+    const PATH: &'static str;
+    const SIGNATURE: &'static str;
+    const FILE: &'static str;
+    const MODULE_PATH: &'static str;
+    const CRATE_NAME: &'static str;
+    const CRATE_VERSION: &'static str;
+    const TRAIT_NAME: &'static str;
+    const AUTO_TRAITS: fn() -> AutoTraits;
+    const TYPE_ID: fn() -> core::any::TypeId;
+    // Type id of `Box<dyn Trait<T, Assoc = ..>>` with the generic arguments and associated types
+    // of the impl block, so registries of other instances of the same trait leave it out
+    const BINDINGS: Option<fn() -> core::any::TypeId> = None;
+    const PRIORITY: i32 = 0;
+    const CAPABILITIES: &'static [&'static str] = &[];
+    const TAGS: &'static [&'static str] = &[];
+    const PHASE: Option<&'static str> = None;
+    const NAMESPACE: Option<&'static str> = None;
+    const AFTER: &'static [&'static str] = &[];
+    const CONFLICTS_WITH: &'static [&'static str] = &[];
+    const FLAGS: &'static [(&'static str, bool)] = &[];
+    const EXPOSED: &'static [Exposed] = &[];
+    const SHUTDOWN: Option<fn()> = None;
+    const HEALTH_CHECK: Option<fn() -> crate::health::HealthStatus> = None;
+    const FACTORY: Option<fn() -> ErasedFactory> = None;
+    const INSTANTIATE_ARGS: Option<fn() -> ErasedFactory> = None;
+    const INSTANTIATE_ANY: Option<InstantiateAny> = None;
+    const EMPLACE: Option<Emplace> = None;
+    const INIT_ASYNC: Option<InitAsync<Trait>> = None;
+    #[cfg(feature = "di")]
+    const INJECT: Option<crate::di::__InjectFn<Trait>> = None;
+    #[cfg(feature = "di")]
+    const LIFETIME: crate::di::Lifetime = crate::di::Lifetime::Transient;
+}
+
+// Constructor of a registered type registered with `any`, returning `None` if a fallible
+// constructor fails
+pub type InstantiateAny = fn() -> Option<Box<dyn core::any::Any>>;
+
+// Constructor of a registered type registered with `async_init`, which also runs its
+// `crate::AsyncInit` hook
+pub type InitAsync<Trait> = fn() -> core::pin::Pin<
+    Box<dyn core::future::Future<Output = Result<Trait, crate::ConstructError>>>,
+>;
+
+// Factory function of a registered type with the signature declared by its registry, see
+// `crate::Factory`, or constructor taking the arguments declared by its registry, see
+// `crate::ConstructorArgs`
+pub type ErasedFactory = fn();
+
+// Constructor of a registered type taking the arguments declared by its registry
+pub type ArgsConstructor<T> = for<'a> fn(
+    <T as crate::ConstructorArgs>::Args<'a>,
+) -> Option<Result<Box<T>, crate::ConstructError>>;
+
+// Calls a constructor with the arguments in a tuple, for constructors registered with `args`
+pub trait CallArgs<Args> {
+    type Output;
+
+    fn call_args(&self, args: Args) -> Self::Output;
+}
+
+macro_rules! impl_call_args {
+    ($($arg:ident),*) => {
+        impl<Func: Fn($($arg),*) -> Output, Output, $($arg),*> CallArgs<($($arg,)*)> for Func {
+            type Output = Output;
+
+            #[allow(non_snake_case)]
+            fn call_args(&self, ($($arg,)*): ($($arg,)*)) -> Output {
+                self($($arg),*)
+            }
+        }
+    };
+}
+
+impl_call_args!(A);
+impl_call_args!(A, B);
+impl_call_args!(A, B, C);
+impl_call_args!(A, B, C, D);
+impl_call_args!(A, B, C, D, E);
+impl_call_args!(A, B, C, D, E, F);
+impl_call_args!(A, B, C, D, E, F, G);
+impl_call_args!(A, B, C, D, E, F, G, H);
+
+// Name and value of an associated const recorded with `expose`
+pub type Exposed = (&'static str, &'static dyn ExposedValue);
+
+// Values recorded with `expose` can be downcast or formatted
+pub trait ExposedValue: core::any::Any + core::fmt::Debug + Sync {
+    fn as_any(&self) -> &dyn core::any::Any;
+
+    fn as_debug(&self) -> &dyn core::fmt::Debug;
+}
+
+impl<T: core::any::Any + core::fmt::Debug + Sync> ExposedValue for T {
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+
+    fn as_debug(&self) -> &dyn core::fmt::Debug {
+        self
+    }
+}
+
+/// Constructor of a registered type into memory provided by an [Arena](crate::arena::Arena)
+#[derive(Clone, Copy)]
+pub struct Emplace {
+    pub layout: core::alloc::Layout,
+    // Writes the value to the first pointer, and a `*mut dyn Trait` to it to the second. Returns
+    // `false` without writing anything if a fallible constructor fails.
+    pub write: unsafe fn(*mut u8, *mut ()) -> bool,
+}
+
+// Converts the return value of registered constructors, which can return either `Self` or
+// `Result<Self, E>` for any error type
+pub trait Constructed<T> {
+    fn into_result(self) -> Result<T, crate::ConstructError>;
+}
+
+impl<T> Constructed<T> for T {
+    fn into_result(self) -> Result<T, crate::ConstructError> {
+        Ok(self)
+    }
+}
+
+impl<T, E: Into<Box<dyn std::error::Error + Send + Sync>>> Constructed<T> for Result<T, E> {
+    fn into_result(self) -> Result<T, crate::ConstructError> {
+        self.map_err(crate::ConstructError::new)
+    }
+}
+
+// Constructor detection for the `auto` option on `register`. The registered type's `new` is
+// named as `Type::new` with a trait in scope providing `new` for every type, which returns
+// `NoNew`, so an inherent `new` takes precedence. The trait is generated with the registration, so
+// the compiler does not suggest importing it for other calls of `new`.
+pub struct NoNew<T>(pub core::marker::PhantomData<T>);
+
+// `AutoDefault::<Type>::DEFAULT` is `Some` when the type implements `Default`, since inherent
+// associated items take precedence over those of `NoDefault`, which must be in scope
+pub struct AutoDefault<T>(core::marker::PhantomData<T>);
+
+impl<T: Default> AutoDefault<T> {
+    pub const DEFAULT: Option<fn() -> T> = Some(T::default);
+}
+
+pub trait NoDefault<T> {
+    const DEFAULT: Option<fn() -> T> = None;
+}
+
+impl<T> NoDefault<T> for AutoDefault<T> {}
+
+// Converts the return value of `Type::new()`, falling back to `Default` when there is no `new`
+pub trait AutoConstructed<T> {
+    const HAS_NEW: bool;
+
+    fn into_auto(self, default: Option<fn() -> T>) -> Option<Result<T, crate::ConstructError>>;
+}
+
+impl<T> AutoConstructed<T> for T {
+    const HAS_NEW: bool = true;
+
+    fn into_auto(self, _: Option<fn() -> T>) -> Option<Result<T, crate::ConstructError>> {
+        Some(Ok(self))
+    }
+}
+
+impl<T, E: Into<Box<dyn std::error::Error + Send + Sync>>> AutoConstructed<T> for Result<T, E> {
+    const HAS_NEW: bool = true;
+
+    fn into_auto(self, _: Option<fn() -> T>) -> Option<Result<T, crate::ConstructError>> {
+        Some(self.map_err(crate::ConstructError::new))
+    }
+}
+
+impl<T> AutoConstructed<T> for NoNew<T> {
+    const HAS_NEW: bool = false;
+
+    fn into_auto(self, default: Option<fn() -> T>) -> Option<Result<T, crate::ConstructError>> {
+        default.map(|default| Ok(default()))
+    }
+}
+
+// `Type::new` may take arguments or return a type `AutoConstructed` is not implemented for, so it
+// is probed with autoref specialization. `ProbeNew` is implemented for `AutoNewProbe` when `new`
+// can be called as `Type::new()`, and `ProbeNoNew` for `&AutoNewProbe` otherwise, in which case the
+// type falls back to `Default`.
+pub struct AutoNewProbe<T, F>(pub F, pub core::marker::PhantomData<T>);
+
+pub trait ProbeNew<T, R> {
+    fn __new(&self) -> R;
+}
+
+impl<T, F: Fn() -> R, R: AutoConstructed<T>> ProbeNew<T, R> for AutoNewProbe<T, F> {
+    fn __new(&self) -> R {
+        (self.0)()
+    }
+}
+
+pub trait ProbeNoNew<T> {
+    fn __new(&self) -> NoNew<T> {
+        NoNew(core::marker::PhantomData)
+    }
+}
+
+impl<T, F> ProbeNoNew<T> for &AutoNewProbe<T, F> {}
+
+/// Was a constructor detected for the `auto` option, taking a closure calling `__new` on the
+/// `AutoNewProbe` of `Type::new`, which is never called, and `AutoDefault::<Type>::DEFAULT`
+pub const fn has_auto_constructor<T, R: AutoConstructed<T>, C: FnOnce() -> R>(
+    _new: &C,
+    default: Option<fn() -> T>,
+) -> bool {
+    R::HAS_NEW || default.is_some()
+}
+
+/// Implementation at an index of an enum generated by [static_dispatch](macro@crate::static_dispatch)
+pub trait StaticDispatchVariant<const INDEX: usize> {
+    type Type;
+}
+
+/// Auto traits implemented by a registered type, or required by a registry
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AutoTraits {
+    pub send: bool,
+    pub sync: bool,
+}
+
+impl AutoTraits {
+    pub(crate) fn satisfies(&self, required: &AutoTraits) -> bool {
+        (self.send || !required.send) && (self.sync || !required.sync)
+    }
+}
+
+// Auto traits of a registered type are detected with autoref specialization, the `Probe*` traits
+// are implemented for `AutoTraitProbe<T>` when `T` implements the auto trait, and
+// `ProbeNot*` traits for `&AutoTraitProbe<T>` otherwise. Since the registered types are
+// concrete, method resolution picks the former wherever it applies.
+pub struct AutoTraitProbe<T: ?Sized>(pub core::marker::PhantomData<T>);
+
+pub trait ProbeSend {
+    fn __is_send(&self) -> bool {
+        true
+    }
+}
+
+impl<T: ?Sized + Send> ProbeSend for AutoTraitProbe<T> {}
+
+pub trait ProbeNotSend {
+    fn __is_send(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ?Sized> ProbeNotSend for &AutoTraitProbe<T> {}
+
+pub trait ProbeSync {
+    fn __is_sync(&self) -> bool {
+        true
+    }
+}
+
+impl<T: ?Sized + Sync> ProbeSync for AutoTraitProbe<T> {}
+
+pub trait ProbeNotSync {
+    fn __is_sync(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ?Sized> ProbeNotSync for &AutoTraitProbe<T> {}
+
+// Registrations submitted before `main()` which have not yet been added to the registry. Pushed to
+// without locking, and drained when a registry is built, so the registry mutex is locked once per
+// registry rather than once per registration.
+pub(crate) static PENDING: core::sync::atomic::AtomicPtr<Registration> =
+    core::sync::atomic::AtomicPtr::new(core::ptr::null_mut());
+
+/// Node in the list of pending registrations, one static per registered implementation
+pub struct Registration {
+    pub(crate) register: fn(&mut Vec<RegisteredImplWrapper<Box<u32>>>),
+    pub(crate) name: &'static str,
+    pub(crate) path: &'static str,
+    pub(crate) trait_name: &'static str,
+    pub(crate) next: core::sync::atomic::AtomicPtr<Registration>,
+}
+
+impl Registration {
+    pub const fn new(
+        register: fn(&mut Vec<RegisteredImplWrapper<Box<u32>>>),
+        name: &'static str,
+        path: &'static str,
+        trait_name: &'static str,
+    ) -> Self {
+        Self {
+            register,
+            name,
+            path,
+            trait_name,
+            next: core::sync::atomic::AtomicPtr::new(core::ptr::null_mut()),
+        }
+    }
+
+    // Add the registration to a registry. Guards are user code which runs before `main()`, often
+    // in a constructor where unwinding would abort the process, so a panic is caught and recorded
+    // instead, and the implementation is not registered. The failure is handled by the policy of
+    // the first registry of the trait to be built.
+    pub(crate) fn register_catching(&self, registry: &mut Vec<RegisteredImplWrapper<Box<u32>>>) {
+        let len = registry.len();
+        let result =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| (self.register)(registry)));
+        let Err(payload) = result else {
+            return;
+        };
+        registry.truncate(len);
+
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        };
+        crate::__TRAITREG_PANICS
+            .lock()
+            .expect("Traitreg internal mutex poisoned")
+            .push((
+                crate::RegistrationPanic {
+                    name: self.name,
+                    path: self.path,
+                    trait_name: self.trait_name,
+                    message,
+                },
+                false,
+            ));
+    }
+
+    pub fn submit(&'static self) {
+        use core::sync::atomic::Ordering;
+
+        if crate::is_frozen() {
+            let mut rejected = vec![];
+            self.register_catching(&mut rejected);
+            crate::handle_failures(self.trait_name, None);
+            for item in rejected {
+                eprintln!(
+                    "traitreg: error: `{}` implements `{}` but was registered after \
+                    `traitreg::freeze()`, it is not added to any registry",
+                    item.path, item.trait_name
+                );
+            }
+            return;
+        }
+
+        let node = self as *const Self as *mut Self;
+        let mut head = PENDING.load(Ordering::Acquire);
+        loop {
+            self.next.store(head, Ordering::Relaxed);
+            match PENDING.compare_exchange_weak(head, node, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+
+        // Registrations are otherwise added lazily, hooks are called as soon as they are made
+        if crate::has_register_hooks() {
+            crate::notify_register_hooks();
+        }
+    }
+}
+
+// Values submitted with `submit!` which have not yet been added to a collection, like `PENDING`
+pub(crate) static SUBMISSIONS: core::sync::atomic::AtomicPtr<Submission> =
+    core::sync::atomic::AtomicPtr::new(core::ptr::null_mut());
+
+/// Node in the list of pending submissions, one static per submitted value
+pub struct Submission {
+    pub(crate) value: fn() -> &'static dyn core::any::Any,
+    pub(crate) next: core::sync::atomic::AtomicPtr<Submission>,
+}
+
+impl Submission {
+    pub const fn new(value: fn() -> &'static dyn core::any::Any) -> Self {
+        Self {
+            value,
+            next: core::sync::atomic::AtomicPtr::new(core::ptr::null_mut()),
+        }
+    }
+
+    pub fn submit(&'static self) {
+        use core::sync::atomic::Ordering;
+
+        let node = self as *const Self as *mut Self;
+        let mut head = SUBMISSIONS.load(Ordering::Acquire);
+        loop {
+            self.next.store(head, Ordering::Relaxed);
+            match SUBMISSIONS.compare_exchange_weak(head, node, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+}
+
+/// Copy a manifest record into an array, so it can be stored inline in a static, followed by the
+/// `has_constructor` field and the record terminator. `N` is the length of the record plus 2.
+pub const fn manifest_bytes<const N: usize>(manifest: &str, has_constructor: bool) -> [u8; N] {
+    let manifest = manifest.as_bytes();
+    let mut bytes = [0; N];
+    let mut index = 0;
+    while index < N - 2 {
+        bytes[index] = manifest[index];
+        index += 1;
+    }
+    bytes[N - 2] = if has_constructor { b'1' } else { b'0' };
+    bytes[N - 1] = 0x1e;
+    bytes
+}
+
+pub fn register_impl<Trait, Type: RegisteredImpl<Trait>>(
+    registry: &mut Vec<RegisteredImplWrapper<Box<u32>>>,
+) {
+    let wrapper = RegisteredImplWrapper::<Trait> {
+        instantiate: Type::INSTANTIATE,
+        wrap: None,
+        has_constructor: Type::HAS_CONSTRUCTOR,
+        name: Type::NAME,
+        path: Type::PATH,
+        signature: Type::SIGNATURE,
+        file: Type::FILE,
+        module_path: Type::MODULE_PATH,
+        crate_name: Type::CRATE_NAME,
+        crate_version: Type::CRATE_VERSION,
+        trait_name: Type::TRAIT_NAME,
+        auto_traits: (Type::AUTO_TRAITS)(),
+        bindings: Type::BINDINGS.map(|type_id| type_id()),
+        type_id: (Type::TYPE_ID)(),
+        priority: Type::PRIORITY,
+        capabilities: Type::CAPABILITIES,
+        tags: Type::TAGS,
+        phase: Type::PHASE,
+        namespace: Type::NAMESPACE,
+        after: Type::AFTER,
+        conflicts_with: Type::CONFLICTS_WITH,
+        flags: Type::FLAGS,
+        exposed: Type::EXPOSED,
+        shutdown: Type::SHUTDOWN,
+        health_check: Type::HEALTH_CHECK,
+        factory: Type::FACTORY.map(|erase| erase()),
+        instantiate_args: Type::INSTANTIATE_ARGS.map(|erase| erase()),
+        instantiate_any: Type::INSTANTIATE_ANY,
+        emplace: Type::EMPLACE,
+        init_async: Type::INIT_ASYNC,
+        #[cfg(feature = "metrics")]
+        instantiations: InstantiationCounter::new(),
+        #[cfg(feature = "di")]
+        inject: Type::INJECT,
+        #[cfg(feature = "di")]
+        lifetime: Type::LIFETIME,
+    };
+
+    // Safety: Access to this type would be UB, but we only access this value after transmuting it
+    // back to the original type. In the mean time storing a fn ptr with a different signature will
+    // not modify the memory layout of RegisteredImplWrapper, so it is safe to store in a Vec.
+    let wrapper: RegisteredImplWrapper<Box<u32>> = unsafe { core::mem::transmute(wrapper) };
+
+    registry.push(wrapper);
+}
+
+// Record a registry declared with `registry`, for `unclaimed_registrations`
+fn declare(
+    trait_: &'static str,
+    namespace: Option<&'static str>,
+    auto_traits: AutoTraits,
+    bindings: Option<core::any::TypeId>,
+) -> crate::DeclaredRegistry {
+    let registry = crate::DeclaredRegistry {
+        trait_,
+        namespace,
+        auto_traits,
+        bindings,
+    };
+    crate::__TRAITREG_DECLARED
+        .lock()
+        .expect("Traitreg internal mutex poisoned")
+        .push(registry);
+    registry
+}
+
+/// Build the storage of a registry, see [registry](macro@crate::registry)
+pub fn build_registry<Trait: 'static>(
+    trait_: &'static str,
+    namespace: Option<&'static str>,
+    auto_traits: AutoTraits,
+    bindings: Option<core::any::TypeId>,
+    on_failure: Option<crate::FailurePolicy>,
+    sorted: bool,
+    eager: bool,
+) -> TraitRegStorage<Trait> {
+    let registry = declare(trait_, namespace, auto_traits, bindings);
+    TraitRegStorage::build(registry, on_failure, sorted, eager, |item| {
+        if !item.has_constructor {
+            return item.without_constructor();
+        }
+
+        // Safety: Since we check the trait name, and the associated types of traits which have
+        // any, before transmuting back we cannot accidentally construct a trait object pointing
+        // to a different vtable in memory. Auto traits do not change the vtable, and the
+        // registered type is checked to implement those required by the registry
+        let item: RegisteredImplWrapper<Trait> = unsafe { core::mem::transmute(item) };
+        item
+    })
+}
+
+/// Wrapper applied to the instances of a registry declared with `wrap`, implemented by a marker
+/// type generated by [registry](macro@crate::registry)
+pub trait Wrap {
+    type Instance;
+    type Wrapped: 'static;
+
+    fn wrap(instance: Self::Instance) -> Self::Wrapped;
+}
+
+// Constructor of an implementation in a registry declared with `wrap`, taking the constructor of
+// the unwrapped instance
+pub type WrapFn<Trait> = unsafe fn(*const ()) -> Option<Result<Trait, crate::ConstructError>>;
+
+unsafe fn instantiate_wrapped<W: Wrap>(
+    instantiate: *const (),
+) -> Option<Result<W::Wrapped, crate::ConstructError>> {
+    let instantiate: fn() -> Option<Result<W::Instance, crate::ConstructError>> =
+        core::mem::transmute(instantiate);
+    Some(instantiate()?.map(W::wrap))
+}
+
+/// Build the storage of a registry declared with `wrap`, see [registry](macro@crate::registry)
+pub fn build_wrapped_registry<W: Wrap>(
+    trait_: &'static str,
+    namespace: Option<&'static str>,
+    auto_traits: AutoTraits,
+    bindings: Option<core::any::TypeId>,
+    on_failure: Option<crate::FailurePolicy>,
+    sorted: bool,
+    eager: bool,
+) -> TraitRegStorage<W::Wrapped> {
+    let registry = declare(trait_, namespace, auto_traits, bindings);
+    TraitRegStorage::build(registry, on_failure, sorted, eager, |item| {
+        if !item.has_constructor {
+            return item.without_constructor();
+        }
+
+        // Safety: As in `build_registry`, the trait name and auto traits are checked, so the
+        // constructor returns `W::Instance`. It is stored with the type of the wrapped constructor,
+        // but only called through `instantiate_wrapped`, which converts it back. Constructors
+        // registered with `inject` return the unwrapped instance, so are left out.
+        let mut item: RegisteredImplWrapper<W::Wrapped> = unsafe { core::mem::transmute(item) };
+        item.wrap = Some(instantiate_wrapped::<W>);
+        item.init_async = None;
+        #[cfg(feature = "di")]
+        {
+            item.inject = None;
+        }
+        item
+    })
+}
+
+/// Build the storage of a `meta_only` registry, see [registry](macro@crate::registry)
+pub fn build_meta_only_registry(
+    trait_: &'static str,
+    namespace: Option<&'static str>,
+    auto_traits: AutoTraits,
+    bindings: Option<core::any::TypeId>,
+    on_failure: Option<crate::FailurePolicy>,
+    sorted: bool,
+    eager: bool,
+) -> TraitRegStorage<crate::MetaOnly> {
+    // Constructors are never called through a metadata only registry, so registered
+    // constructors returning a trait object are discarded rather than transmuted
+    let registry = declare(trait_, namespace, auto_traits, bindings);
+    TraitRegStorage::build(registry, on_failure, sorted, eager, |item| {
+        item.without_constructor()
+    })
+}
+
+/// Print a warning for every registration which is not in any registry, and every registry with no
+/// implementations, see the `warn_orphans` option on [registry](macro@crate::registry). Only warns
+/// the first time it is called.
+pub fn warn_orphans() {
+    static WARNED: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+    if WARNED.swap(true, core::sync::atomic::Ordering::Relaxed) {
+        return;
+    }
+
+    for item in crate::unclaimed_registrations() {
+        eprintln!(
+            "traitreg: warning: `{}` implements `{}` but is not in any registry ({}, {})",
+            item.path, item.trait_name, item.module_path, item.file
+        );
+    }
+    for trait_ in crate::empty_registries() {
+        eprintln!("traitreg: warning: registry of `{trait_}` has no implementations");
+    }
+}
+
+/// Build the storage of a collection, see [collection](macro@crate::collection)
+pub fn build_collection<T: Sync + 'static>() -> crate::collection::Collection<T> {
+    crate::collection::Collection::build()
+}
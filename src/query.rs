@@ -0,0 +1,264 @@
+//! Queries over registered implementations.
+//!
+//! ```rust
+//! trait Codec {}
+//!
+//! #[derive(Default)]
+//! struct Gzip;
+//!
+//! #[traitreg::register(default, tags = ["stable"])]
+//! impl Codec for Gzip {}
+//!
+//! struct Zstd;
+//!
+//! #[traitreg::register(tags = ["experimental"])]
+//! impl Codec for Zstd {}
+//!
+//! #[traitreg::registry(Codec)]
+//! static CODEC_REGISTRY: () = ();
+//!
+//! let codecs = CODEC_REGISTRY
+//!     .query()
+//!     .crate_(env!("CARGO_PKG_NAME"))
+//!     .tag("stable")
+//!     .with_constructor()
+//!     .collect();
+//! assert_eq!(1, codecs.len());
+//! assert_eq!("Gzip", codecs[0].name());
+//! ```
+
+use crate::{RegisteredImplWrapper, TraitRegStorage};
+
+type Filter<'a, Trait> = Box<dyn Fn(&RegisteredImplWrapper<Trait>) -> bool + 'a>;
+
+/// Query over the implementations in a registry, created with [TraitRegStorage::query]. Every
+/// condition must hold for an implementation to be selected.
+pub struct Query<'a, Trait> {
+    registry: &'a TraitRegStorage<Trait>,
+    filters: Vec<Filter<'a, Trait>>,
+}
+
+impl<'a, Trait> Query<'a, Trait> {
+    pub(crate) fn new(registry: &'a TraitRegStorage<Trait>) -> Self {
+        Self {
+            registry,
+            filters: vec![],
+        }
+    }
+
+    /// Select implementations matching a predicate
+    pub fn filter(mut self, filter: impl Fn(&RegisteredImplWrapper<Trait>) -> bool + 'a) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Select implementations with a type name or path
+    pub fn name(self, name: &'a str) -> Self {
+        self.filter(move |item| item.name() == name || item.path() == name)
+    }
+
+    /// Select implementations from a crate, by package name
+    pub fn crate_(self, crate_name: &'a str) -> Self {
+        self.filter(move |item| item.crate_name() == crate_name)
+    }
+
+    /// Select implementations from a version of a crate, by package version
+    pub fn crate_version(self, crate_version: &'a str) -> Self {
+        self.filter(move |item| item.crate_version() == crate_version)
+    }
+
+    /// Select implementations in a module, or its submodules
+    pub fn module(self, module_path: &'a str) -> Self {
+        self.filter(move |item| {
+            item.module_path()
+                .strip_prefix(module_path)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+    }
+
+    /// Select implementations in a source file, see [RegisteredImplWrapper::is_from_file]
+    pub fn file(self, file: &'a str) -> Self {
+        self.filter(move |item| item.is_from_file(file))
+    }
+
+    /// Select implementations with a tag
+    pub fn tag(self, tag: &'a str) -> Self {
+        self.filter(move |item| item.tags().contains(&tag))
+    }
+
+    /// Select implementations in an initialization phase, see [RegisteredImplWrapper::phase]
+    pub fn phase(self, phase: &'a str) -> Self {
+        self.filter(move |item| item.phase() == Some(phase))
+    }
+
+    /// Select implementations which provide a capability
+    pub fn capability(self, capability: &'a str) -> Self {
+        self.filter(move |item| item.capabilities().contains(&capability))
+    }
+
+    /// Select implementations with a flag declared with `flags` which is `true`
+    pub fn flag(self, flag: &'a str) -> Self {
+        self.filter(move |item| item.flag(flag) == Some(true))
+    }
+
+    /// Select implementations registered with a constructor
+    pub fn with_constructor(self) -> Self {
+        self.filter(|item| item.has_constructor())
+    }
+
+    /// Select implementations with at least a priority
+    pub fn min_priority(self, priority: i32) -> Self {
+        self.filter(move |item| item.priority() >= priority)
+    }
+
+    /// Iterate over the selected implementations, in registry order
+    pub fn iter(&self) -> impl Iterator<Item = &'a RegisteredImplWrapper<Trait>> + '_ {
+        self.registry
+            .iter()
+            .filter(|item| self.filters.iter().all(|filter| filter(item)))
+    }
+
+    /// The selected implementations, in registry order
+    pub fn collect(&self) -> Vec<&'a RegisteredImplWrapper<Trait>> {
+        self.iter().collect()
+    }
+
+    /// The first selected implementation
+    pub fn first(&self) -> Option<&'a RegisteredImplWrapper<Trait>> {
+        self.iter().next()
+    }
+
+    /// Number of selected implementations
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Instantiate the selected implementations which have a constructor
+    pub fn instantiate_all(&self) -> impl Iterator<Item = Trait> + '_ {
+        self.iter().filter_map(|item| item.instantiate())
+    }
+
+    /// View of the selected implementations, for set operations with other views
+    pub fn view(&self) -> View<'a, Trait> {
+        View::new(self.collect())
+    }
+}
+
+/// Set of implementations from registries, created with [TraitRegStorage::view] or
+/// [Query::view]. Implementations are the same when they are implementations of the same trait on
+/// the same type, see [RegisteredImplWrapper]'s `PartialEq` implementation, so views of registries
+/// of the same trait with different auto traits can be combined.
+///
+/// ```rust
+/// trait Codec {}
+///
+/// struct Gzip;
+///
+/// #[traitreg::register(tags = ["builtin"])]
+/// impl Codec for Gzip {}
+///
+/// struct Zstd;
+///
+/// #[traitreg::register]
+/// impl Codec for Zstd {}
+///
+/// #[traitreg::registry(Codec)]
+/// static CODEC_REGISTRY: () = ();
+///
+/// let builtin = CODEC_REGISTRY.query().tag("builtin").view();
+/// let added = CODEC_REGISTRY.view().difference(&builtin);
+/// assert_eq!(vec!["Zstd"], added.iter().map(|item| item.name()).collect::<Vec<_>>());
+/// ```
+pub struct View<'a, Trait> {
+    impls: Vec<&'a RegisteredImplWrapper<Trait>>,
+}
+
+impl<Trait> Clone for View<'_, Trait> {
+    fn clone(&self) -> Self {
+        Self {
+            impls: self.impls.clone(),
+        }
+    }
+}
+
+impl<'a, Trait> View<'a, Trait> {
+    pub(crate) fn new(impls: Vec<&'a RegisteredImplWrapper<Trait>>) -> Self {
+        Self { impls }
+    }
+
+    /// Implementations in either view. Implementations only in `other` come after those in this
+    /// view.
+    pub fn union(mut self, other: &View<'a, Trait>) -> Self {
+        for item in other.iter() {
+            if !self.contains(item) {
+                self.impls.push(item);
+            }
+        }
+        self
+    }
+
+    /// Implementations in both views
+    pub fn intersection<Other>(mut self, other: &View<'_, Other>) -> Self {
+        self.impls.retain(|item| other.contains(item));
+        self
+    }
+
+    /// Implementations in this view which are not in `other`
+    pub fn difference<Other>(mut self, other: &View<'_, Other>) -> Self {
+        self.impls.retain(|item| !other.contains(item));
+        self
+    }
+
+    /// Is the implementation in this view
+    pub fn contains<Other>(&self, item: &RegisteredImplWrapper<Other>) -> bool {
+        self.impls.iter().any(|own| *own == item)
+    }
+
+    /// Iterate over the implementations in the view
+    pub fn iter(&self) -> impl Iterator<Item = &'a RegisteredImplWrapper<Trait>> + '_ {
+        self.impls.iter().copied()
+    }
+
+    /// Number of implementations in the view
+    pub fn len(&self) -> usize {
+        self.impls.len()
+    }
+
+    /// Is the view empty
+    pub fn is_empty(&self) -> bool {
+        self.impls.is_empty()
+    }
+}
+
+impl<'a, Trait> IntoIterator for View<'a, Trait> {
+    type Item = &'a RegisteredImplWrapper<Trait>;
+    type IntoIter = std::vec::IntoIter<&'a RegisteredImplWrapper<Trait>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.impls.into_iter()
+    }
+}
+
+// How well a pattern matches the name or path of an implementation, lower is better
+pub(crate) fn match_rank<Trait>(pattern: &str, item: &RegisteredImplWrapper<Trait>) -> Option<u8> {
+    let pattern = pattern.to_lowercase();
+    [item.name(), item.path()]
+        .into_iter()
+        .filter_map(|candidate| {
+            let candidate = candidate.to_lowercase();
+            if candidate == pattern {
+                Some(0)
+            } else if candidate.starts_with(&pattern) {
+                Some(1)
+            } else if candidate.contains(&pattern) {
+                Some(2)
+            } else {
+                let mut chars = candidate.chars();
+                pattern
+                    .chars()
+                    .all(|c| chars.any(|other| other == c))
+                    .then_some(3)
+            }
+        })
+        .min()
+}
@@ -0,0 +1,444 @@
+//! Hot reloading of registrations from plugin libraries. Requires the `hot-reload` feature.
+//!
+//! Plugins are `cdylib` crates which register implementations as usual, and export their
+//! registrations with [export_plugin](crate::export_plugin).
+//!
+//! ```rust,ignore
+//! // In the plugin crate
+//! #[derive(Default)]
+//! struct English;
+//!
+//! #[traitreg::register(default)]
+//! impl Greeter for English {
+//!     fn greet(&self) -> String {
+//!         "hello".to_string()
+//!     }
+//! }
+//!
+//! traitreg::export_plugin!();
+//! ```
+//!
+//! The host loads plugins into a [ReloadableRegistry], and polls for changes with
+//! [ReloadableRegistry::reload_changed], e.g. once per frame or from a file watcher.
+//!
+//! ```rust,no_run
+//! # trait Greeter { fn greet(&self) -> String; }
+//! use traitreg::reload::ReloadableRegistry;
+//!
+//! static GREETERS: ReloadableRegistry<dyn Greeter> = ReloadableRegistry::new("Greeter");
+//!
+//! // Safety: The plugin is built with the same compiler and `Greeter` trait as the host
+//! unsafe { GREETERS.load("target/debug/libgreeter_plugin.so") }.unwrap();
+//!
+//! loop {
+//!     for greeter in GREETERS.instantiate_all() {
+//!         println!("{}", greeter.greet());
+//!     }
+//!     unsafe { GREETERS.reload_changed() }.unwrap();
+//! #   break;
+//! }
+//! ```
+//!
+//! A library is copied before it is loaded, so a rebuilt plugin is loaded as a new library rather
+//! than reusing the old one. Reloading replaces every registration from a plugin at once, and the
+//! old library stays loaded until every [PluginInstance] created from it has been dropped.
+//!
+//! Registrations from a plugin are passed to the host through a C ABI, but registered
+//! implementations are still Rust trait objects, so plugins must be built with the same compiler
+//! as the host and against the same definition of the trait.
+
+use crate::ConstructError;
+use std::ffi::c_void;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+const ENTRY_POINT: &[u8] = b"__traitreg_plugin_entries";
+
+/// Export the registrations of a plugin library, for loading into a [ReloadableRegistry]. Use once
+/// in the root of a `cdylib` crate.
+#[macro_export]
+macro_rules! export_plugin {
+    () => {
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn __traitreg_plugin_entries(
+            visit: $crate::reload::__PluginVisitor,
+            context: *mut ::core::ffi::c_void,
+        ) {
+            $crate::reload::__visit_plugin_entries(visit, context)
+        }
+    };
+}
+
+#[doc(hidden)]
+pub type __PluginVisitor = extern "C" fn(context: *mut c_void, entry: &__PluginEntry);
+
+// Strings are passed as pointers and lengths since plugins and the host may link different copies
+// of traitreg, and only types with a C layout can be relied on to match between them
+#[doc(hidden)]
+#[repr(C)]
+pub struct __PluginStr {
+    ptr: *const u8,
+    len: usize,
+}
+
+impl __PluginStr {
+    fn new(value: &'static str) -> Self {
+        Self {
+            ptr: value.as_ptr(),
+            len: value.len(),
+        }
+    }
+
+    // Safety: The string must have been created in a library which is still loaded
+    unsafe fn to_owned(&self) -> String {
+        let bytes = core::slice::from_raw_parts(self.ptr, self.len);
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
+// Type erased constructor of a registration, as stored in the global registry
+type ErasedConstructor = fn() -> Option<Result<Box<u32>, ConstructError>>;
+
+/// Registration exported by a plugin
+#[doc(hidden)]
+#[repr(C)]
+pub struct __PluginEntry {
+    name: __PluginStr,
+    path: __PluginStr,
+    trait_name: __PluginStr,
+    instantiate: Option<ErasedConstructor>,
+}
+
+#[doc(hidden)]
+pub fn __visit_plugin_entries(visit: __PluginVisitor, context: *mut c_void) {
+    for item in crate::registrations().iter() {
+        let entry = __PluginEntry {
+            name: __PluginStr::new(item.name),
+            path: __PluginStr::new(item.path),
+            trait_name: __PluginStr::new(item.trait_name),
+            instantiate: item.has_constructor.then_some(item.instantiate),
+        };
+        visit(context, &entry);
+    }
+}
+
+extern "C" fn collect_entry(context: *mut c_void, entry: &__PluginEntry) {
+    // Safety: The context is always the `Vec` passed by `LoadedPlugin::load`, and the entry is only
+    // used while the library which created it is loaded
+    let entries = unsafe { &mut *(context as *mut Vec<RawEntry>) };
+    entries.push(unsafe {
+        RawEntry {
+            name: entry.name.to_owned(),
+            path: entry.path.to_owned(),
+            trait_name: entry.trait_name.to_owned(),
+            instantiate: entry.instantiate,
+        }
+    });
+}
+
+struct RawEntry {
+    name: String,
+    path: String,
+    trait_name: String,
+    instantiate: Option<ErasedConstructor>,
+}
+
+/// Error loading a plugin library
+#[derive(Debug)]
+pub enum ReloadError {
+    /// The plugin library could not be read or copied
+    Io(std::io::Error),
+    /// The plugin library could not be loaded
+    Load(libloading::Error),
+    /// The plugin library does not export its registrations with
+    /// [export_plugin](crate::export_plugin)
+    MissingEntryPoint(PathBuf),
+}
+
+impl core::fmt::Display for ReloadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read plugin library: {error}"),
+            Self::Load(error) => write!(f, "Failed to load plugin library: {error}"),
+            Self::MissingEntryPoint(path) => write!(
+                f,
+                "Plugin library '{}' does not use traitreg::export_plugin!()",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReloadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Load(error) => Some(error),
+            Self::MissingEntryPoint(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ReloadError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<libloading::Error> for ReloadError {
+    fn from(error: libloading::Error) -> Self {
+        Self::Load(error)
+    }
+}
+
+struct PluginImpl<T: ?Sized> {
+    name: String,
+    path: String,
+    instantiate: fn() -> Option<Result<Box<T>, ConstructError>>,
+}
+
+/// Loaded version of a plugin library
+struct LoadedPlugin<T: ?Sized> {
+    source: PathBuf,
+    modified: SystemTime,
+    copy: PathBuf,
+    impls: Vec<PluginImpl<T>>,
+    library: Option<libloading::Library>,
+}
+
+impl<T: ?Sized> LoadedPlugin<T> {
+    // Safety: See `ReloadableRegistry::load`
+    unsafe fn load(source: &Path, trait_name: &str) -> Result<Self, ReloadError> {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        static LOADED: AtomicUsize = AtomicUsize::new(0);
+
+        let modified = std::fs::metadata(source)?.modified()?;
+        let file_name = source.file_name().unwrap_or(source.as_os_str());
+        let copy = std::env::temp_dir().join(format!(
+            "traitreg-{}-{}-{}",
+            std::process::id(),
+            LOADED.fetch_add(1, Ordering::Relaxed),
+            file_name.to_string_lossy()
+        ));
+        std::fs::copy(source, &copy)?;
+
+        let mut plugin = Self {
+            source: source.to_path_buf(),
+            modified,
+            copy,
+            impls: vec![],
+            library: None,
+        };
+        let library = plugin
+            .library
+            .insert(libloading::Library::new(&plugin.copy)?);
+
+        let visit_entries = library
+            .get::<extern "C" fn(__PluginVisitor, *mut c_void)>(ENTRY_POINT)
+            .map_err(|_| ReloadError::MissingEntryPoint(plugin.source.clone()))?;
+        let mut entries: Vec<RawEntry> = vec![];
+        visit_entries(
+            collect_entry,
+            &mut entries as *mut Vec<RawEntry> as *mut c_void,
+        );
+
+        plugin.impls = entries
+            .into_iter()
+            .filter(|entry| entry.trait_name == trait_name)
+            .map(|entry| PluginImpl {
+                name: entry.name,
+                path: entry.path,
+                instantiate: match entry.instantiate {
+                    // Safety: The trait name was checked, and the caller guarantees the plugin
+                    // and host agree on the layout of the trait object
+                    Some(instantiate) => core::mem::transmute::<
+                        ErasedConstructor,
+                        fn() -> Option<Result<Box<T>, ConstructError>>,
+                    >(instantiate),
+                    None => crate::no_constructor,
+                },
+            })
+            .collect();
+
+        Ok(plugin)
+    }
+}
+
+impl<T: ?Sized> Drop for LoadedPlugin<T> {
+    fn drop(&mut self) {
+        // The copy can only be removed once the library is closed on some platforms
+        drop(self.library.take());
+        let _ = std::fs::remove_file(&self.copy);
+    }
+}
+
+/// Instance of an implementation from a plugin library. Keeps the version of the library it was
+/// created from loaded until it is dropped, even if the plugin has been reloaded since.
+pub struct PluginInstance<T: ?Sized> {
+    // Dropped before the plugin, since the instance's vtable and drop glue are in the library
+    instance: Box<T>,
+    plugin: Arc<LoadedPlugin<T>>,
+}
+
+impl<T: ?Sized> PluginInstance<T> {
+    /// The plugin library the instance was created from
+    pub fn source(&self) -> &Path {
+        &self.plugin.source
+    }
+}
+
+impl<T: ?Sized> core::ops::Deref for PluginInstance<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.instance
+    }
+}
+
+impl<T: ?Sized> core::ops::DerefMut for PluginInstance<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.instance
+    }
+}
+
+/// Registry of implementations of a trait in plugin libraries which can be reloaded while the
+/// program is running
+pub struct ReloadableRegistry<T: ?Sized> {
+    trait_name: &'static str,
+    plugins: RwLock<Vec<Arc<LoadedPlugin<T>>>>,
+}
+
+impl<T: ?Sized> ReloadableRegistry<T> {
+    /// Create an empty registry for implementations of the trait named `trait_name`, where `T` is
+    /// the trait object type, e.g. `ReloadableRegistry::<dyn MyTrait>::new("MyTrait")`
+    pub const fn new(trait_name: &'static str) -> Self {
+        Self {
+            trait_name,
+            plugins: RwLock::new(vec![]),
+        }
+    }
+
+    /// Load a plugin library and add its registrations, replacing those of an earlier version of
+    /// the same library
+    ///
+    /// # Safety
+    ///
+    /// Loading a library runs its initialization code. The library must export its registrations
+    /// with [export_plugin](crate::export_plugin), be built with the same compiler as this program,
+    /// and define the trait with the same name as `T` identically. `T` must be the trait object
+    /// type without auto traits.
+    pub unsafe fn load(&self, path: impl AsRef<Path>) -> Result<(), ReloadError> {
+        let plugin = Arc::new(LoadedPlugin::load(path.as_ref(), self.trait_name)?);
+        self.replace(plugin);
+        Ok(())
+    }
+
+    /// Reload every plugin library which has been modified since it was loaded. Returns the number
+    /// of libraries reloaded. Stops at the first library which fails to reload, which keeps its
+    /// current version.
+    ///
+    /// # Safety
+    ///
+    /// See [ReloadableRegistry::load]
+    pub unsafe fn reload_changed(&self) -> Result<usize, ReloadError> {
+        let changed: Vec<PathBuf> = self
+            .read()
+            .iter()
+            .filter(|plugin| {
+                std::fs::metadata(&plugin.source)
+                    .and_then(|metadata| metadata.modified())
+                    .is_ok_and(|modified| modified != plugin.modified)
+            })
+            .map(|plugin| plugin.source.clone())
+            .collect();
+
+        for source in &changed {
+            self.load(source)?;
+        }
+        Ok(changed.len())
+    }
+
+    /// Remove the registrations of a plugin library. The library is unloaded once every instance
+    /// created from it has been dropped. Returns whether the library was loaded.
+    pub fn unload(&self, path: impl AsRef<Path>) -> bool {
+        let mut plugins = self.write();
+        let len = plugins.len();
+        plugins.retain(|plugin| plugin.source != path.as_ref());
+        plugins.len() != len
+    }
+
+    /// Names of the registered implementations in every loaded plugin library
+    pub fn names(&self) -> Vec<String> {
+        self.read()
+            .iter()
+            .flat_map(|plugin| plugin.impls.iter().map(|item| item.name.clone()))
+            .collect()
+    }
+
+    /// Paths of the loaded plugin libraries
+    pub fn sources(&self) -> Vec<PathBuf> {
+        self.read()
+            .iter()
+            .map(|plugin| plugin.source.clone())
+            .collect()
+    }
+
+    /// Instantiate the implementation with a name or path, if it was registered with a constructor
+    pub fn instantiate(&self, name: &str) -> Option<PluginInstance<T>> {
+        self.read().iter().find_map(|plugin| {
+            plugin
+                .impls
+                .iter()
+                .find(|item| item.name == name || item.path == name)
+                .and_then(|item| Self::instantiate_impl(plugin, item))
+        })
+    }
+
+    /// Instantiate all registered implementations which have a constructor
+    pub fn instantiate_all(&self) -> Vec<PluginInstance<T>> {
+        self.read()
+            .iter()
+            .flat_map(|plugin| {
+                plugin
+                    .impls
+                    .iter()
+                    .filter_map(|item| Self::instantiate_impl(plugin, item))
+            })
+            .collect()
+    }
+
+    fn instantiate_impl(
+        plugin: &Arc<LoadedPlugin<T>>,
+        item: &PluginImpl<T>,
+    ) -> Option<PluginInstance<T>> {
+        Some(PluginInstance {
+            instance: (item.instantiate)()?.ok()?,
+            plugin: plugin.clone(),
+        })
+    }
+
+    fn replace(&self, plugin: Arc<LoadedPlugin<T>>) {
+        let mut plugins = self.write();
+        match plugins
+            .iter_mut()
+            .find(|loaded| loaded.source == plugin.source)
+        {
+            Some(loaded) => *loaded = plugin,
+            None => plugins.push(plugin),
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<Arc<LoadedPlugin<T>>>> {
+        self.plugins
+            .read()
+            .expect("Traitreg reloadable registry lock poisoned")
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<Arc<LoadedPlugin<T>>>> {
+        self.plugins
+            .write()
+            .expect("Traitreg reloadable registry lock poisoned")
+    }
+}
@@ -0,0 +1,85 @@
+//! Strategies for selecting one implementation out of a registry.
+//!
+//! ```rust
+//! use traitreg::select::Strategy;
+//!
+//! trait Renderer {}
+//!
+//! #[derive(Default)]
+//! struct Software;
+//!
+//! #[traitreg::register(default)]
+//! impl Renderer for Software {}
+//!
+//! #[derive(Default)]
+//! struct Vulkan;
+//!
+//! #[traitreg::register(default, priority = 10)]
+//! impl Renderer for Vulkan {}
+//!
+//! #[traitreg::registry(Renderer)]
+//! static RENDERER_REGISTRY: () = ();
+//!
+//! let renderer = RENDERER_REGISTRY.select(Strategy::HighestPriority).unwrap();
+//! assert_eq!("Vulkan", renderer.name());
+//!
+//! let renderer = RENDERER_REGISTRY.select(Strategy::ByName("Software")).unwrap();
+//! assert_eq!("Software", renderer.name());
+//! ```
+//!
+//! Other strategies can be written by implementing [SelectionStrategy].
+//!
+//! ```rust
+//! use traitreg::select::SelectionStrategy;
+//! use traitreg::RegisteredImplWrapper;
+//!
+//! // Select the implementation from a crate, if there is one
+//! struct FromCrate(&'static str);
+//!
+//! impl SelectionStrategy for FromCrate {
+//!     fn select<'a, Trait>(
+//!         &self,
+//!         impls: &'a [RegisteredImplWrapper<Trait>],
+//!     ) -> Option<&'a RegisteredImplWrapper<Trait>> {
+//!         impls.iter().find(|item| item.crate_name() == self.0)
+//!     }
+//! }
+//! ```
+
+use crate::RegisteredImplWrapper;
+
+/// Strategy for selecting one implementation out of a registry, see
+/// [TraitRegStorage::select](crate::TraitRegStorage::select)
+pub trait SelectionStrategy {
+    /// Select an implementation, given the implementations in registry order
+    fn select<'a, Trait>(
+        &self,
+        impls: &'a [RegisteredImplWrapper<Trait>],
+    ) -> Option<&'a RegisteredImplWrapper<Trait>>;
+}
+
+/// Common selection strategies
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Strategy<'a> {
+    /// The first implementation in the registry
+    First,
+    /// The implementation with the highest [priority](RegisteredImplWrapper::priority), or the
+    /// first of those with the highest priority
+    HighestPriority,
+    /// The implementation on a type with this [name](RegisteredImplWrapper::name)
+    ByName(&'a str),
+}
+
+impl SelectionStrategy for Strategy<'_> {
+    fn select<'a, Trait>(
+        &self,
+        impls: &'a [RegisteredImplWrapper<Trait>],
+    ) -> Option<&'a RegisteredImplWrapper<Trait>> {
+        match self {
+            Self::First => impls.first(),
+            // `max_by_key` returns the last of equal elements, so compare in reverse
+            Self::HighestPriority => impls.iter().rev().max_by_key(|item| item.priority()),
+            Self::ByName(name) => impls.iter().find(|item| item.name() == *name),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Immutable snapshots of registries.
+//!
+//! A [Snapshot] owns a copy of the implementations in a registry when it was taken, so it can be
+//! held for a long time, or across await points, and iterated over without seeing later changes to
+//! the registry. Snapshots are cheap to clone, clones share the same copy.
+//!
+//! ```rust
+//! trait MyTrait {}
+//!
+//! #[derive(Default)]
+//! struct MyStruct;
+//!
+//! #[traitreg::register(default)]
+//! impl MyTrait for MyStruct {}
+//!
+//! #[traitreg::registry(MyTrait)]
+//! static MYTRAIT_REGISTRY: () = ();
+//!
+//! let snapshot = MYTRAIT_REGISTRY.snapshot();
+//! let handle = std::thread::spawn(move || snapshot.iter().map(|item| item.name()).collect());
+//! let names: Vec<&str> = handle.join().unwrap();
+//! assert_eq!(vec!["MyStruct"], names);
+//! ```
+
+use crate::{ImplId, RegisteredImplWrapper};
+use std::sync::Arc;
+
+/// Owned, immutable copy of the implementations in a registry, see
+/// [TraitRegStorage::snapshot](crate::TraitRegStorage::snapshot)
+pub struct Snapshot<Trait> {
+    impls: Arc<[RegisteredImplWrapper<Trait>]>,
+}
+
+impl<Trait> Clone for Snapshot<Trait> {
+    fn clone(&self) -> Self {
+        Self {
+            impls: self.impls.clone(),
+        }
+    }
+}
+
+impl<Trait> Snapshot<Trait> {
+    pub(crate) fn new(impls: Vec<RegisteredImplWrapper<Trait>>) -> Self {
+        Self {
+            impls: impls.into(),
+        }
+    }
+
+    /// Iterate over the implementations in the snapshot, in registry order
+    pub fn iter(&self) -> core::slice::Iter<'_, RegisteredImplWrapper<Trait>> {
+        self.impls.iter()
+    }
+
+    /// Implementations in the snapshot, in registry order
+    pub fn as_slice(&self) -> &[RegisteredImplWrapper<Trait>] {
+        &self.impls
+    }
+
+    /// Find the implementation with an id in the registry the snapshot was taken from
+    pub fn get_by_id(&self, id: ImplId) -> Option<&RegisteredImplWrapper<Trait>> {
+        self.impls.get(id.to_raw() as usize)
+    }
+
+    /// Number of implementations in the snapshot
+    pub fn len(&self) -> usize {
+        self.impls.len()
+    }
+
+    /// Is the snapshot empty
+    pub fn is_empty(&self) -> bool {
+        self.impls.is_empty()
+    }
+}
+
+impl<'a, Trait> IntoIterator for &'a Snapshot<Trait> {
+    type Item = &'a RegisteredImplWrapper<Trait>;
+    type IntoIter = core::slice::Iter<'a, RegisteredImplWrapper<Trait>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.impls.iter()
+    }
+}
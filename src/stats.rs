@@ -0,0 +1,91 @@
+//! Statistics of the registrations in the process.
+//!
+//! [stats](crate::stats()) counts every registration made so far by crate and by trait, so large
+//! workspaces can track how many implementations each crate contributes, or check at runtime that
+//! a feature crate registered the implementations it is expected to.
+//!
+//! ```rust
+//! trait Codec {}
+//!
+//! struct Gzip;
+//!
+//! #[traitreg::register]
+//! impl Codec for Gzip {}
+//!
+//! struct Zstd;
+//!
+//! #[traitreg::register]
+//! impl Codec for Zstd {}
+//!
+//! let stats = traitreg::stats();
+//! assert_eq!(2, stats.registrations_of_trait("Codec"));
+//! assert_eq!(2, stats.registrations_of_crate(env!("CARGO_PKG_NAME")));
+//! ```
+
+use std::collections::BTreeMap;
+
+/// Counts of registrations, see [stats](crate::stats())
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Stats {
+    total: usize,
+    with_constructor: usize,
+    failed: usize,
+    by_crate: BTreeMap<&'static str, usize>,
+    by_trait: BTreeMap<&'static str, usize>,
+}
+
+impl Stats {
+    pub(crate) fn new<'a, Trait: 'a>(
+        registrations: impl Iterator<Item = &'a crate::RegisteredImplWrapper<Trait>>,
+        failed: usize,
+    ) -> Self {
+        let mut stats = Self {
+            failed,
+            ..Self::default()
+        };
+        for item in registrations {
+            stats.total += 1;
+            stats.with_constructor += usize::from(item.has_constructor());
+            *stats.by_crate.entry(item.crate_name()).or_default() += 1;
+            *stats.by_trait.entry(item.trait_name()).or_default() += 1;
+        }
+        stats
+    }
+
+    /// Number of registrations
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Number of registrations with a constructor
+    pub fn with_constructor(&self) -> usize {
+        self.with_constructor
+    }
+
+    /// Number of registrations which panicked, see
+    /// [registration_panics](crate::registration_panics)
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Number of registrations from each crate, by package name
+    pub fn registrations_by_crate(&self) -> &BTreeMap<&'static str, usize> {
+        &self.by_crate
+    }
+
+    /// Number of registrations of each trait, by trait name
+    pub fn registrations_by_trait(&self) -> &BTreeMap<&'static str, usize> {
+        &self.by_trait
+    }
+
+    /// Number of registrations from a crate, by package name
+    pub fn registrations_of_crate(&self, crate_name: &str) -> usize {
+        self.by_crate.get(crate_name).copied().unwrap_or_default()
+    }
+
+    /// Number of registrations of a trait, by trait name
+    pub fn registrations_of_trait(&self, trait_name: &str) -> usize {
+        self.by_trait.get(trait_name).copied().unwrap_or_default()
+    }
+}
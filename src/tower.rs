@@ -0,0 +1,113 @@
+//! Adapter composing registered layers into a `tower::Layer`. Requires the `tower` feature.
+//!
+//! Layers are registered as implementations of a trait with [LayerFactory] as a supertrait, and
+//! applied in order of priority, with the highest priority as the outermost layer.
+//!
+//! ```rust
+//! use std::convert::Infallible;
+//! use tower::util::{BoxCloneSyncServiceLayer, MapResponseLayer};
+//! use tower::Layer;
+//! use traitreg::tower::{BoxedLayer, LayerFactory, RegisteredLayers};
+//!
+//! trait Plugin: LayerFactory<String, String, Infallible> {}
+//!
+//! #[derive(Default)]
+//! struct Greeting;
+//!
+//! impl LayerFactory<String, String, Infallible> for Greeting {
+//!     fn layer(&self) -> BoxedLayer<String, String, Infallible> {
+//!         BoxCloneSyncServiceLayer::new(MapResponseLayer::new(|response| {
+//!             format!("Hello {response}")
+//!         }))
+//!     }
+//! }
+//!
+//! #[traitreg::register(default)]
+//! impl Plugin for Greeting {}
+//!
+//! #[traitreg::registry(Plugin)]
+//! static PLUGIN_REGISTRY: () = ();
+//!
+//! let layers = RegisteredLayers::new(&PLUGIN_REGISTRY);
+//! let service = layers.layer(tower::service_fn(|request: String| async move {
+//!     Ok::<_, Infallible>(request)
+//! }));
+//! ```
+
+use crate::TraitRegStorage;
+use tower::util::{BoxCloneSyncService, BoxCloneSyncServiceLayer};
+use tower::{Layer, Service};
+
+/// Type erased layer created by a [LayerFactory], wrapping a type erased service
+pub type BoxedLayer<Request, Response, Error> = BoxCloneSyncServiceLayer<
+    BoxCloneSyncService<Request, Response, Error>,
+    Request,
+    Response,
+    Error,
+>;
+
+/// Creates a layer to be composed by [RegisteredLayers]
+pub trait LayerFactory<Request, Response, Error> {
+    /// Create the layer
+    fn layer(&self) -> BoxedLayer<Request, Response, Error>;
+}
+
+/// Layers created by every registered [LayerFactory] which has a constructor, composed in order
+/// of priority. Services are type erased to a `BoxCloneSyncService`, so the result can be used as
+/// a layer in frameworks such as axum or tonic.
+pub struct RegisteredLayers<Request, Response, Error> {
+    layers: Vec<BoxedLayer<Request, Response, Error>>,
+}
+
+impl<Request, Response, Error> RegisteredLayers<Request, Response, Error> {
+    /// Create layers from every registered implementation which has a constructor, ordered by
+    /// [priority](crate::RegisteredImplWrapper::priority) with the highest first
+    pub fn new<T: ?Sized + LayerFactory<Request, Response, Error>>(
+        registry: &TraitRegStorage<Box<T>>,
+    ) -> Self {
+        Self {
+            layers: registry
+                .iter_by_priority()
+                .filter_map(|item| item.instantiate())
+                .map(|factory| factory.layer())
+                .collect(),
+        }
+    }
+
+    /// Number of composed layers
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Are there no composed layers
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+impl<Request, Response, Error> Clone for RegisteredLayers<Request, Response, Error> {
+    fn clone(&self) -> Self {
+        Self {
+            layers: self.layers.clone(),
+        }
+    }
+}
+
+impl<S, Request, Response, Error> Layer<S> for RegisteredLayers<Request, Response, Error>
+where
+    S: Service<Request, Response = Response, Error = Error> + Clone + Send + Sync + 'static,
+    S::Future: Send + 'static,
+    Request: 'static,
+{
+    type Service = BoxCloneSyncService<Request, Response, Error>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        // Applied innermost first, so the highest priority layer handles requests first
+        self.layers
+            .iter()
+            .rev()
+            .fold(BoxCloneSyncService::new(inner), |service, layer| {
+                layer.layer(service)
+            })
+    }
+}
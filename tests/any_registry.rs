@@ -0,0 +1,87 @@
+trait Shape {
+    fn sides(&self) -> u32;
+}
+
+#[derive(Default)]
+struct Square;
+
+#[traitreg::register(default, any, tags = ["regular"])]
+impl Shape for Square {
+    fn sides(&self) -> u32 {
+        4
+    }
+}
+
+#[derive(Default)]
+struct Triangle;
+
+#[traitreg::register(default)]
+impl Shape for Triangle {
+    fn sides(&self) -> u32 {
+        3
+    }
+}
+
+#[traitreg::registry(Shape)]
+static SHAPE_REGISTRY: () = ();
+
+trait Codec {}
+
+struct Gzip;
+
+#[traitreg::register(priority = 2)]
+impl Codec for Gzip {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+// Generic tooling which knows nothing about the traits
+fn describe(registries: &[traitreg::AnyRegistry]) -> Vec<String> {
+    registries
+        .iter()
+        .flat_map(|registry| registry.iter())
+        .map(|item| {
+            format!(
+                "{}: {} ({})",
+                item.trait_name(),
+                item.name(),
+                item.priority()
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn main() {
+    let registries = [SHAPE_REGISTRY.to_any(), CODEC_REGISTRY.to_any()];
+    let mut lines = describe(&registries);
+    lines.sort();
+    assert_eq!(
+        vec![
+            "Codec: Gzip (2)",
+            "Shape: Square (0)",
+            "Shape: Triangle (0)"
+        ],
+        lines
+    );
+
+    // Entries are equal to those of the typed registry, and keep their metadata
+    for (item, erased) in SHAPE_REGISTRY.iter().zip(&registries[0]) {
+        assert_eq!(item, erased);
+        assert_eq!(item.tags(), erased.tags());
+        assert_eq!(item.has_constructor(), erased.has_constructor());
+    }
+
+    // Only implementations registered with `any` can be instantiated
+    let square = registries[0]
+        .iter()
+        .find(|item| item.name() == "Square")
+        .unwrap();
+    assert_eq!(4, square.instantiate_downcast::<Square>().unwrap().sides());
+    let triangle = registries[0]
+        .iter()
+        .find(|item| item.name() == "Triangle")
+        .unwrap();
+    assert!(triangle.instantiate_any().is_none());
+    assert!(triangle.instantiate().is_none());
+}
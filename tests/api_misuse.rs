@@ -1,13 +1,29 @@
 #[test]
 fn ui() {
     let t = trybuild::TestCases::new();
+    t.compile_fail("tests/api_misuse/implement_registered_impl.rs");
     t.compile_fail("tests/api_misuse/not_register_impl.rs");
+    t.compile_fail("tests/api_misuse/register_args_wrong_signature.rs");
+    t.compile_fail("tests/api_misuse/register_auto_with_constructor.rs");
+    t.compile_fail("tests/api_misuse/register_factory_wrong_signature.rs");
+    t.compile_fail("tests/api_misuse/register_fn_with_auto.rs");
     t.compile_fail("tests/api_misuse/register_impl_for_array.rs");
     t.compile_fail("tests/api_misuse/register_impl_for_inferred.rs");
     t.compile_fail("tests/api_misuse/register_impl_for_never.rs");
     t.compile_fail("tests/api_misuse/register_impl_for_pointer.rs");
     t.compile_fail("tests/api_misuse/register_impl_for_reference.rs");
     t.compile_fail("tests/api_misuse/register_impl_for_tuple.rs");
+    t.compile_fail("tests/api_misuse/register_not_dyn_compatible.rs");
     t.compile_fail("tests/api_misuse/register_self_impl.rs");
+    // Dependencies enabled by the bevy feature define traits with a `new` function, which rustc
+    // lists as candidates
+    #[cfg(not(feature = "bevy"))]
     t.compile_fail("tests/api_misuse/register_struct_with_missing_constructor.rs");
+    t.compile_fail("tests/api_misuse/register_struct_with_missing_named_constructor.rs");
+    t.compile_fail("tests/api_misuse/register_twice.rs");
+    t.compile_fail("tests/api_misuse/register_unknown_lifetime.rs");
+    t.compile_fail("tests/api_misuse/register_unknown_option.rs");
+    t.compile_fail("tests/api_misuse/registry_alias_mismatch.rs");
+    t.compile_fail("tests/api_misuse/registry_meta_only_eager.rs");
+    t.compile_fail("tests/api_misuse/static_dispatch_without_receiver.rs");
 }
@@ -0,0 +1,21 @@
+fn main() {}
+
+trait MyTrait {}
+
+struct MyStruct;
+
+impl MyTrait for MyStruct {}
+
+impl traitreg::__private::RegisteredImpl<Box<dyn MyTrait>> for MyStruct {
+    const INSTANTIATE: fn() -> Option<Box<dyn MyTrait>> = || Some(Box::new(MyStruct));
+    const HAS_CONSTRUCTOR: bool = true;
+    const NAME: &'static str = "MyStruct";
+    const PATH: &'static str = "MyStruct";
+    const FILE: &'static str = file!();
+    const MODULE_PATH: &'static str = module_path!();
+    const CRATE_NAME: &'static str = env!("CARGO_PKG_NAME");
+    const CRATE_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+    const TRAIT_NAME: &'static str = "Other";
+    const AUTO_TRAITS: fn() -> traitreg::__private::AutoTraits = Default::default;
+    const TYPE_ID: fn() -> core::any::TypeId = core::any::TypeId::of::<MyStruct>;
+}
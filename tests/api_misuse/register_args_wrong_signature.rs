@@ -0,0 +1,17 @@
+fn main() {}
+
+trait MyTrait {}
+
+struct MyStruct;
+
+impl MyStruct {
+    fn new(_name: &str) -> Self {
+        Self
+    }
+}
+
+#[traitreg::register(new, args)]
+impl MyTrait for MyStruct {}
+
+#[traitreg::registry(MyTrait, args = (&str, u32))]
+static MYTRAIT_REGISTRY: () = ();
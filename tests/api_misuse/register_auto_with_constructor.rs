@@ -0,0 +1,11 @@
+fn main() {}
+
+
+
+trait MyTrait {}
+
+#[derive(Default)]
+struct MyStruct;
+
+#[traitreg::register(default, auto)]
+impl MyTrait for MyStruct {}
@@ -0,0 +1,15 @@
+fn main() {}
+
+trait MyTrait {}
+
+struct MyStruct;
+
+fn create() -> Box<dyn MyTrait> {
+    Box::new(MyStruct)
+}
+
+#[traitreg::register(factory = create)]
+impl MyTrait for MyStruct {}
+
+#[traitreg::registry(MyTrait, factory = fn(u32) -> Box<dyn MyTrait>)]
+static MYTRAIT_REGISTRY: () = ();
@@ -0,0 +1,10 @@
+fn main() {}
+
+
+
+trait Handler {}
+
+impl<F: Fn()> Handler for F {}
+
+#[traitreg::register(Handler, auto)]
+fn ping() {}
@@ -0,0 +1,14 @@
+fn main() {}
+
+
+
+trait MyTrait {
+    fn generic<T>(&self, value: T);
+}
+
+struct MyStruct;
+
+#[traitreg::register]
+impl MyTrait for MyStruct {
+    fn generic<T>(&self, _value: T) {}
+}
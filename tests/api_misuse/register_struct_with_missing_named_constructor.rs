@@ -0,0 +1,11 @@
+fn main() {}
+
+// No trait of any dependency defines `connect`, so the error lists no candidate traits whatever
+// features are enabled
+
+trait MyTrait {}
+
+struct MyStruct;
+
+#[traitreg::register(connect)]
+impl MyTrait for MyStruct {}
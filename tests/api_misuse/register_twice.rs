@@ -0,0 +1,10 @@
+trait MyTrait {}
+
+#[derive(Default)]
+struct MyType;
+
+#[traitreg::register(default)]
+#[traitreg::register]
+impl MyTrait for MyType {}
+
+fn main() {}
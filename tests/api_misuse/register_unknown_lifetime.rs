@@ -0,0 +1,9 @@
+trait MyTrait {}
+
+#[derive(Default)]
+struct MyType;
+
+#[traitreg::register(default, lifetime = forever)]
+impl MyTrait for MyType {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+fn main() {}
+
+
+
+trait MyTrait {}
+
+struct MyStruct;
+
+#[traitreg::register(foo = bar)]
+impl MyTrait for MyStruct {}
@@ -0,0 +1,10 @@
+trait MyTrait {}
+
+trait MyOtherTrait {}
+
+type MyObject = dyn MyOtherTrait;
+
+#[traitreg::registry(MyObject = dyn MyTrait)]
+static MYOBJECT_REGISTRY: () = ();
+
+fn main() {}
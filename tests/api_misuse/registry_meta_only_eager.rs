@@ -0,0 +1,8 @@
+fn main() {}
+
+
+
+trait MyTrait {}
+
+#[traitreg::registry(MyTrait, meta_only, eager)]
+static MYTRAIT_REGISTRY: () = ();
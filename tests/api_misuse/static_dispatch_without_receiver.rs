@@ -0,0 +1,14 @@
+#[traitreg::static_dispatch(MyTraitEnum, MyType)]
+trait MyTrait {
+    fn new() -> Self;
+}
+
+struct MyType;
+
+impl MyTrait for MyType {
+    fn new() -> Self {
+        Self
+    }
+}
+
+fn main() {}
@@ -2,9 +2,14 @@
 fn ui() {
     let t = trybuild::TestCases::new();
     t.pass("tests/api_usage/empty_registry.rs");
+    t.pass("tests/api_usage/public_registry.rs");
+    t.pass("tests/api_usage/register_impl_for_const_generic.rs");
     t.pass("tests/api_usage/register_impl_for_enum.rs");
     t.pass("tests/api_usage/register_impl_for_struct.rs");
     t.pass("tests/api_usage/register_impl_for_type.rs");
     t.pass("tests/api_usage/register_impl_for_union.rs");
+    t.pass("tests/api_usage/register_impl_with_where_clause.rs");
+    t.pass("tests/api_usage/register_unsafe_impl.rs");
+    t.pass("tests/api_usage/register_with_feature.rs");
     t.pass("tests/api_usage/registry_with_items.rs");
 }
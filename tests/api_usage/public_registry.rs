@@ -0,0 +1,20 @@
+#![deny(missing_docs, private_interfaces)]
+//! Public registry
+
+fn main() {
+    assert_eq!(1, library::MYTRAIT_REGISTRY.iter().count());
+}
+
+mod library {
+    /// Trait
+    pub trait MyTrait {}
+
+    /// Registry
+    #[traitreg::registry(MyTrait)]
+    pub static MYTRAIT_REGISTRY: () = ();
+}
+
+struct MyStruct;
+
+#[traitreg::register]
+impl library::MyTrait for MyStruct {}
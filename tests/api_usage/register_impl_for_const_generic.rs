@@ -0,0 +1,19 @@
+fn main() {
+    let mut names: Vec<&str> = MYTRAIT_REGISTRY.iter().map(|item| item.name()).collect();
+    names.sort();
+    assert_eq!(names, ["Block<16>", "Block<32>"]);
+}
+
+trait MyTrait {}
+
+#[derive(Default)]
+struct Block<const N: usize>;
+
+#[traitreg::register(default)]
+impl MyTrait for Block<16> {}
+
+#[traitreg::register(default)]
+impl MyTrait for Block<32> {}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
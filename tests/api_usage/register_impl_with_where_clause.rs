@@ -0,0 +1,19 @@
+fn main() {
+    assert_eq!(1, MYTRAIT_REGISTRY.iter().count());
+    assert_eq!(1, MYTRAIT_REGISTRY.instantiate_all().count());
+}
+
+trait MyTrait {}
+
+#[derive(Default)]
+struct MyStruct;
+
+#[traitreg::register(default)]
+impl MyTrait for MyStruct
+where
+    MyStruct: Send + Sync,
+{
+}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
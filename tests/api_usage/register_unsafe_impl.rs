@@ -0,0 +1,14 @@
+fn main() {
+    assert_eq!(1, MYTRAIT_REGISTRY.iter().count());
+}
+
+unsafe trait MyTrait {}
+
+#[derive(Default)]
+struct MyStruct;
+
+#[traitreg::register(default)]
+unsafe impl MyTrait for MyStruct {}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
@@ -0,0 +1,18 @@
+fn main() {
+    assert_eq!(1, MYTRAIT_REGISTRY.iter().count());
+}
+
+trait MyTrait {}
+
+struct MyStruct;
+
+#[traitreg::register]
+impl MyTrait for MyStruct {}
+
+struct MyOtherStruct;
+
+#[traitreg::register(feature = "not-enabled")]
+impl MyTrait for MyOtherStruct {}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
@@ -0,0 +1,121 @@
+use core::alloc::Layout;
+use core::cell::{Cell, UnsafeCell};
+use core::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+trait Handler {
+    fn handle(&mut self) -> u64;
+}
+
+#[derive(Default)]
+struct Counter {
+    count: u64,
+}
+
+#[traitreg::register(default)]
+impl Handler for Counter {
+    fn handle(&mut self) -> u64 {
+        self.count += 1;
+        self.count
+    }
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Default)]
+struct Unit;
+
+#[traitreg::register(default)]
+impl Handler for Unit {
+    fn handle(&mut self) -> u64 {
+        0
+    }
+}
+
+struct Unconstructed;
+
+#[traitreg::register]
+impl Handler for Unconstructed {
+    fn handle(&mut self) -> u64 {
+        unreachable!()
+    }
+}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[repr(align(16))]
+struct FixedArena {
+    buffer: UnsafeCell<[u8; 64]>,
+    used: Cell<usize>,
+}
+
+unsafe impl traitreg::arena::Arena for FixedArena {
+    fn alloc(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let start = self.used.get().next_multiple_of(layout.align());
+        let end = start.checked_add(layout.size())?;
+        if layout.align() > 16 || end > 64 {
+            return None;
+        }
+        self.used.set(end);
+        NonNull::new(unsafe { self.buffer.get().cast::<u8>().add(start) })
+    }
+}
+
+fn get(name: &str) -> &'static traitreg::RegisteredImplWrapper<Box<dyn Handler>> {
+    HANDLER_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn main() {
+    let arena = FixedArena {
+        buffer: UnsafeCell::new([0; 64]),
+        used: Cell::new(0),
+    };
+
+    let mut counter = get("Counter").instantiate_in(&arena).unwrap();
+    assert_eq!(1, counter.handle());
+    assert_eq!(2, counter.handle());
+    assert_eq!(8, arena.used.get());
+
+    let mut unit = get("Unit").instantiate_in(&arena).unwrap();
+    assert_eq!(0, unit.handle());
+    assert_eq!(8, arena.used.get());
+
+    assert!(get("Unconstructed").instantiate_in(&arena).is_none());
+
+    drop(counter);
+    assert_eq!(1, DROPPED.load(Ordering::Relaxed));
+
+    // The arena is full
+    let instances: Vec<_> = (0..10)
+        .map_while(|_| get("Counter").instantiate_in(&arena))
+        .collect();
+    assert_eq!(7, instances.len());
+
+    #[cfg(feature = "bumpalo")]
+    {
+        let bump = bumpalo::Bump::new();
+        let mut handlers: Vec<_> = HANDLER_REGISTRY
+            .iter()
+            .filter_map(|item| item.instantiate_in(&bump))
+            .collect();
+        assert_eq!(2, handlers.len());
+        assert_eq!(
+            1,
+            handlers
+                .iter_mut()
+                .map(|handler| handler.handle())
+                .sum::<u64>()
+        );
+    }
+}
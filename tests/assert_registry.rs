@@ -0,0 +1,42 @@
+trait Plugin {}
+
+struct Audio;
+
+#[traitreg::register]
+impl Plugin for Audio {}
+
+struct Video;
+
+#[traitreg::register]
+impl Plugin for Video {}
+
+struct Network;
+
+#[traitreg::registry(Plugin, sorted)]
+static PLUGIN_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    PLUGIN_REGISTRY.assert_min_impls(0);
+    PLUGIN_REGISTRY.assert_min_impls(2);
+    PLUGIN_REGISTRY.assert_contains::<Audio>();
+    PLUGIN_REGISTRY.assert_contains::<Video>();
+}
+
+#[test]
+#[should_panic(
+    expected = "traitreg: expected at least 3 implementations in the registry of \
+    `alloc::boxed::Box<dyn assert_registry::Plugin>`, found 2: [Audio, Video]"
+)]
+fn min_impls() {
+    PLUGIN_REGISTRY.assert_min_impls(3);
+}
+
+#[test]
+#[should_panic(
+    expected = "traitreg: expected `assert_registry::Network` in the registry of \
+    `alloc::boxed::Box<dyn assert_registry::Plugin>`, found [Audio, Video]"
+)]
+fn contains() {
+    PLUGIN_REGISTRY.assert_contains::<Network>();
+}
@@ -0,0 +1,155 @@
+trait Handler {
+    type Output;
+
+    fn handle(&self) -> Self::Output;
+}
+
+#[derive(Default)]
+struct Json;
+
+#[traitreg::register(default)]
+impl Handler for Json {
+    type Output = String;
+
+    fn handle(&self) -> String {
+        "{}".to_string()
+    }
+}
+
+#[derive(Default)]
+struct Text;
+
+#[traitreg::register(default)]
+impl Handler for Text {
+    type Output = String;
+
+    fn handle(&self) -> String {
+        "text".to_string()
+    }
+}
+
+#[derive(Default)]
+struct Count;
+
+#[traitreg::register(default)]
+impl Handler for Count {
+    type Output = usize;
+
+    fn handle(&self) -> usize {
+        1
+    }
+}
+
+#[traitreg::registry(Handler<Output = String>, sorted)]
+static STRING_HANDLER_REGISTRY: () = ();
+
+#[traitreg::registry(dyn Handler<Output = usize> + Send, dispatch = count_handler)]
+static COUNT_HANDLER_REGISTRY: () = ();
+
+type StringHandler = dyn Handler<Output = String> + Send + Sync;
+
+#[traitreg::registry(StringHandler = dyn Handler<Output = String> + Send + Sync)]
+static ALIAS_HANDLER_REGISTRY: () = ();
+
+#[traitreg::registry(Handler<Output = usize>, meta_only)]
+static META_HANDLER_REGISTRY: () = ();
+
+trait Service<Request> {
+    type Response;
+
+    fn call(&self, request: Request) -> Self::Response;
+}
+
+#[derive(Default)]
+struct Echo;
+
+#[traitreg::register(default)]
+impl Service<u32> for Echo {
+    type Response = u32;
+
+    fn call(&self, request: u32) -> u32 {
+        request
+    }
+}
+
+#[traitreg::registry(Service<u32, Response = u32>)]
+static SERVICE_REGISTRY: () = ();
+
+trait Codec<T> {
+    fn encode(&self) -> T;
+}
+
+#[derive(Default)]
+struct ByteCodec;
+
+#[traitreg::register(default)]
+impl Codec<u8> for ByteCodec {
+    fn encode(&self) -> u8 {
+        1
+    }
+}
+
+#[derive(Default)]
+struct TextCodec;
+
+#[traitreg::register(default)]
+impl Codec<String> for TextCodec {
+    fn encode(&self) -> String {
+        "text".to_string()
+    }
+}
+
+#[traitreg::registry(Codec<u8>)]
+static BYTE_CODEC_REGISTRY: () = ();
+
+#[traitreg::registry(Codec<String>)]
+static TEXT_CODEC_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let outputs: Vec<String> = STRING_HANDLER_REGISTRY
+        .instantiate_all()
+        .map(|handler| handler.handle())
+        .collect();
+    assert_eq!(vec!["{}", "text"], outputs);
+
+    let counter: Box<dyn Handler<Output = usize> + Send> = count_handler("Count").unwrap();
+    assert_eq!(1, counter.handle());
+    assert!(count_handler("Json").is_none());
+    assert_eq!(1, COUNT_HANDLER_REGISTRY.len());
+
+    let _: Box<StringHandler> = ALIAS_HANDLER_REGISTRY.instantiate_all().next().unwrap();
+    assert_eq!(2, ALIAS_HANDLER_REGISTRY.len());
+
+    assert_eq!(
+        vec!["Count"],
+        META_HANDLER_REGISTRY.iter_names().collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        3,
+        SERVICE_REGISTRY.instantiate_all().next().unwrap().call(3)
+    );
+
+    // Only the implementations of the same instance of a generic trait are included
+    let codecs: Vec<Box<dyn Codec<u8>>> = BYTE_CODEC_REGISTRY.instantiate_all().collect();
+    assert_eq!(
+        vec![1],
+        codecs
+            .iter()
+            .map(|codec| codec.encode())
+            .collect::<Vec<_>>()
+    );
+    let codecs: Vec<Box<dyn Codec<String>>> = TEXT_CODEC_REGISTRY.instantiate_all().collect();
+    assert_eq!(
+        vec!["text"],
+        codecs
+            .iter()
+            .map(|codec| codec.encode())
+            .collect::<Vec<_>>()
+    );
+
+    // Registrations with other associated types are still claimed by a registry
+    assert!(traitreg::unclaimed_registrations().is_empty());
+    assert_eq!(3, traitreg::dynamic_registry("Handler").len());
+}
@@ -0,0 +1,192 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use traitreg::{AsyncInit, InstantiateError};
+
+static EVENTS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut context = Context::from_waker(std::task::Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+// Returns pending once, so other initializations can run before this one completes
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        context.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+trait Service {
+    fn ready(&self) -> bool;
+}
+
+#[derive(Default)]
+struct Database {
+    ready: bool,
+}
+
+impl AsyncInit for Database {
+    type Error = std::io::Error;
+
+    async fn init(&mut self) -> Result<(), Self::Error> {
+        EVENTS.lock().unwrap().push("Database start".to_string());
+        YieldNow(false).await;
+        EVENTS.lock().unwrap().push("Database end".to_string());
+        self.ready = true;
+        Ok(())
+    }
+}
+
+#[traitreg::register(default, async_init)]
+impl Service for Database {
+    fn ready(&self) -> bool {
+        self.ready
+    }
+}
+
+#[derive(Default)]
+struct Cache {
+    ready: bool,
+}
+
+impl AsyncInit for Cache {
+    type Error = std::io::Error;
+
+    async fn init(&mut self) -> Result<(), Self::Error> {
+        EVENTS.lock().unwrap().push("Cache start".to_string());
+        YieldNow(false).await;
+        EVENTS.lock().unwrap().push("Cache end".to_string());
+        self.ready = true;
+        Ok(())
+    }
+}
+
+#[traitreg::register(default, async_init)]
+impl Service for Cache {
+    fn ready(&self) -> bool {
+        self.ready
+    }
+}
+
+// Constructed without an init hook
+#[derive(Default)]
+struct Metrics;
+
+#[traitreg::register(default)]
+impl Service for Metrics {
+    fn ready(&self) -> bool {
+        true
+    }
+}
+
+// Skipped without a constructor
+struct Unconstructed;
+
+#[traitreg::register]
+impl Service for Unconstructed {
+    fn ready(&self) -> bool {
+        false
+    }
+}
+
+#[traitreg::registry(Service)]
+static SERVICE_REGISTRY: () = ();
+
+trait Job {}
+
+#[derive(Default)]
+struct Upload;
+
+impl AsyncInit for Upload {
+    type Error = &'static str;
+
+    async fn init(&mut self) -> Result<(), Self::Error> {
+        Err("bucket missing")
+    }
+}
+
+#[traitreg::register(default, async_init)]
+impl Job for Upload {}
+
+struct Download;
+
+impl Download {
+    fn new() -> Result<Self, &'static str> {
+        Err("offline")
+    }
+}
+
+#[traitreg::register(new)]
+impl Job for Download {}
+
+#[derive(Default)]
+struct Cleanup;
+
+#[traitreg::register(default)]
+impl Job for Cleanup {}
+
+#[traitreg::registry(Job)]
+static JOB_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let services = block_on(SERVICE_REGISTRY.init_all()).unwrap();
+    assert_eq!(3, services.len());
+    assert!(services.iter().all(|service| service.ready()));
+
+    // Initialization is concurrent, both start before either ends
+    let events = EVENTS.lock().unwrap().clone();
+    assert_eq!(4, events.len());
+    assert!(events[..2].iter().all(|event| event.ends_with("start")));
+
+    // Not initialized, so not instantiated synchronously
+    let database = SERVICE_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Database")
+        .unwrap();
+    assert!(database.has_constructor());
+    assert!(database.instantiate().is_none());
+    assert!(block_on(database.instantiate_async()).unwrap().ready());
+}
+
+#[test]
+fn errors() {
+    let Err(errors) = block_on(JOB_REGISTRY.init_all()) else {
+        panic!("Expected initialization to fail");
+    };
+    let mut failed: Vec<_> = errors
+        .errors()
+        .iter()
+        .map(|error| match error {
+            InstantiateError::ConstructorFailed { name, error, .. } => (*name, error.to_string()),
+            InstantiateError::NoConstructor { .. } => unreachable!(),
+        })
+        .collect();
+    failed.sort();
+    assert_eq!(
+        vec![
+            ("Download", "offline".to_string()),
+            ("Upload", "bucket missing".to_string())
+        ],
+        failed
+    );
+    assert!(errors
+        .to_string()
+        .starts_with("2 implementations failed to initialize\n"));
+}
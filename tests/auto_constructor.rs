@@ -0,0 +1,171 @@
+trait Plugin {
+    fn name(&self) -> String;
+}
+
+#[derive(Default)]
+struct Defaulted;
+
+#[traitreg::register(auto)]
+impl Plugin for Defaulted {
+    fn name(&self) -> String {
+        "default".to_string()
+    }
+}
+
+struct Constructed(&'static str);
+
+impl Constructed {
+    fn new() -> Self {
+        Self("new")
+    }
+}
+
+#[traitreg::register(auto)]
+impl Plugin for Constructed {
+    fn name(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+// `new` is preferred over `Default`
+#[derive(Default)]
+struct Both(&'static str);
+
+impl Both {
+    fn new() -> Self {
+        Self("both")
+    }
+}
+
+#[traitreg::register(auto)]
+impl Plugin for Both {
+    fn name(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+struct Fallible;
+
+impl Fallible {
+    fn new() -> Result<Self, &'static str> {
+        Err("unavailable")
+    }
+}
+
+#[traitreg::register(auto)]
+impl Plugin for Fallible {
+    fn name(&self) -> String {
+        "fallible".to_string()
+    }
+}
+
+struct Unconstructed;
+
+#[traitreg::register(auto)]
+impl Plugin for Unconstructed {
+    fn name(&self) -> String {
+        "unconstructed".to_string()
+    }
+}
+
+// `new` taking arguments falls back to `Default`
+#[derive(Default)]
+struct WithArgs(u64);
+
+impl WithArgs {
+    #[allow(unused)]
+    fn new(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+#[traitreg::register(auto)]
+impl Plugin for WithArgs {
+    fn name(&self) -> String {
+        format!("args {}", self.0)
+    }
+}
+
+// As does `new` returning a type which is not a constructor result
+#[derive(Default)]
+struct Optional(&'static str);
+
+impl Optional {
+    #[allow(unused)]
+    fn new() -> Option<Self> {
+        Some(Self("new"))
+    }
+}
+
+#[traitreg::register(auto)]
+impl Plugin for Optional {
+    fn name(&self) -> String {
+        format!("optional {}", self.0)
+    }
+}
+
+// Without `Default`, it is registered without a constructor
+struct Unsupported;
+
+impl Unsupported {
+    #[allow(unused)]
+    fn new(_value: u64) -> Self {
+        Self
+    }
+}
+
+#[traitreg::register(auto)]
+impl Plugin for Unsupported {
+    fn name(&self) -> String {
+        "unsupported".to_string()
+    }
+}
+
+#[traitreg::registry(Plugin, sorted)]
+static PLUGIN_REGISTRY: () = ();
+
+fn find(name: &str) -> &'static traitreg::RegisteredImplWrapper<Box<dyn Plugin>> {
+    PLUGIN_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn main() {
+    assert_eq!(8, PLUGIN_REGISTRY.len());
+
+    assert!(find("Defaulted").has_constructor());
+    assert_eq!("default", find("Defaulted").instantiate().unwrap().name());
+    assert_eq!("new", find("Constructed").instantiate().unwrap().name());
+    assert_eq!("both", find("Both").instantiate().unwrap().name());
+
+    assert!(find("Fallible").has_constructor());
+    assert!(find("Fallible").instantiate().is_none());
+
+    assert!(!find("Unconstructed").has_constructor());
+    assert!(find("Unconstructed").instantiate().is_none());
+
+    assert_eq!("args 0", find("WithArgs").instantiate().unwrap().name());
+    assert_eq!("optional ", find("Optional").instantiate().unwrap().name());
+    assert!(!find("Unsupported").has_constructor());
+    assert!(find("Unsupported").instantiate().is_none());
+
+    let mut names: Vec<String> = PLUGIN_REGISTRY
+        .instantiate_all()
+        .map(|plugin| plugin.name())
+        .collect();
+    names.sort();
+    assert_eq!(vec!["args 0", "both", "default", "new", "optional "], names);
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn arena() {
+    let bump = bumpalo::Bump::new();
+    assert_eq!(
+        "new",
+        find("Constructed").instantiate_in(&bump).unwrap().name()
+    );
+    assert!(find("Unconstructed").instantiate_in(&bump).is_none());
+}
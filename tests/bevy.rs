@@ -0,0 +1,60 @@
+#![cfg(feature = "bevy")]
+
+use bevy_app::{App, Plugin, PluginGroup};
+use std::sync::Mutex;
+use traitreg::bevy::{RegisteredPlugin, RegisteredPlugins};
+
+static BUILT: Mutex<Vec<&'static str>> = Mutex::new(vec![]);
+
+trait GamePlugin: RegisteredPlugin {}
+
+#[derive(Default)]
+struct RenderPlugin;
+
+impl Plugin for RenderPlugin {
+    fn build(&self, _app: &mut App) {
+        BUILT.lock().unwrap().push("render");
+    }
+}
+
+#[traitreg::register(default)]
+impl GamePlugin for RenderPlugin {}
+
+#[derive(Default)]
+struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, _app: &mut App) {
+        BUILT.lock().unwrap().push("physics");
+    }
+}
+
+#[traitreg::register(default, priority = 10)]
+impl GamePlugin for PhysicsPlugin {}
+
+struct DisabledPlugin;
+
+impl Plugin for DisabledPlugin {
+    fn build(&self, _app: &mut App) {
+        BUILT.lock().unwrap().push("disabled");
+    }
+}
+
+#[traitreg::register]
+impl GamePlugin for DisabledPlugin {}
+
+#[traitreg::registry(GamePlugin)]
+static GAME_PLUGIN_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let group = RegisteredPlugins::new(&GAME_PLUGIN_REGISTRY).build();
+    assert!(group.contains::<PhysicsPlugin>());
+    assert!(group.contains::<RenderPlugin>());
+    assert!(!group.contains::<DisabledPlugin>());
+
+    let mut app = App::new();
+    app.add_plugins(RegisteredPlugins::new(&GAME_PLUGIN_REGISTRY));
+    assert!(app.is_plugin_added::<PhysicsPlugin>());
+    assert_eq!(vec!["physics", "render"], *BUILT.lock().unwrap());
+}
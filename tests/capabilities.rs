@@ -0,0 +1,48 @@
+trait Exporter {}
+
+struct JsonExporter;
+
+#[traitreg::register(capabilities = ["json", "streaming"])]
+impl Exporter for JsonExporter {}
+
+struct CsvExporter;
+
+#[traitreg::register(capabilities = ["csv"])]
+impl Exporter for CsvExporter {}
+
+struct NullExporter;
+
+#[traitreg::register]
+impl Exporter for NullExporter {}
+
+#[traitreg::registry(Exporter)]
+static EXPORTER_REGISTRY: () = ();
+
+fn compatible(requirements: &[&str]) -> Vec<&'static str> {
+    let mut names: Vec<&str> = EXPORTER_REGISTRY
+        .compatible_with(requirements)
+        .map(|item| item.name())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn main() {
+    let json = EXPORTER_REGISTRY
+        .iter()
+        .find(|item| item.name() == "JsonExporter")
+        .unwrap();
+    assert_eq!(&["json", "streaming"], json.capabilities());
+    assert!(json.provides(&["streaming"]));
+    assert!(!json.provides(&["streaming", "csv"]));
+
+    assert_eq!(vec!["JsonExporter"], compatible(&["json"]));
+    assert_eq!(vec!["JsonExporter"], compatible(&["streaming", "json"]));
+    assert_eq!(vec!["CsvExporter"], compatible(&["csv"]));
+    assert!(compatible(&["json", "csv"]).is_empty());
+    assert_eq!(
+        vec!["CsvExporter", "JsonExporter", "NullExporter"],
+        compatible(&[])
+    );
+}
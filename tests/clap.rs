@@ -0,0 +1,92 @@
+#![cfg(feature = "clap")]
+
+use std::sync::Mutex;
+use traitreg::clap::{RegisteredSubcommands, Subcommand};
+
+static OUTPUT: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+trait Plugin: Subcommand {}
+
+#[derive(Default)]
+struct Add;
+
+impl Subcommand for Add {
+    fn name(&self) -> &'static str {
+        "add"
+    }
+
+    fn about(&self) -> Option<&'static str> {
+        Some("Add two numbers")
+    }
+
+    fn args(&self) -> Vec<clap::Arg> {
+        vec![
+            clap::Arg::new("a")
+                .required(true)
+                .value_parser(clap::value_parser!(i64)),
+            clap::Arg::new("b")
+                .required(true)
+                .value_parser(clap::value_parser!(i64)),
+        ]
+    }
+
+    fn run(&self, matches: &clap::ArgMatches) {
+        let a = matches.get_one::<i64>("a").unwrap();
+        let b = matches.get_one::<i64>("b").unwrap();
+        OUTPUT.lock().unwrap().push(format!("{}", a + b));
+    }
+}
+
+#[traitreg::register(default)]
+impl Plugin for Add {}
+
+#[derive(Default)]
+struct Version;
+
+impl Subcommand for Version {
+    fn name(&self) -> &'static str {
+        "version"
+    }
+
+    fn run(&self, _matches: &clap::ArgMatches) {
+        OUTPUT.lock().unwrap().push("1.0".to_string());
+    }
+}
+
+#[traitreg::register(default)]
+impl Plugin for Version {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let subcommands = RegisteredSubcommands::new(&PLUGIN_REGISTRY);
+    assert_eq!(2, subcommands.subcommands().len());
+
+    let command = subcommands.command(clap::Command::new("tool"));
+    let add = command.find_subcommand("add").unwrap();
+    assert_eq!(
+        Some("Add two numbers"),
+        add.get_about().map(|about| about.to_string()).as_deref()
+    );
+
+    let matches = command
+        .clone()
+        .try_get_matches_from(["tool", "add", "2", "3"])
+        .unwrap();
+    assert!(subcommands.dispatch(&matches));
+
+    let matches = command
+        .clone()
+        .try_get_matches_from(["tool", "version"])
+        .unwrap();
+    assert!(subcommands.dispatch(&matches));
+
+    let matches = command.clone().try_get_matches_from(["tool"]).unwrap();
+    assert!(!subcommands.dispatch(&matches));
+
+    assert!(command.try_get_matches_from(["tool", "add", "2"]).is_err());
+
+    assert_eq!(vec!["5", "1.0"], *OUTPUT.lock().unwrap());
+}
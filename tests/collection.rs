@@ -0,0 +1,55 @@
+struct Route {
+    path: &'static str,
+    handler: fn() -> &'static str,
+}
+
+static INDEX: Route = Route {
+    path: "/",
+    handler: || "index",
+};
+
+traitreg::submit!(INDEX as Route);
+traitreg::submit!(Route {
+    path: "/about",
+    handler: || "about",
+} as Route);
+
+mod admin {
+    traitreg::submit!(super::Route {
+        path: "/admin",
+        handler: || "admin",
+    } as super::Route);
+}
+
+// Values are only collected by collections of their type
+traitreg::submit!("/not-a-route" as &'static str);
+
+#[traitreg::collection(Route)]
+static ROUTES: () = ();
+
+#[traitreg::collection(&'static str)]
+static STRINGS: () = ();
+
+#[traitreg::collection(u64)]
+static EMPTY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(3, ROUTES.len());
+    let mut routes: Vec<_> = ROUTES
+        .iter()
+        .map(|route| (route.path, (route.handler)()))
+        .collect();
+    routes.sort();
+    assert_eq!(
+        vec![("/", "index"), ("/about", "about"), ("/admin", "admin")],
+        routes
+    );
+
+    // Statics are collected by reference, rather than copied
+    assert!(ROUTES.iter().any(|route| core::ptr::eq(route, &INDEX)));
+
+    assert_eq!(vec![&"/not-a-route"], STRINGS.as_slice());
+    assert!(EMPTY.is_empty());
+    assert_eq!(0, (&*EMPTY).into_iter().count());
+}
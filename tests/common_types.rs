@@ -0,0 +1,78 @@
+use std::any::TypeId;
+
+trait Reader {
+    fn read(&self) -> &'static str;
+}
+
+trait Seekable {
+    fn seek(&self) -> usize;
+}
+
+mod files {
+    #[derive(Default)]
+    pub struct File;
+
+    #[traitreg::register(default)]
+    impl super::Reader for File {
+        fn read(&self) -> &'static str {
+            "file"
+        }
+    }
+}
+
+// Registered through a re-export, so the path differs from the registration above
+pub use files::File as ReexportedFile;
+
+#[traitreg::register(default)]
+impl Seekable for ReexportedFile {
+    fn seek(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Default)]
+struct Socket;
+
+#[traitreg::register(default)]
+impl Reader for Socket {
+    fn read(&self) -> &'static str {
+        "socket"
+    }
+}
+
+#[derive(Default)]
+struct Cursor;
+
+#[traitreg::register(default)]
+impl Seekable for Cursor {
+    fn seek(&self) -> usize {
+        2
+    }
+}
+
+#[traitreg::registry(Reader)]
+static READER_REGISTRY: () = ();
+
+#[traitreg::registry(Seekable)]
+static SEEKABLE_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let common: Vec<_> = READER_REGISTRY.common_types(&SEEKABLE_REGISTRY).collect();
+    assert_eq!(1, common.len());
+
+    let (reader, seekable) = common[0];
+    assert_eq!("File", reader.path());
+    assert_eq!("ReexportedFile", seekable.path());
+    assert_eq!(TypeId::of::<files::File>(), reader.type_id());
+    assert_eq!("file", reader.instantiate().unwrap().read());
+    assert_eq!(1, seekable.instantiate().unwrap().seek());
+
+    let socket = READER_REGISTRY
+        .get_by_type_id(TypeId::of::<Socket>())
+        .unwrap();
+    assert_eq!("Socket", socket.name());
+    assert!(SEEKABLE_REGISTRY
+        .get_by_type_id(TypeId::of::<Socket>())
+        .is_none());
+}
@@ -0,0 +1,101 @@
+use traitreg::config::{ImplConfig, RegistryConfig};
+
+trait Codec {}
+
+#[derive(Default)]
+struct Gzip;
+
+#[traitreg::register(default, priority = 5)]
+impl Codec for Gzip {}
+
+#[derive(Default)]
+struct Zstd;
+
+#[traitreg::register(default)]
+impl Codec for Zstd {}
+
+#[derive(Default)]
+struct Brotli;
+
+#[traitreg::register(default)]
+impl Codec for Brotli {}
+
+#[traitreg::registry(Codec, sorted)]
+static CODEC_REGISTRY: () = ();
+
+fn configure(name: &str, enabled: Option<bool>, priority: Option<i32>) -> (String, ImplConfig) {
+    (name.to_string(), ImplConfig { enabled, priority })
+}
+
+#[test]
+fn main() {
+    // The default config changes nothing
+    let registry = CODEC_REGISTRY.apply_config(&RegistryConfig::default());
+    assert_eq!(
+        CODEC_REGISTRY.iter_names().collect::<Vec<_>>(),
+        registry.iter_names().collect::<Vec<_>>()
+    );
+
+    let config = RegistryConfig {
+        enabled: true,
+        implementations: [
+            configure("Gzip", Some(false), None),
+            configure("Zstd", None, Some(10)),
+            configure("Missing", Some(true), Some(1)),
+        ]
+        .into_iter()
+        .collect(),
+    };
+    let registry = CODEC_REGISTRY.apply_config(&config);
+    assert_eq!(
+        vec!["Brotli", "Zstd"],
+        registry.iter_names().collect::<Vec<_>>()
+    );
+    assert_eq!("Zstd", registry.iter_by_priority().next().unwrap().name());
+    assert_eq!(2, registry.instantiate_all().count());
+
+    // The registry itself is unchanged
+    assert_eq!(3, CODEC_REGISTRY.len());
+    assert!(CODEC_REGISTRY
+        .iter()
+        .all(|item| item.name() == "Gzip" || item.priority() == 0));
+
+    // Only implementations which are enabled explicitly
+    let config = RegistryConfig {
+        enabled: false,
+        implementations: [
+            configure("Gzip", Some(true), None),
+            configure("Zstd", None, Some(1)),
+        ]
+        .into_iter()
+        .collect(),
+    };
+    let registry = CODEC_REGISTRY.apply_config(&config);
+    assert_eq!(vec!["Gzip"], registry.iter_names().collect::<Vec<_>>());
+    assert_eq!(5, registry.iter().next().unwrap().priority());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize() {
+    let config: RegistryConfig = serde_json::from_value(serde_json::json!({
+        "implementations": {
+            "Brotli": { "enabled": false },
+            "Zstd": { "priority": 3 }
+        }
+    }))
+    .unwrap();
+    assert!(config.enabled);
+    assert!(!config.is_enabled("Brotli"));
+    assert!(config.is_enabled("Gzip"));
+    assert_eq!(Some(3), config.priority("Zstd"));
+
+    let registry = CODEC_REGISTRY.apply_config(&config);
+    assert_eq!(
+        vec!["Gzip", "Zstd"],
+        registry.iter_names().collect::<Vec<_>>()
+    );
+
+    let config: RegistryConfig = serde_json::from_str(r#"{ "enabled": false }"#).unwrap();
+    assert!(CODEC_REGISTRY.apply_config(&config).is_empty());
+}
@@ -0,0 +1,133 @@
+use traitreg::{Conflict, FailurePolicy};
+
+trait Allocator {}
+
+struct Bump;
+
+#[traitreg::register(conflicts_with("Slab", "Arena"))]
+impl Allocator for Bump {}
+
+struct Slab;
+
+#[traitreg::register(conflicts_with("Bump"))]
+impl Allocator for Slab {}
+
+struct System;
+
+#[traitreg::register]
+impl Allocator for System {}
+
+#[traitreg::registry(Allocator, sorted)]
+static ALLOCATOR_REGISTRY: () = ();
+
+trait Codec {}
+
+struct Gzip;
+
+// Conflicts with an implementation which is not registered
+#[traitreg::register(conflicts_with("Zstd"))]
+impl Codec for Gzip {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+trait Cache {}
+
+mod a {
+    pub struct Pool;
+}
+
+mod b {
+    pub struct Pool;
+}
+
+#[traitreg::register]
+impl Cache for a::Pool {}
+
+#[traitreg::register]
+impl Cache for b::Pool {}
+
+struct Lru;
+
+// Only conflicts with one of the implementations named `Pool`
+#[traitreg::register(conflicts_with("a::Pool"))]
+impl Cache for Lru {}
+
+#[traitreg::registry(Cache)]
+static CACHE_REGISTRY: () = ();
+
+trait Sink {}
+
+struct Stdout;
+
+#[traitreg::register(conflicts_with("Stderr"))]
+impl Sink for Stdout {}
+
+struct Stderr;
+
+#[traitreg::register]
+impl Sink for Stderr {}
+
+struct File;
+
+#[traitreg::register]
+impl Sink for File {}
+
+#[traitreg::registry(Sink, on_failure = skip)]
+static SINK_REGISTRY: () = ();
+
+trait Runtime {}
+
+struct Tokio;
+
+#[traitreg::register(conflicts_with("Smol"))]
+impl Runtime for Tokio {}
+
+struct Smol;
+
+#[traitreg::register]
+impl Runtime for Smol {}
+
+// The failure policy is global, so everything is checked in one test
+#[test]
+fn main() {
+    let bump = ALLOCATOR_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Bump")
+        .unwrap();
+    assert_eq!(&["Slab", "Arena"], bump.conflicts_with());
+
+    // Declared by both, but only reported once
+    let conflicts = ALLOCATOR_REGISTRY.conflicts();
+    assert_eq!(
+        vec![Conflict {
+            path: "Bump",
+            conflicts_with: "Slab",
+            trait_name: "Allocator",
+        }],
+        conflicts
+    );
+    assert_eq!(
+        "`Bump` conflicts with `Slab`, but both are registered as \
+        implementations of `Allocator`",
+        conflicts[0].to_string()
+    );
+    assert_eq!(Err(conflicts[0]), ALLOCATOR_REGISTRY.check_conflicts());
+
+    assert!(CODEC_REGISTRY.conflicts().is_empty());
+    assert_eq!(Ok(()), CODEC_REGISTRY.check_conflicts());
+
+    let conflicts = CACHE_REGISTRY.conflicts();
+    assert_eq!(1, conflicts.len());
+    assert_eq!("Lru", conflicts[0].path);
+    assert_eq!("a::Pool", conflicts[0].conflicts_with.replace(' ', ""));
+
+    // Both conflicting implementations are left out
+    assert_eq!(vec!["File"], SINK_REGISTRY.iter_names().collect::<Vec<_>>());
+    assert_eq!(Ok(()), SINK_REGISTRY.check_conflicts());
+
+    traitreg::set_failure_policy(FailurePolicy::Panic);
+    assert!(std::panic::catch_unwind(|| traitreg::dynamic_registry("Runtime")).is_err());
+    traitreg::set_failure_policy(FailurePolicy::Log);
+    assert_eq!(2, traitreg::dynamic_registry("Runtime").len());
+}
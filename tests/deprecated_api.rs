@@ -0,0 +1,18 @@
+#![allow(deprecated)]
+
+trait MyTrait {}
+
+#[derive(Default)]
+struct MyStruct;
+
+#[traitreg::register(default)]
+impl MyTrait for MyStruct {}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(1, MYTRAIT_REGISTRY.instanciate_all().count());
+    assert!(MYTRAIT_REGISTRY.iter().next().unwrap().instanciate().is_some());
+}
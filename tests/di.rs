@@ -0,0 +1,271 @@
+#![cfg(feature = "di")]
+
+use std::sync::Arc;
+use traitreg::di::{Container, ResolveError, Resolver};
+
+trait Database {
+    fn query(&self) -> &'static str;
+}
+
+#[derive(Default)]
+struct Postgres;
+
+#[traitreg::register(default)]
+impl Database for Postgres {
+    fn query(&self) -> &'static str {
+        "postgres"
+    }
+}
+
+struct InMemory;
+
+impl Database for InMemory {
+    fn query(&self) -> &'static str {
+        "in memory"
+    }
+}
+
+trait Cache {
+    fn get(&self) -> &'static str;
+}
+
+#[derive(Default)]
+struct Redis;
+
+#[traitreg::register(default)]
+impl Cache for Redis {
+    fn get(&self) -> &'static str {
+        "redis"
+    }
+}
+
+#[derive(Default)]
+struct Memcached;
+
+#[traitreg::register(default)]
+impl Cache for Memcached {
+    fn get(&self) -> &'static str {
+        "memcached"
+    }
+}
+
+trait Unused {}
+
+#[traitreg::registry(Database)]
+static DATABASE_REGISTRY: () = ();
+
+#[traitreg::registry(Cache)]
+static CACHE_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let mut container = Container::new();
+    container.add_registry(&DATABASE_REGISTRY);
+    container.add_registry(&CACHE_REGISTRY);
+
+    assert_eq!(
+        "postgres",
+        container.resolve::<dyn Database>().unwrap().query()
+    );
+    assert_eq!(2, container.resolve_all::<dyn Cache>().unwrap().len());
+
+    let Err(ResolveError::Ambiguous { candidates, .. }) = container.resolve::<dyn Cache>() else {
+        panic!("Expected ambiguous resolution");
+    };
+    assert_eq!(2, candidates.len());
+
+    container.bind_implementation::<dyn Cache>("Memcached");
+    assert_eq!("memcached", container.resolve::<dyn Cache>().unwrap().get());
+
+    container.bind_implementation::<dyn Cache>("Missing");
+    let error = container.resolve::<dyn Cache>().err().unwrap();
+    assert!(matches!(
+        error,
+        ResolveError::UnknownImplementation {
+            name: "Missing",
+            ..
+        }
+    ));
+
+    container.bind::<dyn Database>(|| Box::new(InMemory));
+    assert_eq!(
+        "in memory",
+        container.resolve::<dyn Database>().unwrap().query()
+    );
+
+    let error = container.resolve::<dyn Unused>().err().unwrap();
+    assert!(matches!(error, ResolveError::MissingBinding { .. }));
+    assert!(error.to_string().contains("Unused"));
+}
+
+trait Service {
+    fn describe(&self) -> String;
+}
+
+struct UserService {
+    database: Arc<dyn Database>,
+}
+
+impl UserService {
+    fn new(resolver: &Resolver) -> Self {
+        Self {
+            database: resolver.resolve().expect("Database is registered"),
+        }
+    }
+}
+
+#[traitreg::register(new, inject)]
+impl Service for UserService {
+    fn describe(&self) -> String {
+        format!("users in {}", self.database.query())
+    }
+}
+
+#[traitreg::registry(Service)]
+static SERVICE_REGISTRY: () = ();
+
+#[test]
+fn inject() {
+    let registered = SERVICE_REGISTRY.iter().next().unwrap();
+    assert!(registered.has_constructor());
+    assert!(registered.instantiate().is_none());
+
+    let mut container = Container::new();
+    container.add_registry(&DATABASE_REGISTRY);
+    container.add_registry(&SERVICE_REGISTRY);
+    assert_eq!(
+        "users in postgres",
+        container.resolve::<dyn Service>().unwrap().describe()
+    );
+
+    container.bind::<dyn Database>(|| Box::new(InMemory));
+    assert_eq!(
+        "users in in memory",
+        container.resolve::<dyn Service>().unwrap().describe()
+    );
+}
+
+trait Clock {}
+
+#[derive(Default)]
+struct SystemClock;
+
+#[traitreg::register(default, lifetime = singleton)]
+impl Clock for SystemClock {}
+
+trait Session {}
+
+#[derive(Default)]
+struct RequestSession;
+
+#[traitreg::register(default, lifetime = scoped)]
+impl Session for RequestSession {}
+
+#[traitreg::registry(Clock)]
+static CLOCK_REGISTRY: () = ();
+
+#[traitreg::registry(Session)]
+static SESSION_REGISTRY: () = ();
+
+#[test]
+fn lifetimes() {
+    let mut container = Container::new();
+    container.add_registry(&DATABASE_REGISTRY);
+    container.add_registry(&CLOCK_REGISTRY);
+    container.add_registry(&SESSION_REGISTRY);
+
+    let first = container.resolve::<dyn Database>().unwrap();
+    let second = container.resolve::<dyn Database>().unwrap();
+    assert!(!Arc::ptr_eq(&first, &second));
+
+    let first = container.resolve::<dyn Clock>().unwrap();
+    let second = container.resolve::<dyn Clock>().unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+
+    let error = container.resolve::<dyn Session>().err().unwrap();
+    assert!(matches!(
+        error,
+        ResolveError::MissingScope {
+            name: "RequestSession",
+            ..
+        }
+    ));
+
+    let scope = container.scope();
+    let first = scope.resolve::<dyn Session>().unwrap();
+    let second = scope.resolve::<dyn Session>().unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+    assert!(Arc::ptr_eq(
+        &scope.resolve::<dyn Clock>().unwrap(),
+        &container.resolve::<dyn Clock>().unwrap()
+    ));
+
+    let other_scope = container.scope();
+    assert!(!Arc::ptr_eq(
+        &first,
+        &other_scope.resolve::<dyn Session>().unwrap()
+    ));
+}
+
+trait Producer {}
+
+trait Consumer {}
+
+struct CyclicProducer {
+    _consumer: Arc<dyn Consumer>,
+}
+
+impl CyclicProducer {
+    fn new(resolver: &Resolver) -> Result<Self, ResolveError> {
+        Ok(Self {
+            _consumer: resolver.resolve()?,
+        })
+    }
+}
+
+#[traitreg::register(new, inject)]
+impl Producer for CyclicProducer {}
+
+struct CyclicConsumer {
+    _producer: Arc<dyn Producer>,
+}
+
+impl CyclicConsumer {
+    fn new(resolver: &Resolver) -> Result<Self, ResolveError> {
+        Ok(Self {
+            _producer: resolver.resolve()?,
+        })
+    }
+}
+
+#[traitreg::register(new, inject)]
+impl Consumer for CyclicConsumer {}
+
+#[traitreg::registry(Producer)]
+static PRODUCER_REGISTRY: () = ();
+
+#[traitreg::registry(Consumer)]
+static CONSUMER_REGISTRY: () = ();
+
+#[test]
+fn cycle() {
+    let mut container = Container::new();
+    container.add_registry(&PRODUCER_REGISTRY);
+    container.add_registry(&CONSUMER_REGISTRY);
+
+    let error = container.resolve::<dyn Producer>().err().unwrap();
+    assert_eq!(
+        ResolveError::Cycle {
+            cycle: vec!["CyclicProducer", "CyclicConsumer", "CyclicProducer"]
+        },
+        error
+    );
+    assert_eq!(
+        "Circular dependency between 'CyclicProducer' -> 'CyclicConsumer' -> 'CyclicProducer'",
+        error.to_string()
+    );
+
+    // Resolution after a cycle is not affected by the earlier attempt
+    let error = container.resolve::<dyn Consumer>().err().unwrap();
+    assert!(matches!(error, ResolveError::Cycle { cycle } if cycle[0] == "CyclicConsumer"));
+}
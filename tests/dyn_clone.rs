@@ -0,0 +1,38 @@
+#![cfg(feature = "dyn-clone")]
+
+trait MyTrait: dyn_clone::DynClone {
+    fn foo(&self) -> u32;
+}
+
+#[derive(Clone)]
+struct MyStruct {
+    value: u32,
+}
+
+impl MyStruct {
+    fn new() -> Self {
+        Self { value: 123 }
+    }
+}
+
+#[traitreg::register(new)]
+impl MyTrait for MyStruct {
+    fn foo(&self) -> u32 {
+        self.value
+    }
+}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let registered = MYTRAIT_REGISTRY.iter().next().unwrap();
+    let instance = registered.instantiate().unwrap();
+    assert_eq!(123, registered.clone_instance(&*instance).foo());
+
+    let instances: Vec<Vec<Box<dyn MyTrait>>> = MYTRAIT_REGISTRY.instantiate_all_cloned(3).collect();
+    assert_eq!(1, instances.len());
+    assert_eq!(3, instances[0].len());
+    assert!(instances[0].iter().all(|instance| instance.foo() == 123));
+}
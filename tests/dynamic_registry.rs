@@ -0,0 +1,61 @@
+mod formats {
+    pub trait Format {
+        fn extension(&self) -> &'static str;
+    }
+}
+
+use formats::Format;
+
+#[derive(Default)]
+struct Json;
+
+#[traitreg::register(default, any)]
+impl Format for Json {
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+}
+
+// Included whatever auto traits it implements
+#[derive(Default)]
+struct Local(core::marker::PhantomData<std::rc::Rc<()>>);
+
+#[traitreg::register(default, tags = ["local"])]
+impl Format for Local {
+    fn extension(&self) -> &'static str {
+        "local"
+    }
+}
+
+#[traitreg::registry(dyn Format + Send + Sync)]
+static FORMAT_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(1, FORMAT_REGISTRY.len());
+
+    let registry = traitreg::dynamic_registry("formats::Format");
+    let mut names: Vec<_> = registry.iter().map(|item| item.name()).collect();
+    names.sort();
+    assert_eq!(vec!["Json", "Local"], names);
+    assert_eq!(2, traitreg::dynamic_registry("Format").len());
+    assert!(traitreg::dynamic_registry("formats::Unknown").is_empty());
+
+    // Dynamic registries of a trait share their entries
+    assert!(core::ptr::eq(
+        registry.as_slice(),
+        traitreg::dynamic_registry("Format").as_slice()
+    ));
+
+    // Entries are type erased, but can be instantiated as `Any` when registered with `any`
+    let json = registry.iter().find(|item| item.name() == "Json").unwrap();
+    assert!(json.has_constructor());
+    assert_eq!("json", json.instantiate_downcast::<Json>().unwrap().extension());
+    let local = registry.iter().find(|item| item.name() == "Local").unwrap();
+    assert_eq!(&["local"], local.tags());
+    assert!(local.instantiate_any().is_none());
+
+    // Dynamic registries are not declared registries
+    let unclaimed = traitreg::unclaimed_registrations();
+    assert!(unclaimed.iter().any(|item| item.name == "Local"));
+}
@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+static CONSTRUCTED: AtomicU32 = AtomicU32::new(0);
+
+trait Plugin: Send + Sync {
+    fn name(&self) -> &str;
+}
+
+struct Audio;
+
+impl Audio {
+    fn new() -> Self {
+        CONSTRUCTED.fetch_add(1, Ordering::Relaxed);
+        Self
+    }
+}
+
+#[traitreg::register(new)]
+impl Plugin for Audio {
+    fn name(&self) -> &str {
+        "audio"
+    }
+}
+
+struct Failing;
+
+impl Failing {
+    fn new() -> Result<Self, &'static str> {
+        Err("unavailable")
+    }
+}
+
+#[traitreg::register(new)]
+impl Plugin for Failing {
+    fn name(&self) -> &str {
+        "failing"
+    }
+}
+
+struct Unconstructed;
+
+#[traitreg::register]
+impl Plugin for Unconstructed {
+    fn name(&self) -> &str {
+        "unconstructed"
+    }
+}
+
+#[traitreg::registry(Plugin, eager)]
+static EAGER_REGISTRY: () = ();
+
+#[traitreg::registry(Plugin, eager, wrap = Arc<Mutex<_>>)]
+static WRAPPED_REGISTRY: () = ();
+
+#[traitreg::registry(Plugin)]
+static LAZY_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    // Constructed once by each eager registry before main
+    assert_eq!(2, CONSTRUCTED.load(Ordering::Relaxed));
+
+    let names: Vec<&str> = EAGER_REGISTRY
+        .instances()
+        .iter()
+        .map(|plugin| plugin.name())
+        .collect();
+    assert_eq!(vec!["audio"], names);
+    assert_eq!(3, EAGER_REGISTRY.len());
+
+    let instance = &WRAPPED_REGISTRY.instances()[0];
+    assert_eq!("audio", instance.lock().unwrap().name());
+
+    // Shared between threads
+    let handle = std::thread::spawn(|| EAGER_REGISTRY.instances().len());
+    assert_eq!(1, handle.join().unwrap());
+
+    assert!(LAZY_REGISTRY.instances().is_empty());
+    let config = traitreg::config::RegistryConfig::default();
+    assert!(EAGER_REGISTRY.apply_config(&config).instances().is_empty());
+    assert_eq!(2, CONSTRUCTED.load(Ordering::Relaxed));
+}
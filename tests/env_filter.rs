@@ -0,0 +1,56 @@
+#![cfg(feature = "env-filter")]
+
+trait Plugin {}
+
+struct Noisy;
+
+#[traitreg::register]
+impl Plugin for Noisy {}
+
+struct Quiet;
+
+#[traitreg::register]
+impl Plugin for Quiet {}
+
+struct Broken;
+
+#[traitreg::register]
+impl Plugin for Broken {}
+
+trait Codec {}
+
+struct Gzip;
+
+#[traitreg::register]
+impl Codec for Gzip {}
+
+struct Zstd;
+
+#[traitreg::register]
+impl Codec for Zstd {}
+
+trait Handler {}
+
+struct Noisy2;
+
+#[traitreg::register]
+impl Handler for Noisy2 {}
+
+// The variable is read when the first registry is built, so no registry is declared with
+// `registry`, which would be built before `main()`
+#[test]
+fn main() {
+    std::env::set_var(
+        "TRAITREG_DISABLE",
+        "my::Plugin::Noisy, Codec::*, Broken,,Unknown::Noisy2",
+    );
+
+    let names = |trait_| {
+        let mut names: Vec<_> = traitreg::dynamic_registry(trait_).iter_names().collect();
+        names.sort();
+        names
+    };
+    assert_eq!(vec!["Quiet"], names("Plugin"));
+    assert!(names("Codec").is_empty());
+    assert_eq!(vec!["Noisy2"], names("Handler"));
+}
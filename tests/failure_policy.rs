@@ -0,0 +1,62 @@
+use traitreg::FailurePolicy;
+
+fn unavailable() -> bool {
+    panic!("unavailable")
+}
+
+trait Logged {}
+
+struct LoggedImpl;
+
+#[traitreg::register(guard = unavailable)]
+impl Logged for LoggedImpl {}
+
+#[traitreg::registry(Logged)]
+static LOGGED_REGISTRY: () = ();
+
+trait Quiet {}
+
+struct QuietImpl;
+
+#[traitreg::register(guard = unavailable)]
+impl Quiet for QuietImpl {}
+
+#[traitreg::registry(Quiet, on_failure = skip)]
+static QUIET_REGISTRY: () = ();
+
+trait Dynamic {}
+
+struct DynamicImpl;
+
+#[traitreg::register(guard = unavailable)]
+impl Dynamic for DynamicImpl {}
+
+// The policy is global, so everything is checked in one test
+#[test]
+fn main() {
+    assert!(LOGGED_REGISTRY.is_empty());
+    assert!(QUIET_REGISTRY.is_empty());
+    assert_eq!(3, traitreg::registration_panics().len());
+
+    // Failures are handled once, by the first registry of the trait to be built
+    assert_eq!(FailurePolicy::Log, traitreg::failure_policy());
+    traitreg::set_failure_policy(FailurePolicy::Panic);
+    assert_eq!(FailurePolicy::Panic, traitreg::failure_policy());
+    assert!(std::panic::catch_unwind(|| traitreg::dynamic_registry("Dynamic")).is_err());
+    assert!(traitreg::dynamic_registry("Dynamic").is_empty());
+    traitreg::set_failure_policy(FailurePolicy::Log);
+
+    // The registries are built when the test binary starts, so run it again to capture errors
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .arg("--list")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let errors: Vec<_> = stderr
+        .lines()
+        .filter(|line| line.starts_with("traitreg: error:"))
+        .collect();
+    assert_eq!(1, errors.len(), "{stderr}");
+    assert!(errors[0].contains("`LoggedImpl` as an implementation of `Logged`"));
+}
@@ -0,0 +1,64 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CONSTRUCTED: AtomicUsize = AtomicUsize::new(0);
+
+trait Connection {}
+
+struct Database;
+
+impl Database {
+    fn connect() -> Self {
+        CONSTRUCTED.fetch_add(1, Ordering::Relaxed);
+        Database
+    }
+}
+
+#[traitreg::register(connect)]
+impl Connection for Database {}
+
+struct Offline;
+
+#[traitreg::register]
+impl Connection for Offline {}
+
+#[traitreg::registry(Connection)]
+static CONNECTION_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let formatted: Vec<_> = CONNECTION_REGISTRY
+        .iter()
+        .map(|item| format!("{item:?}"))
+        .collect();
+    assert_eq!(0, CONSTRUCTED.load(Ordering::Relaxed));
+
+    let database = formatted
+        .iter()
+        .find(|item| item.contains("Database"))
+        .unwrap();
+    assert!(database.contains("Has Constructor: true"));
+    let offline = formatted
+        .iter()
+        .find(|item| item.contains("Offline"))
+        .unwrap();
+    assert!(offline.contains("Has Constructor: false"));
+
+    // Display is one line
+    let offline = CONNECTION_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Offline")
+        .unwrap();
+    assert_eq!(
+        "formatting: Offline implements Connection (ctor: no)",
+        offline.to_string()
+    );
+    let database = CONNECTION_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Database")
+        .unwrap();
+    assert_eq!(
+        "formatting: Database implements Connection (ctor: yes)",
+        database.to_string()
+    );
+    assert_eq!(0, CONSTRUCTED.load(Ordering::Relaxed));
+}
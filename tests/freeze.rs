@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SHUTDOWN: AtomicUsize = AtomicUsize::new(0);
+
+trait Plugin {}
+
+struct Audio;
+
+fn stop_audio() {
+    SHUTDOWN.fetch_add(1, Ordering::Relaxed);
+}
+
+#[traitreg::register(shutdown = stop_audio)]
+impl Plugin for Audio {}
+
+#[derive(Default)]
+struct Video;
+
+#[traitreg::register(default, any)]
+impl Plugin for Video {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+trait Unused {}
+
+struct Orphan;
+
+#[traitreg::register]
+impl Unused for Orphan {}
+
+// Freezing affects the whole process, so everything is checked in one test
+#[test]
+fn main() {
+    assert!(!traitreg::is_frozen());
+    traitreg::freeze();
+    assert!(traitreg::is_frozen());
+    traitreg::freeze();
+    assert!(traitreg::is_frozen());
+
+    assert_eq!(2, PLUGIN_REGISTRY.len());
+
+    let registry = traitreg::dynamic_registry("Plugin");
+    assert_eq!(2, registry.len());
+    let video = registry.iter().find(|item| item.name() == "Video").unwrap();
+    assert!(video.instantiate_downcast::<Video>().is_some());
+
+    let unclaimed = traitreg::unclaimed_registrations();
+    assert_eq!(1, unclaimed.len());
+    assert_eq!("Orphan", unclaimed[0].name);
+
+    // Shutdown hooks still run once after freezing
+    traitreg::shutdown_all();
+    traitreg::shutdown_all();
+    assert_eq!(1, SHUTDOWN.load(Ordering::Relaxed));
+}
@@ -0,0 +1,106 @@
+use traitreg::health::{HealthCheck, HealthStatus};
+
+trait Service {}
+
+struct Database;
+
+fn database_health() -> HealthStatus {
+    HealthStatus::Unhealthy("connection refused".to_string())
+}
+
+#[traitreg::register(health_check = database_health)]
+impl Service for Database {}
+
+struct Cache;
+
+fn cache_health() -> HealthStatus {
+    HealthStatus::Degraded("evicting".to_string())
+}
+
+#[traitreg::register(priority = 5, health_check = cache_health)]
+impl Service for Cache {}
+
+struct Server;
+
+fn server_health() -> HealthStatus {
+    HealthStatus::Healthy
+}
+
+#[traitreg::register(health_check = server_health)]
+impl Service for Server {}
+
+struct Metrics;
+
+#[traitreg::register]
+impl Service for Metrics {}
+
+#[traitreg::registry(Service)]
+static SERVICE_REGISTRY: () = ();
+
+trait Handler {}
+
+impl<F: Fn()> Handler for F {}
+
+fn handler_health() -> HealthStatus {
+    HealthStatus::Healthy
+}
+
+#[traitreg::register(Handler, health_check = handler_health)]
+fn ping() {}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+fn get(name: &str) -> &'static traitreg::RegisteredImplWrapper<Box<dyn Service>> {
+    SERVICE_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn main() {
+    assert!(get("Server").health_check().unwrap().is_healthy());
+    assert!(get("Cache").health_check().unwrap().is_degraded());
+    assert!(get("Database").health_check().unwrap().is_unhealthy());
+    assert_eq!(None, get("Metrics").health_check());
+
+    let report = SERVICE_REGISTRY.health_check_all();
+    let mut names: Vec<_> = report.checks().iter().map(|check| check.name).collect();
+    names.sort();
+    assert_eq!(vec!["Cache", "Database", "Server"], names);
+
+    assert!(!report.is_healthy());
+    assert_eq!(
+        HealthStatus::Unhealthy("connection refused".to_string()),
+        report.status()
+    );
+    assert_eq!(2, report.failing().count());
+    assert!(report
+        .into_iter()
+        .any(|check: &HealthCheck| check.name == "Server" && check.status.is_healthy()));
+}
+
+#[test]
+fn function() {
+    let report = HANDLER_REGISTRY.health_check_all();
+    assert_eq!(1, report.checks().len());
+    assert!(report.is_healthy());
+    assert_eq!(HealthStatus::Healthy, report.status());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize() {
+    let value = serde_json::to_value(SERVICE_REGISTRY.health_check_all()).unwrap();
+    let database = value["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["name"] == "Database")
+        .unwrap();
+    assert_eq!(
+        serde_json::json!({ "Unhealthy": "connection refused" }),
+        database["status"]
+    );
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "hot-reload")]
+
+use std::path::{Path, PathBuf};
+use traitreg::reload::{ReloadError, ReloadableRegistry};
+
+trait Greeter {
+    fn greet(&self) -> String;
+}
+
+static GREETERS: ReloadableRegistry<dyn Greeter> = ReloadableRegistry::new("Greeter");
+
+// Build the plugin in `tests/hot_reload/plugin.rs` as a separate crate, printing `greeting`
+fn build_plugin(dir: &Path, greeting: &str) -> PathBuf {
+    let manifest = format!(
+        r#"[package]
+name = "greeter_plugin"
+version = "0.0.0"
+edition = "2021"
+
+[lib]
+crate-type = ["cdylib"]
+path = "{}/tests/hot_reload/plugin.rs"
+
+[dependencies]
+traitreg = {{ path = "{}", features = ["hot-reload"] }}
+
+[workspace]
+"#,
+        env!("CARGO_MANIFEST_DIR"),
+        env!("CARGO_MANIFEST_DIR"),
+    );
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+    // Use the same dependency versions as the workspace
+    std::fs::copy(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.lock"),
+        dir.join("Cargo.lock"),
+    )
+    .unwrap();
+
+    let status = std::process::Command::new(std::env::var("CARGO").unwrap_or("cargo".into()))
+        .arg("build")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(dir.join("target"))
+        .env("GREETING", greeting)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    dir.join("target").join("debug").join(format!(
+        "{}greeter_plugin{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_SUFFIX
+    ))
+}
+
+#[test]
+fn main() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("hot_reload_plugin");
+    let library = build_plugin(&dir, "hello");
+
+    unsafe { GREETERS.load(&library) }.unwrap();
+    assert_eq!(vec!["Plugin".to_string()], GREETERS.names());
+    assert_eq!(0, unsafe { GREETERS.reload_changed() }.unwrap());
+
+    let old = GREETERS.instantiate("Plugin").unwrap();
+    assert_eq!("hello", old.greet());
+    assert_eq!(library, old.source());
+
+    build_plugin(&dir, "goodbye");
+    assert_eq!(1, unsafe { GREETERS.reload_changed() }.unwrap());
+
+    let instances = GREETERS.instantiate_all();
+    assert_eq!(1, instances.len());
+    assert_eq!("goodbye", instances[0].greet());
+
+    // The old version stays loaded while its instances are alive
+    assert_eq!("hello", old.greet());
+    drop(old);
+
+    assert!(GREETERS.unload(&library));
+    assert!(GREETERS.names().is_empty());
+    assert_eq!("goodbye", instances[0].greet());
+}
+
+#[test]
+fn missing_library() {
+    let registry = ReloadableRegistry::<dyn Greeter>::new("Greeter");
+    let result = unsafe { registry.load("missing/libgreeter_plugin.so") };
+    assert!(matches!(result, Err(ReloadError::Io(_))));
+    assert!(registry.sources().is_empty());
+}
@@ -0,0 +1,15 @@
+pub trait Greeter {
+    fn greet(&self) -> String;
+}
+
+#[derive(Default)]
+struct Plugin;
+
+#[traitreg::register(default)]
+impl Greeter for Plugin {
+    fn greet(&self) -> String {
+        env!("GREETING").to_string()
+    }
+}
+
+traitreg::export_plugin!();
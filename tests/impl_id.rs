@@ -0,0 +1,55 @@
+use traitreg::ImplId;
+
+trait Plugin {}
+
+struct Audio;
+
+#[traitreg::register]
+impl Plugin for Audio {}
+
+struct Video;
+
+#[traitreg::register]
+impl Plugin for Video {}
+
+struct Network;
+
+#[traitreg::register]
+impl Plugin for Network {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+#[traitreg::registry(Plugin)]
+static OTHER_PLUGIN_REGISTRY: () = ();
+
+#[traitreg::registry(Plugin, sorted)]
+static SORTED_PLUGIN_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let ids: Vec<_> = PLUGIN_REGISTRY.iter_with_ids().collect();
+    assert_eq!(3, ids.len());
+
+    for (raw, (id, item)) in ids.into_iter().enumerate() {
+        assert_eq!(raw as u32, id.to_raw());
+        assert_eq!(id, ImplId::from_raw(raw as u32));
+        assert_eq!(Some(item), PLUGIN_REGISTRY.get_by_id(id));
+        assert_eq!(Some(id), PLUGIN_REGISTRY.id_of(item));
+    }
+
+    assert!(PLUGIN_REGISTRY.get_by_id(ImplId::from_raw(3)).is_none());
+
+    // Registries of the same trait and options share implementations and their ids
+    let other = OTHER_PLUGIN_REGISTRY.iter().next().unwrap();
+    assert!(OTHER_PLUGIN_REGISTRY.id_of(other).is_some());
+    assert_eq!(
+        OTHER_PLUGIN_REGISTRY.id_of(other),
+        PLUGIN_REGISTRY.id_of(other)
+    );
+
+    // Otherwise ids are only valid within the registry they are from
+    let sorted = SORTED_PLUGIN_REGISTRY.iter().next().unwrap();
+    assert!(SORTED_PLUGIN_REGISTRY.id_of(sorted).is_some());
+    assert!(PLUGIN_REGISTRY.id_of(sorted).is_none());
+}
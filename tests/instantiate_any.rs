@@ -0,0 +1,52 @@
+trait Codec {
+    fn name(&self) -> String;
+}
+
+#[derive(Default)]
+struct Gzip {
+    level: u32,
+}
+
+#[traitreg::register(default, any)]
+impl Codec for Gzip {
+    fn name(&self) -> String {
+        format!("gzip-{}", self.level)
+    }
+}
+
+#[derive(Default)]
+struct Brotli;
+
+#[traitreg::register(default)]
+impl Codec for Brotli {
+    fn name(&self) -> String {
+        "brotli".to_string()
+    }
+}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let gzip = CODEC_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Gzip")
+        .unwrap();
+    let instance = gzip.instantiate_any().unwrap();
+    assert_eq!(0, instance.downcast_ref::<Gzip>().unwrap().level);
+
+    let mut instance = gzip.instantiate_downcast::<Gzip>().unwrap();
+    instance.level = 9;
+    assert_eq!("gzip-9", instance.name());
+    assert!(gzip.instantiate_downcast::<Brotli>().is_none());
+
+    // Implementations registered without `any` can only be instantiated as the trait object
+    let brotli = CODEC_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Brotli")
+        .unwrap();
+    assert!(brotli.instantiate_any().is_none());
+    assert!(brotli.instantiate_downcast::<Brotli>().is_none());
+    assert!(brotli.instantiate().is_some());
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+
+trait Renderer {
+    fn name(&self) -> &'static str;
+}
+
+struct Gpu;
+
+impl Gpu {
+    fn new() -> Result<Self, &'static str> {
+        Err("no gpu available")
+    }
+}
+
+#[traitreg::register(new, any, priority = 10)]
+impl Renderer for Gpu {
+    fn name(&self) -> &'static str {
+        "gpu"
+    }
+}
+
+#[derive(Debug)]
+struct DriverError;
+
+impl fmt::Display for DriverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("driver not loaded")
+    }
+}
+
+impl std::error::Error for DriverError {}
+
+struct Vulkan;
+
+impl Vulkan {
+    fn new() -> Result<Self, DriverError> {
+        Err(DriverError)
+    }
+}
+
+#[traitreg::register(new, priority = 5)]
+impl Renderer for Vulkan {
+    fn name(&self) -> &'static str {
+        "vulkan"
+    }
+}
+
+struct Metal;
+
+impl Metal {
+    fn new() -> Result<Self, String> {
+        Ok(Self)
+    }
+}
+
+#[traitreg::register(new, any)]
+impl Renderer for Metal {
+    fn name(&self) -> &'static str {
+        "metal"
+    }
+}
+
+#[derive(Default)]
+struct Software;
+
+#[traitreg::register(default, priority = -5)]
+impl Renderer for Software {
+    fn name(&self) -> &'static str {
+        "software"
+    }
+}
+
+#[traitreg::registry(Renderer)]
+static RENDERER_REGISTRY: () = ();
+
+trait Unavailable {}
+
+struct Missing;
+
+impl Missing {
+    fn new() -> Result<Self, &'static str> {
+        Err("missing")
+    }
+}
+
+#[traitreg::register(new)]
+impl Unavailable for Missing {}
+
+#[traitreg::registry(Unavailable)]
+static UNAVAILABLE_REGISTRY: () = ();
+
+fn get(name: &str) -> &'static traitreg::RegisteredImplWrapper<Box<dyn Renderer>> {
+    RENDERER_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        "metal",
+        RENDERER_REGISTRY.instantiate_first_ok().unwrap().name()
+    );
+    assert!(UNAVAILABLE_REGISTRY.instantiate_first_ok().is_none());
+}
+
+#[test]
+fn failed_constructor() {
+    assert!(get("Gpu").has_constructor());
+    assert!(get("Gpu").instantiate().is_none());
+    assert!(get("Gpu").instantiate_any().is_none());
+    assert!(get("Vulkan").instantiate().is_none());
+
+    assert_eq!("metal", get("Metal").instantiate().unwrap().name());
+    assert!(get("Metal").instantiate_downcast::<Metal>().is_some());
+
+    let mut names: Vec<_> = RENDERER_REGISTRY
+        .instantiate_all()
+        .map(|renderer| renderer.name())
+        .collect();
+    names.sort();
+    assert_eq!(vec!["metal", "software"], names);
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn failed_constructor_in_arena() {
+    let bump = bumpalo::Bump::new();
+    assert!(get("Gpu").instantiate_in(&bump).is_none());
+    assert_eq!("metal", get("Metal").instantiate_in(&bump).unwrap().name());
+}
+
+#[cfg(feature = "di")]
+#[test]
+fn failed_constructor_resolved() {
+    use traitreg::di::{Container, ResolveError};
+
+    let mut container = Container::new();
+    container.add_registry(&RENDERER_REGISTRY);
+    container.bind_implementation::<dyn Renderer>("Vulkan");
+
+    let Err(ResolveError::ConstructorFailed { name, message, .. }) =
+        container.resolve::<dyn Renderer>()
+    else {
+        panic!("Expected the constructor to fail");
+    };
+    assert_eq!("Vulkan", name);
+    assert_eq!("driver not loaded", message);
+}
@@ -0,0 +1,133 @@
+// The test binary is also the plugin process, so runs without the test harness which would write
+// to standard output
+
+use traitreg::ipc::{Error, PluginProcess, Service};
+
+trait Plugin: Service {}
+
+#[derive(Default)]
+struct Echo;
+
+impl Service for Echo {
+    fn call(&self, request: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(request.to_vec())
+    }
+}
+
+#[traitreg::register(default)]
+impl Plugin for Echo {}
+
+#[derive(Default)]
+struct Counter(std::sync::atomic::AtomicU8);
+
+impl Service for Counter {
+    fn call(&self, request: &[u8]) -> Result<Vec<u8>, Error> {
+        match request {
+            b"panic" => panic!("requested"),
+            b"fail" => Err(Error::Failed("requested".to_string())),
+            _ => Ok(vec![self
+                .0
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed)]),
+        }
+    }
+}
+
+#[traitreg::register(default)]
+impl Plugin for Counter {}
+
+struct Unconstructed;
+
+impl Service for Unconstructed {
+    fn call(&self, _: &[u8]) -> Result<Vec<u8>, Error> {
+        unreachable!()
+    }
+}
+
+#[traitreg::register]
+impl Plugin for Unconstructed {}
+
+// Implementations with the same name are told apart
+mod first {
+    #[derive(Default)]
+    pub struct Named;
+}
+
+mod second {
+    #[derive(Default)]
+    pub struct Named;
+}
+
+impl Service for first::Named {
+    fn call(&self, _: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(b"first".to_vec())
+    }
+}
+
+#[traitreg::register(default)]
+impl Plugin for first::Named {}
+
+impl Service for second::Named {
+    fn call(&self, _: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(b"second".to_vec())
+    }
+}
+
+#[traitreg::register(default)]
+impl Plugin for second::Named {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+fn main() {
+    if std::env::var_os("TRAITREG_IPC_CHILD").is_some() {
+        std::panic::set_hook(Box::new(|_| {}));
+        traitreg::ipc::serve(&PLUGIN_REGISTRY).unwrap();
+        return;
+    }
+
+    let mut command = std::process::Command::new(std::env::current_exe().unwrap());
+    command.env("TRAITREG_IPC_CHILD", "1");
+    let plugins = PluginProcess::spawn(command).unwrap();
+
+    let mut names: Vec<_> = plugins.iter().map(|entry| entry.name()).collect();
+    names.sort();
+    assert_eq!(
+        vec!["Counter", "Echo", "Named", "Named", "Unconstructed"],
+        names
+    );
+    assert!(plugins.iter().all(|entry| entry.trait_name() == "Plugin"));
+
+    let entry = |name| plugins.iter().find(|entry| entry.name() == name).unwrap();
+
+    let echo = entry("Echo").instantiate().unwrap();
+    assert_eq!(b"hello".to_vec(), echo.call(b"hello").unwrap());
+
+    // Instances keep their state in the plugin process
+    let first = entry("Counter").instantiate().unwrap();
+    let second = entry("Counter").instantiate().unwrap();
+    assert_eq!(vec![0], first.call(b"").unwrap());
+    assert_eq!(vec![1], first.call(b"").unwrap());
+    assert_eq!(vec![0], second.call(b"").unwrap());
+
+    let error = first.call(b"fail").unwrap_err();
+    assert!(matches!(&error, Error::Failed(message) if message == "requested"));
+    let error = first.call(b"panic").unwrap_err();
+    assert!(matches!(&error, Error::Failed(message) if message == "Plugin panicked: requested"));
+    assert_eq!(vec![2], first.call(b"").unwrap());
+
+    for entry in plugins.iter().filter(|entry| entry.name() == "Named") {
+        let expected = entry.path().split("::").next().unwrap().trim().as_bytes();
+        assert_eq!(expected, entry.instantiate().unwrap().call(b"").unwrap());
+    }
+
+    assert!(!entry("Unconstructed").has_constructor());
+    assert!(entry("Unconstructed").instantiate().is_err());
+
+    drop(plugins);
+    assert_eq!(
+        b"still running".to_vec(),
+        echo.call(b"still running").unwrap()
+    );
+
+    println!("test main ... ok");
+}
@@ -0,0 +1,34 @@
+trait Codec {}
+
+struct Gzip;
+
+#[traitreg::register]
+impl Codec for Gzip {}
+
+struct Zstd;
+
+#[traitreg::register]
+impl Codec for Zstd {}
+
+mod other {
+    pub struct Zstd;
+}
+
+#[traitreg::register]
+impl Codec for other::Zstd {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let names: Vec<_> = CODEC_REGISTRY.iter_names().collect();
+    let expected: Vec<_> = CODEC_REGISTRY.iter().map(|item| item.name()).collect();
+    assert_eq!(expected, names);
+    assert_eq!(3, CODEC_REGISTRY.iter_names().len());
+
+    let paths: Vec<&'static str> = CODEC_REGISTRY.iter_paths().collect();
+    let expected: Vec<_> = CODEC_REGISTRY.iter().map(|item| item.path()).collect();
+    assert_eq!(expected, paths);
+    assert!(paths.iter().any(|path| path.starts_with("other")));
+}
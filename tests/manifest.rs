@@ -0,0 +1,56 @@
+trait Codec {}
+
+struct Gzip;
+
+#[traitreg::register]
+impl Codec for Gzip {}
+
+mod other {
+    #[derive(Default)]
+    pub struct Block<const N: usize>;
+
+    #[traitreg::register(default)]
+    impl super::Codec for Block<16> {}
+}
+
+#[test]
+fn main() {
+    let data = std::fs::read(std::env::current_exe().unwrap()).unwrap();
+    let entries: Vec<_> = traitreg::manifest::entries(&data)
+        .filter(|entry| entry.trait_name == "Codec")
+        .collect();
+    assert_eq!(2, entries.len());
+
+    let gzip = entries.iter().find(|entry| entry.name == "Gzip").unwrap();
+    assert_eq!("Gzip", gzip.path);
+    assert_eq!("manifest", gzip.module_path);
+    assert_eq!("traitreg", gzip.crate_name);
+    assert_eq!(env!("CARGO_PKG_VERSION"), gzip.crate_version);
+    assert_eq!(Some(false), gzip.has_constructor);
+
+    let block = entries
+        .iter()
+        .find(|entry| entry.name == "Block<16>")
+        .unwrap();
+    assert_eq!("manifest::other", block.module_path);
+    assert_eq!(Some(true), block.has_constructor);
+}
+
+#[test]
+fn parse() {
+    let data = b"\0\0traitreg-manifest\x1fA\x1fa::A\x1fT\x1fa\x1fc\x1f1.0\x1e\0traitreg-manifest\x1fbroken\x1e\
+        traitreg-manifest\x1fB\x1fB\x1fT\x1fb\x1fc\x1f2.0\x1e";
+    let names: Vec<_> = traitreg::manifest::entries(data)
+        .map(|entry| (entry.name, entry.crate_version))
+        .collect();
+    assert_eq!(vec![("A", "1.0"), ("B", "2.0")], names);
+
+    // Records from before `has_constructor` was recorded are still read
+    let data = b"traitreg-manifest\x1fA\x1fA\x1fT\x1fa\x1fc\x1f1.0\x1f1\x1e\
+        traitreg-manifest\x1fB\x1fB\x1fT\x1fb\x1fc\x1f2.0\x1e\
+        traitreg-manifest\x1fC\x1fC\x1fT\x1fc\x1fc\x1f3.0\x1fyes\x1e";
+    let constructors: Vec<_> = traitreg::manifest::entries(data)
+        .map(|entry| (entry.name, entry.has_constructor))
+        .collect();
+    assert_eq!(vec![("A", Some(true)), ("B", None)], constructors);
+}
@@ -0,0 +1,59 @@
+trait MyTrait {
+    fn generic<T>(&self, value: T) -> T;
+}
+
+struct MyStruct;
+
+#[traitreg::register(meta_only)]
+impl MyTrait for MyStruct {
+    fn generic<T>(&self, value: T) -> T {
+        value
+    }
+}
+
+enum MyEnum {
+    MyEnumVariant,
+}
+
+#[traitreg::register(meta_only)]
+impl MyTrait for MyEnum {
+    fn generic<T>(&self, value: T) -> T {
+        value
+    }
+}
+
+#[traitreg::registry(MyTrait, meta_only)]
+static MYTRAIT_REGISTRY: () = ();
+
+trait MyOtherTrait {}
+
+#[derive(Default)]
+struct MyOtherStruct;
+
+#[traitreg::register(default)]
+impl MyOtherTrait for MyOtherStruct {}
+
+#[traitreg::register(meta_only)]
+impl MyOtherTrait for MyStruct {}
+
+#[traitreg::registry(MyOtherTrait)]
+static MYOTHERTRAIT_REGISTRY: () = ();
+
+#[traitreg::registry(MyOtherTrait, meta_only)]
+static MYOTHERTRAIT_META_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(1, MyStruct.generic(1));
+    assert_eq!(2, MyEnum::MyEnumVariant.generic(2));
+
+    assert_eq!(2, MYTRAIT_REGISTRY.iter().count());
+    assert_eq!(0, MYTRAIT_REGISTRY.instantiate_all().count());
+    assert!(MYTRAIT_REGISTRY.iter().all(|item| item.instantiate().is_none()));
+
+    assert_eq!(2, MYOTHERTRAIT_REGISTRY.iter().count());
+    assert_eq!(1, MYOTHERTRAIT_REGISTRY.instantiate_all().count());
+
+    assert_eq!(2, MYOTHERTRAIT_META_REGISTRY.iter().count());
+    assert_eq!(0, MYOTHERTRAIT_META_REGISTRY.instantiate_all().count());
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "metrics")]
+
+trait Codec {}
+
+#[derive(Default)]
+struct Gzip;
+
+#[traitreg::register(default)]
+impl Codec for Gzip {}
+
+#[derive(Default)]
+struct Brotli;
+
+#[traitreg::register(default)]
+impl Codec for Brotli {}
+
+struct Unused;
+
+#[traitreg::register]
+impl Codec for Unused {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+#[traitreg::registry(dyn Codec + Send)]
+static SEND_CODEC_REGISTRY: () = ();
+
+fn count(name: &str) -> usize {
+    CODEC_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+        .instantiation_count()
+}
+
+#[test]
+fn main() {
+    let gzip = CODEC_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Gzip")
+        .unwrap();
+    gzip.instantiate();
+    gzip.instantiate();
+    assert_eq!(2, count("Gzip"));
+
+    // Counts are shared between registries
+    let _ = SEND_CODEC_REGISTRY.instantiate_all().count();
+    assert_eq!(3, count("Gzip"));
+    assert_eq!(1, count("Brotli"));
+
+    // Types without a constructor are never instantiated
+    assert!(CODEC_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Unused")
+        .unwrap()
+        .instantiate()
+        .is_none());
+    assert_eq!(0, count("Unused"));
+
+    let counts: Vec<_> = traitreg::manifest::instantiation_counts()
+        .into_iter()
+        .filter(|count| count.trait_name == "Codec")
+        .map(|count| (count.name, count.count))
+        .collect();
+    assert_eq!(3, counts.len());
+    assert!(counts.contains(&("Gzip", 3)));
+    assert!(counts.contains(&("Brotli", 1)));
+    assert!(counts.contains(&("Unused", 0)));
+}
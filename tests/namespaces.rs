@@ -0,0 +1,88 @@
+mod imagecodecs {
+    pub trait Plugin {
+        fn extension(&self) -> &'static str;
+    }
+
+    #[derive(Default)]
+    pub struct Png;
+
+    #[traitreg::register(default, namespace = "imagecodecs")]
+    impl Plugin for Png {
+        fn extension(&self) -> &'static str {
+            "png"
+        }
+    }
+
+    #[traitreg::registry(Plugin, namespace = "imagecodecs")]
+    pub static PLUGIN_REGISTRY: () = ();
+}
+
+mod audio {
+    pub trait Plugin {
+        fn channels(&self) -> u32;
+    }
+
+    #[derive(Default)]
+    pub struct Stereo;
+
+    #[traitreg::register(default, namespace = "audio")]
+    impl Plugin for Stereo {
+        fn channels(&self) -> u32 {
+            2
+        }
+    }
+
+    #[derive(Default)]
+    pub struct Mono;
+
+    #[traitreg::register(default, namespace = "audio")]
+    impl Plugin for Mono {
+        fn channels(&self) -> u32 {
+            1
+        }
+    }
+
+    #[traitreg::registry(Plugin, namespace = "audio", sorted)]
+    pub static PLUGIN_REGISTRY: () = ();
+}
+
+trait Plugin {}
+
+struct Global;
+
+#[traitreg::register]
+impl Plugin for Global {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let extensions: Vec<_> = imagecodecs::PLUGIN_REGISTRY
+        .instantiate_all()
+        .map(|plugin| plugin.extension())
+        .collect();
+    assert_eq!(vec!["png"], extensions);
+
+    let channels: Vec<_> = audio::PLUGIN_REGISTRY
+        .instantiate_all()
+        .map(|plugin| plugin.channels())
+        .collect();
+    assert_eq!(vec![1, 2], channels);
+    assert_eq!(
+        Some("audio"),
+        audio::PLUGIN_REGISTRY.iter().next().unwrap().namespace()
+    );
+
+    assert_eq!(
+        vec!["Global"],
+        PLUGIN_REGISTRY.iter_names().collect::<Vec<_>>()
+    );
+    assert_eq!(None, PLUGIN_REGISTRY.iter().next().unwrap().namespace());
+
+    // Registrations in a namespace are claimed by the registry of that namespace
+    assert!(traitreg::unclaimed_registrations().is_empty());
+
+    let dynamic = traitreg::dynamic_registry("Plugin");
+    assert_eq!(vec!["Global"], dynamic.iter_names().collect::<Vec<_>>());
+}
@@ -0,0 +1,55 @@
+use std::sync::Mutex;
+
+static FIRST: Mutex<Vec<&str>> = Mutex::new(vec![]);
+static SECOND: Mutex<Vec<&str>> = Mutex::new(vec![]);
+
+trait Plugin {}
+
+struct Audio;
+
+#[traitreg::register]
+impl Plugin for Audio {}
+
+#[derive(Default)]
+struct Video;
+
+#[traitreg::register(default)]
+impl Plugin for Video {}
+
+trait Codec {}
+
+struct Zstd;
+
+#[traitreg::register]
+impl Codec for Zstd {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+fn record(
+    seen: &'static Mutex<Vec<&'static str>>,
+) -> impl Fn(&traitreg::RegisteredImplWrapper<traitreg::MetaOnly>) {
+    move |item| {
+        // Hooks can build registries
+        assert!(!traitreg::dynamic_registry(item.trait_name()).is_empty());
+        seen.lock().unwrap().push(item.name());
+    }
+}
+
+// Hooks are global, so everything is checked in one test
+#[test]
+fn main() {
+    traitreg::on_register(record(&FIRST));
+    let mut first = FIRST.lock().unwrap().clone();
+    first.sort();
+    assert_eq!(vec!["Audio", "Video", "Zstd"], first);
+
+    // Hooks installed later are also called once for every registration made so far
+    traitreg::on_register(record(&SECOND));
+    let mut second = SECOND.lock().unwrap().clone();
+    second.sort();
+    assert_eq!(first, second);
+    assert_eq!(3, FIRST.lock().unwrap().len());
+
+    assert_eq!(2, PLUGIN_REGISTRY.len());
+}
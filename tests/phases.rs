@@ -0,0 +1,108 @@
+use std::sync::Mutex;
+
+static CONSTRUCTED: Mutex<Vec<&str>> = Mutex::new(vec![]);
+
+trait Plugin {
+    fn name(&self) -> &'static str;
+}
+
+macro_rules! plugin {
+    ($name:ident, $($option:tt)*) => {
+        struct $name;
+
+        impl $name {
+            fn new() -> Self {
+                CONSTRUCTED.lock().unwrap().push(stringify!($name));
+                Self
+            }
+        }
+
+        #[traitreg::register(new, $($option)*)]
+        impl Plugin for $name {
+            fn name(&self) -> &'static str {
+                stringify!($name)
+            }
+        }
+    };
+}
+
+plugin!(Search, phase = extensions);
+plugin!(Database, phase = core);
+plugin!(Logging, phase = core);
+plugin!(Standalone,);
+
+struct Unconstructed;
+
+#[traitreg::register(phase = extensions)]
+impl Plugin for Unconstructed {
+    fn name(&self) -> &'static str {
+        "Unconstructed"
+    }
+}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+trait Handler {}
+
+impl<F: Fn()> Handler for F {}
+
+#[traitreg::register(Handler, phase = core)]
+fn ping() {}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+fn names<'a>(
+    items: impl Iterator<Item = &'a traitreg::RegisteredImplWrapper<Box<dyn Plugin>>>,
+) -> Vec<&'static str> {
+    let mut names: Vec<_> = items.map(|item| item.name()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        vec!["Database", "Logging"],
+        names(PLUGIN_REGISTRY.iter_phase("core"))
+    );
+    assert_eq!(
+        vec!["Search", "Unconstructed"],
+        names(PLUGIN_REGISTRY.query().phase("extensions").iter())
+    );
+    assert_eq!(
+        None,
+        PLUGIN_REGISTRY
+            .iter()
+            .find(|item| item.name() == "Standalone")
+            .unwrap()
+            .phase()
+    );
+
+    let mut phases = PLUGIN_REGISTRY.instantiate_phases(&["core", "extensions"]);
+    // Nothing is constructed until a phase is reached
+    assert!(CONSTRUCTED.lock().unwrap().is_empty());
+
+    let (phase, core) = phases.next().unwrap();
+    assert_eq!("core", phase);
+    let mut core: Vec<_> = core.iter().map(|plugin| plugin.name()).collect();
+    core.sort();
+    assert_eq!(vec!["Database", "Logging"], core);
+    assert_eq!(2, CONSTRUCTED.lock().unwrap().len());
+
+    let (phase, extensions) = phases.next().unwrap();
+    assert_eq!("extensions", phase);
+    assert_eq!(1, extensions.len());
+    assert_eq!(vec!["Database", "Logging", "Search"], {
+        let mut constructed = CONSTRUCTED.lock().unwrap().clone();
+        constructed[..2].sort();
+        constructed
+    });
+    assert!(phases.next().is_none());
+
+    assert_eq!(
+        Some("core"),
+        HANDLER_REGISTRY.iter().next().unwrap().phase()
+    );
+}
@@ -0,0 +1,104 @@
+use traitreg::pipeline::{Middleware, Next, Pipeline};
+
+struct Request {
+    path: String,
+    log: Vec<&'static str>,
+}
+
+trait Handler: Middleware<Request, Result<String, u16>> {}
+
+#[derive(Default)]
+struct Logging;
+
+impl Middleware<Request, Result<String, u16>> for Logging {
+    fn handle(
+        &self,
+        mut request: Request,
+        next: Next<'_, Request, Result<String, u16>>,
+    ) -> Result<String, u16> {
+        request.log.push("logging");
+        next.run(request).map(|body| format!("[logged] {body}"))
+    }
+}
+
+#[traitreg::register(default, priority = 100)]
+impl Handler for Logging {}
+
+#[derive(Default)]
+struct Auth;
+
+impl Middleware<Request, Result<String, u16>> for Auth {
+    fn handle(
+        &self,
+        mut request: Request,
+        next: Next<'_, Request, Result<String, u16>>,
+    ) -> Result<String, u16> {
+        request.log.push("auth");
+        if request.path.starts_with("/admin") {
+            return Err(403);
+        }
+        next.run(request)
+    }
+}
+
+const AUTH_PRIORITY: i32 = 50;
+
+#[traitreg::register(default, priority = AUTH_PRIORITY)]
+impl Handler for Auth {}
+
+#[derive(Default)]
+struct Compression;
+
+impl Middleware<Request, Result<String, u16>> for Compression {
+    fn handle(
+        &self,
+        mut request: Request,
+        next: Next<'_, Request, Result<String, u16>>,
+    ) -> Result<String, u16> {
+        request.log.push("compression");
+        next.run(request)
+    }
+}
+
+#[traitreg::register(default, priority = -1)]
+impl Handler for Compression {}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let priorities: Vec<i32> = {
+        let mut priorities: Vec<i32> = HANDLER_REGISTRY
+            .iter()
+            .map(|item| item.priority())
+            .collect();
+        priorities.sort();
+        priorities
+    };
+    assert_eq!(vec![-1, 50, 100], priorities);
+
+    let pipeline = Pipeline::new(&HANDLER_REGISTRY);
+    assert_eq!(3, pipeline.middleware().len());
+
+    let response = pipeline.handle(
+        Request {
+            path: "/index".to_string(),
+            log: vec![],
+        },
+        |request| Ok(format!("{} via {}", request.path, request.log.join(", "))),
+    );
+    assert_eq!(
+        Ok("[logged] /index via logging, auth, compression".to_string()),
+        response
+    );
+
+    let response = pipeline.handle(
+        Request {
+            path: "/admin".to_string(),
+            log: vec![],
+        },
+        |_| Ok(String::new()),
+    );
+    assert_eq!(Err(403), response);
+}
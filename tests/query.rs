@@ -0,0 +1,97 @@
+trait Codec {}
+
+#[derive(Default)]
+struct Gzip;
+
+#[traitreg::register(default, tags = ["stable"], capabilities = ["streaming"])]
+impl Codec for Gzip {}
+
+#[derive(Default)]
+struct Brotli;
+
+#[traitreg::register(default, priority = 10, tags = ["stable", "fast"])]
+impl Codec for Brotli {}
+
+struct Zstd;
+
+#[traitreg::register(tags = ["stable"])]
+impl Codec for Zstd {}
+
+mod experimental {
+    #[derive(Default)]
+    pub struct Lz4;
+
+    #[traitreg::register(default, tags = ["experimental"])]
+    impl super::Codec for Lz4 {}
+}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+fn names(query: traitreg::query::Query<'_, Box<dyn Codec>>) -> Vec<&'static str> {
+    let mut names: Vec<_> = query.iter().map(|item| item.name()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn main() {
+    assert_eq!(4, CODEC_REGISTRY.query().count());
+    assert_eq!(
+        vec!["Brotli", "Gzip"],
+        names(
+            CODEC_REGISTRY
+                .query()
+                .crate_("traitreg")
+                .tag("stable")
+                .with_constructor()
+        )
+    );
+    assert_eq!(vec!["Brotli"], names(CODEC_REGISTRY.query().tag("fast")));
+    assert_eq!(
+        vec!["Brotli"],
+        names(CODEC_REGISTRY.query().min_priority(1))
+    );
+    assert_eq!(
+        vec!["Gzip"],
+        names(CODEC_REGISTRY.query().capability("streaming"))
+    );
+    assert_eq!(
+        vec!["Lz4"],
+        names(CODEC_REGISTRY.query().module("query::experimental"))
+    );
+    assert_eq!(
+        Vec::<&str>::new(),
+        names(CODEC_REGISTRY.query().module("query::exp"))
+    );
+    assert_eq!(vec!["Zstd"], names(CODEC_REGISTRY.query().name("Zstd")));
+    assert!(CODEC_REGISTRY.query().crate_("other").first().is_none());
+    assert_eq!(
+        4,
+        CODEC_REGISTRY
+            .query()
+            .crate_version(env!("CARGO_PKG_VERSION"))
+            .count()
+    );
+    assert_eq!(
+        3,
+        CODEC_REGISTRY
+            .query()
+            .with_constructor()
+            .instantiate_all()
+            .count()
+    );
+    assert_eq!(
+        vec!["Gzip", "Zstd"],
+        names(
+            CODEC_REGISTRY
+                .query()
+                .filter(|item| item.priority() == 0)
+                .tag("stable")
+        )
+    );
+
+    let gzip = CODEC_REGISTRY.query().name("Gzip").first().unwrap();
+    assert_eq!(&["stable"], gzip.tags());
+    assert_eq!("traitreg", gzip.crate_name());
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "rayon")]
+
+use rayon::iter::ParallelIterator;
+
+trait Shader {
+    fn name(&self) -> String;
+}
+
+macro_rules! shaders {
+    ($($shader:ident),*) => {
+        $(
+            #[derive(Default)]
+            struct $shader;
+
+            #[traitreg::register(default)]
+            impl Shader for $shader {
+                fn name(&self) -> String {
+                    stringify!($shader).to_string()
+                }
+            }
+        )*
+    };
+}
+
+shaders!(Blur, Bloom, Fog, Grain, Outline, Sharpen, Tonemap, Vignette);
+
+struct Missing;
+
+impl Missing {
+    fn new() -> Result<Self, &'static str> {
+        Err("not compiled")
+    }
+}
+
+#[traitreg::register(new)]
+impl Shader for Missing {
+    fn name(&self) -> String {
+        "Missing".to_string()
+    }
+}
+
+#[traitreg::registry(Shader, send)]
+static SHADER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let shaders: Vec<String> = SHADER_REGISTRY
+        .par_instantiate_all()
+        .map(|shader| shader.name())
+        .collect();
+    let expected: Vec<String> = SHADER_REGISTRY
+        .instantiate_all()
+        .map(|shader| shader.name())
+        .collect();
+    assert_eq!(8, shaders.len());
+    assert_eq!(expected, shaders);
+}
@@ -0,0 +1,23 @@
+trait MyTrait {}
+
+struct MyStruct;
+
+#[traitreg::register]
+#[cfg(test)]
+impl MyTrait for MyStruct {}
+
+#[cfg(not(test))]
+struct MyOtherStruct;
+
+#[traitreg::register]
+#[cfg(not(test))]
+impl MyTrait for MyOtherStruct {}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(1, MYTRAIT_REGISTRY.iter().count());
+    assert_eq!("MyStruct", MYTRAIT_REGISTRY.iter().next().unwrap().name());
+}
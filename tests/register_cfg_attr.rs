@@ -0,0 +1,94 @@
+trait Plugin {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Default)]
+struct Enabled;
+
+#[cfg_attr(test, traitreg::register(default))]
+impl Plugin for Enabled {
+    fn name(&self) -> &'static str {
+        "enabled"
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Default)]
+struct Disabled;
+
+#[cfg_attr(not(test), traitreg::register(default))]
+impl Plugin for Disabled {
+    fn name(&self) -> &'static str {
+        "disabled"
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Default)]
+struct Optional;
+
+#[cfg_attr(feature = "di", traitreg::register(default, priority = 1))]
+impl Plugin for Optional {
+    fn name(&self) -> &'static str {
+        "optional"
+    }
+}
+
+#[cfg(test)]
+#[derive(Default)]
+struct Nested;
+
+// The registration is removed along with the impl by the cfg inside the other cfg_attr
+#[traitreg::register(default)]
+#[cfg_attr(all(), cfg(test))]
+impl Plugin for Nested {
+    fn name(&self) -> &'static str {
+        "nested"
+    }
+}
+
+#[cfg(not(test))]
+#[derive(Default)]
+struct NestedDisabled;
+
+#[traitreg::register(default)]
+#[cfg_attr(all(), cfg(not(test)))]
+impl Plugin for NestedDisabled {
+    fn name(&self) -> &'static str {
+        "nested disabled"
+    }
+}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+trait Handler {}
+
+impl<F: Fn()> Handler for F {}
+
+#[cfg_attr(test, traitreg::register(Handler))]
+fn enabled() {}
+
+#[cfg_attr(not(test), traitreg::register(Handler))]
+#[allow(dead_code)]
+fn disabled() {}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let mut names: Vec<_> = PLUGIN_REGISTRY
+        .instantiate_all()
+        .map(|plugin| plugin.name())
+        .collect();
+    names.sort();
+
+    #[cfg(feature = "di")]
+    assert_eq!(vec!["enabled", "nested", "optional"], names);
+    #[cfg(not(feature = "di"))]
+    assert_eq!(vec!["enabled", "nested"], names);
+
+    assert_eq!(1, HANDLER_REGISTRY.len());
+    assert_eq!("enabled", HANDLER_REGISTRY.iter().next().unwrap().name());
+}
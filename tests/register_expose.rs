@@ -0,0 +1,56 @@
+trait Plugin {}
+
+trait Versioned {
+    const VERSION: (u32, u32);
+}
+
+#[derive(Default)]
+struct Audio;
+
+impl Audio {
+    const NAME: &'static str = "audio";
+    const CHANNELS: u8 = 2;
+}
+
+impl Versioned for Audio {
+    const VERSION: (u32, u32) = (1, 4);
+}
+
+#[traitreg::register(default, expose(NAME, VERSION, CHANNELS))]
+impl Plugin for Audio {}
+
+struct Video;
+
+#[traitreg::register]
+impl Plugin for Video {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+fn get(name: &str) -> &'static traitreg::RegisteredImplWrapper<Box<dyn Plugin>> {
+    PLUGIN_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn main() {
+    let audio = get("Audio");
+    assert_eq!(Some(&"audio"), audio.exposed::<&str>("NAME"));
+    assert_eq!(Some(&(1, 4)), audio.exposed::<(u32, u32)>("VERSION"));
+    assert_eq!(Some(&2), audio.exposed::<u8>("CHANNELS"));
+    assert_eq!(None, audio.exposed::<u32>("CHANNELS"));
+    assert_eq!(None, audio.exposed::<&str>("MISSING"));
+
+    let values: Vec<_> = audio
+        .exposed_values()
+        .map(|(name, value)| format!("{name}={value:?}"))
+        .collect();
+    assert_eq!(
+        vec!["NAME=\"audio\"", "VERSION=(1, 4)", "CHANNELS=2"],
+        values
+    );
+
+    assert_eq!(0, get("Video").exposed_values().count());
+}
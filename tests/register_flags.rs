@@ -0,0 +1,63 @@
+trait Codec {}
+
+trait Describe {
+    const LOSSLESS: bool;
+}
+
+struct Gzip;
+
+impl Gzip {
+    const SUPPORTS_STREAMING: bool = true;
+}
+
+impl Describe for Gzip {
+    const LOSSLESS: bool = true;
+}
+
+#[traitreg::register(flags(SUPPORTS_STREAMING, LOSSLESS))]
+impl Codec for Gzip {}
+
+struct Jpeg;
+
+impl Jpeg {
+    const SUPPORTS_STREAMING: bool = false;
+}
+
+impl Describe for Jpeg {
+    const LOSSLESS: bool = false;
+}
+
+#[traitreg::register(flags(SUPPORTS_STREAMING, LOSSLESS,))]
+impl Codec for Jpeg {}
+
+struct Raw;
+
+#[traitreg::register]
+impl Codec for Raw {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+fn get(name: &str) -> &'static traitreg::RegisteredImplWrapper<Box<dyn Codec>> {
+    CODEC_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn main() {
+    assert_eq!(Some(true), get("Gzip").flag("SUPPORTS_STREAMING"));
+    assert_eq!(Some(true), get("Gzip").flag("LOSSLESS"));
+    assert_eq!(None, get("Gzip").flag("UNKNOWN"));
+    assert_eq!(
+        &[("SUPPORTS_STREAMING", false), ("LOSSLESS", false)],
+        get("Jpeg").flags()
+    );
+    assert_eq!(None, get("Raw").flag("SUPPORTS_STREAMING"));
+    assert!(get("Raw").flags().is_empty());
+
+    let streaming = CODEC_REGISTRY.query().flag("SUPPORTS_STREAMING").collect();
+    assert_eq!(1, streaming.len());
+    assert_eq!("Gzip", streaming[0].name());
+}
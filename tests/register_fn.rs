@@ -0,0 +1,68 @@
+pub struct Request(&'static str);
+pub struct Response(String);
+
+trait Handler {
+    fn handle(&self, request: Request) -> Response;
+}
+
+impl<F: Fn(Request) -> Response> Handler for F {
+    fn handle(&self, request: Request) -> Response {
+        self(request)
+    }
+}
+
+#[traitreg::register(Handler, priority = 1, tags = ["greeting"])]
+fn hello(request: Request) -> Response {
+    Response(format!("hello {}", request.0))
+}
+
+mod handlers {
+    use super::{Request, Response};
+
+    #[traitreg::register(crate::Handler)]
+    pub fn goodbye(request: Request) -> Response {
+        Response(format!("goodbye {}", request.0))
+    }
+}
+
+fn never() -> bool {
+    false
+}
+
+#[traitreg::register(Handler, guard = never)]
+fn unregistered(request: Request) -> Response {
+    Response(request.0.to_string())
+}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    // The functions can still be called directly
+    assert_eq!("hello world", hello(Request("world")).0);
+    assert_eq!("world", unregistered(Request("world")).0);
+
+    let responses: Vec<_> = HANDLER_REGISTRY
+        .iter_by_priority()
+        .map(|item| item.instantiate().unwrap().handle(Request("world")).0)
+        .collect();
+    assert_eq!(vec!["hello world", "goodbye world"], responses);
+
+    let item = HANDLER_REGISTRY
+        .iter()
+        .find(|item| item.name() == "hello")
+        .unwrap();
+    assert_eq!("hello", item.path());
+    assert_eq!("Handler", item.trait_name());
+    assert_eq!(&["greeting"], item.tags());
+    assert!(item.has_constructor());
+
+    // Functions with the same signature are different implementations
+    let goodbye = HANDLER_REGISTRY
+        .iter()
+        .find(|item| item.name() == "goodbye")
+        .unwrap();
+    assert_ne!(item, goodbye);
+    assert_eq!("register_fn::handlers", goodbye.module_path());
+}
@@ -0,0 +1,29 @@
+trait MyTrait {}
+
+fn is_supported() -> bool {
+    true
+}
+
+fn is_not_supported() -> bool {
+    false
+}
+
+#[derive(Default)]
+struct MyStruct;
+
+#[traitreg::register(default, guard = is_supported)]
+impl MyTrait for MyStruct {}
+
+struct MyOtherStruct;
+
+#[traitreg::register(guard = is_not_supported)]
+impl MyTrait for MyOtherStruct {}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(1, MYTRAIT_REGISTRY.iter().count());
+    assert_eq!("MyStruct", MYTRAIT_REGISTRY.iter().next().unwrap().name());
+}
@@ -0,0 +1,38 @@
+trait MyTrait {
+    fn foo(&self) -> u32;
+}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    #[derive(Default)]
+    struct MyStruct;
+
+    #[traitreg::register(default)]
+    impl MyTrait for MyStruct {
+        fn foo(&self) -> u32 {
+            123
+        }
+    }
+
+    assert_eq!(1, MYTRAIT_REGISTRY.iter().count());
+    assert_eq!(123, MYTRAIT_REGISTRY.instantiate_all().next().unwrap().foo());
+}
+
+#[test]
+fn registry_in_fn() {
+    trait MyOtherTrait {}
+
+    #[derive(Default)]
+    struct MyOtherStruct;
+
+    #[traitreg::register(default)]
+    impl MyOtherTrait for MyOtherStruct {}
+
+    #[traitreg::registry(MyOtherTrait)]
+    static MYOTHERTRAIT_REGISTRY: () = ();
+
+    assert_eq!(1, MYOTHERTRAIT_REGISTRY.instantiate_all().count());
+}
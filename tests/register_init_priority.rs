@@ -0,0 +1,58 @@
+// Constructors are not ordered on apple platforms
+#![cfg(not(target_vendor = "apple"))]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static CONFIG_LOADED: AtomicBool = AtomicBool::new(false);
+
+trait Plugin {}
+
+// Registered without an init_priority, so after `Config` regardless of the order in the source
+struct Database;
+
+fn config_loaded() -> bool {
+    CONFIG_LOADED.load(Ordering::Relaxed)
+}
+
+#[traitreg::register(guard = config_loaded)]
+impl Plugin for Database {}
+
+struct Late;
+
+#[traitreg::register(init_priority = 19999)]
+impl Plugin for Late {}
+
+struct Config;
+
+fn load_config() -> bool {
+    CONFIG_LOADED.store(true, Ordering::Relaxed);
+    true
+}
+
+#[traitreg::register(guard = load_config, init_priority = 9000)]
+impl Plugin for Config {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+trait Handler {}
+
+impl<F: Fn()> Handler for F {}
+
+#[traitreg::register(Handler, init_priority = 12000)]
+fn second() {}
+
+#[traitreg::register(Handler, init_priority = 101)]
+fn first() {}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let names: Vec<_> = PLUGIN_REGISTRY.iter().map(|item| item.name()).collect();
+    assert_eq!(vec!["Config", "Database", "Late"], names);
+
+    let names: Vec<_> = HANDLER_REGISTRY.iter().map(|item| item.name()).collect();
+    assert_eq!(vec!["first", "second"], names);
+}
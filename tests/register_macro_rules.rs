@@ -0,0 +1,102 @@
+trait Codec {
+    fn name(&self) -> &'static str;
+}
+
+macro_rules! codec {
+    ($type:ident, $name:literal) => {
+        #[derive(Default)]
+        struct $type;
+
+        #[traitreg::register(default)]
+        impl Codec for $type {
+            fn name(&self) -> &'static str {
+                $name
+            }
+        }
+    };
+}
+
+codec!(Gzip, "gzip");
+codec!(Zstd, "zstd");
+
+macro_rules! codec_for_type {
+    ($type:ty, $name:literal) => {
+        #[traitreg::register(default)]
+        impl Codec for $type {
+            fn name(&self) -> &'static str {
+                $name
+            }
+        }
+    };
+}
+
+#[derive(Default)]
+struct Brotli;
+
+#[derive(Default)]
+struct Lz4<T>(core::marker::PhantomData<T>);
+
+// Identifiers generated from `Lz4<u8>` and `Lz4_u8_` would be the same without the path hash
+#[allow(non_camel_case_types)]
+#[derive(Default)]
+struct Lz4_u8_;
+
+codec_for_type!(Brotli, "brotli");
+codec_for_type!(Lz4<u8>, "lz4");
+codec_for_type!(Lz4_u8_, "lz4 alias");
+
+macro_rules! codec_for_path {
+    ($trait:path, $type:path, $name:literal) => {
+        #[traitreg::register(default)]
+        impl $trait for $type {
+            fn name(&self) -> &'static str {
+                $name
+            }
+        }
+    };
+}
+
+mod snappy {
+    #[derive(Default)]
+    pub struct Snappy;
+}
+
+codec_for_path!(Codec, snappy::Snappy, "snappy");
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+trait Handler {}
+
+impl<F: Fn()> Handler for F {}
+
+macro_rules! handlers {
+    ($($name:ident),*) => {
+        $(
+            #[traitreg::register(Handler)]
+            fn $name() {}
+        )*
+    };
+}
+
+handlers!(r#match, r_match, ping);
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let mut names: Vec<_> = CODEC_REGISTRY
+        .instantiate_all()
+        .map(|codec| codec.name())
+        .collect();
+    names.sort();
+    assert_eq!(
+        vec!["brotli", "gzip", "lz4", "lz4 alias", "snappy", "zstd"],
+        names
+    );
+
+    let mut names: Vec<_> = HANDLER_REGISTRY.iter().map(|item| item.name()).collect();
+    names.sort();
+    assert_eq!(vec!["ping", "r#match", "r_match"], names);
+}
@@ -0,0 +1,69 @@
+mod codecs {
+    pub trait Codec {
+        fn name(&self) -> &'static str;
+    }
+}
+
+use codecs::Codec;
+
+macro_rules! codec {
+    ($($name:ident),*) => {
+        $(
+            struct $name;
+
+            impl $name {
+                fn new() -> Result<Self, &'static str> {
+                    Ok(Self)
+                }
+            }
+
+            impl Codec for $name {
+                fn name(&self) -> &'static str {
+                    stringify!($name)
+                }
+            }
+        )*
+    };
+}
+
+codec!(Gzip, Zstd, Brotli);
+
+traitreg::register_many!(codecs::Codec[new, priority = 2, tags = ["compression"]]: Gzip, Zstd, Brotli,);
+
+trait Plugin {}
+
+struct Audio;
+impl Plugin for Audio {}
+
+struct Wrapper<T>(T);
+impl Plugin for Wrapper<u32> {}
+
+traitreg::register_many!(Plugin: Audio, Wrapper<u32>);
+
+#[traitreg::registry(Codec, sorted)]
+static CODEC_REGISTRY: () = ();
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let names: Vec<_> = CODEC_REGISTRY
+        .instantiate_all()
+        .map(|codec| codec.name())
+        .collect();
+    assert_eq!(vec!["Brotli", "Gzip", "Zstd"], names);
+    assert!(CODEC_REGISTRY
+        .iter()
+        .all(|item| item.priority() == 2 && item.tags() == ["compression"]));
+    assert_eq!(
+        "impl codecs::Codec for Gzip",
+        CODEC_REGISTRY.iter().nth(1).unwrap().signature()
+    );
+
+    assert_eq!(2, PLUGIN_REGISTRY.len());
+    assert!(PLUGIN_REGISTRY.iter().all(|item| !item.has_constructor()));
+    assert!(PLUGIN_REGISTRY
+        .iter()
+        .any(|item| item.name() == "Wrapper<u32>"));
+}
@@ -0,0 +1,35 @@
+trait Job {}
+
+mod first {
+    #[derive(Default)]
+    pub struct Worker;
+}
+
+mod second {
+    #[derive(Default)]
+    pub struct Worker;
+
+    pub trait Job {}
+
+    #[traitreg::register(default)]
+    impl Job for Worker {}
+}
+
+#[traitreg::register(default)]
+impl Job for first::Worker {}
+
+#[traitreg::register(default)]
+impl Job for second::Worker {}
+
+#[traitreg::registry(Job)]
+static JOB_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    // The impls of both traits named Job are included, see the outstanding issues in the README
+    assert_eq!(3, JOB_REGISTRY.iter().count());
+
+    let paths: Vec<&str> = JOB_REGISTRY.iter().map(|item| item.path()).collect();
+    assert!(paths.contains(&"first :: Worker"));
+    assert!(paths.contains(&"second :: Worker"));
+}
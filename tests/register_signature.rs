@@ -0,0 +1,47 @@
+mod codecs {
+    pub trait Codec {}
+}
+
+use codecs::Codec;
+
+struct Gzip;
+
+#[traitreg::register]
+impl Codec for Gzip {}
+
+#[traitreg::register]
+impl codecs::Codec for std::vec::Vec<[u8; 4]> where Vec<[u8; 4]>: Default {}
+
+trait Handler {}
+
+impl<F: Fn(&str) -> Option<String>> Handler for F {}
+
+#[traitreg::register(Handler)]
+fn ping(request: &str) -> Option<String> {
+    Some(request.to_string())
+}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let signature = |name| {
+        CODEC_REGISTRY
+            .iter()
+            .find(|item| item.name() == name)
+            .unwrap()
+            .signature()
+    };
+    assert_eq!("impl Codec for Gzip", signature("Gzip"));
+    assert_eq!(
+        "impl codecs::Codec for std::vec::Vec<[u8; 4]> where Vec<[u8; 4]>: Default",
+        signature("Vec<[u8;4]>")
+    );
+
+    let ping = HANDLER_REGISTRY.iter().next().unwrap();
+    assert_eq!("fn ping(request: &str) -> Option<String>", ping.signature());
+}
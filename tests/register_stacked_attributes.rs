@@ -0,0 +1,95 @@
+trait Plugin {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Default)]
+struct Above;
+
+#[rustversion::since(1.31)]
+#[traitreg::register(default)]
+impl Plugin for Above {
+    fn name(&self) -> &'static str {
+        "above"
+    }
+}
+
+#[derive(Default)]
+struct Below;
+
+#[traitreg::register(default)]
+#[rustversion::since(1.31)]
+#[allow(clippy::all)]
+impl Plugin for Below {
+    fn name(&self) -> &'static str {
+        "below"
+    }
+}
+
+#[allow(dead_code)]
+struct Removed;
+
+// The other attribute removes the impl, so it must not be registered
+#[traitreg::register]
+#[rustversion::before(1.0)]
+impl Plugin for Removed {
+    fn name(&self) -> &'static str {
+        "removed"
+    }
+}
+
+#[derive(Default)]
+struct Inert;
+
+// Inert built-in attributes are left on the item after the deferred register attribute, which
+// must still only be deferred once
+#[traitreg::register(default)]
+#[automatically_derived]
+impl Plugin for Inert {
+    fn name(&self) -> &'static str {
+        "inert"
+    }
+}
+
+#[derive(Default)]
+struct Both;
+
+#[traitreg::register(default)]
+#[rustversion::since(1.31)]
+#[automatically_derived]
+impl Plugin for Both {
+    fn name(&self) -> &'static str {
+        "both"
+    }
+}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+trait Handler {}
+
+impl<F: Fn()> Handler for F {}
+
+#[traitreg::register(Handler)]
+#[rustversion::since(1.31)]
+fn ping() {}
+
+#[traitreg::register(Handler)]
+#[rustversion::before(1.0)]
+fn removed() {}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let mut names: Vec<_> = PLUGIN_REGISTRY
+        .instantiate_all()
+        .map(|plugin| plugin.name())
+        .collect();
+    names.sort();
+    assert_eq!(vec!["above", "below", "both", "inert"], names);
+    assert_eq!(4, PLUGIN_REGISTRY.len());
+
+    assert_eq!(1, HANDLER_REGISTRY.len());
+    assert_eq!("ping", HANDLER_REGISTRY.iter().next().unwrap().name());
+}
@@ -0,0 +1,42 @@
+use std::collections::HashSet;
+
+trait MyTrait {}
+
+#[derive(Default)]
+struct MyStruct;
+
+#[traitreg::register(default)]
+impl MyTrait for MyStruct {}
+
+mod other {
+    pub struct MyStruct;
+
+    #[traitreg::register]
+    impl super::MyTrait for MyStruct {}
+}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
+
+#[traitreg::registry(dyn MyTrait + Send)]
+static MYTRAIT_SEND_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let first: Vec<_> = MYTRAIT_REGISTRY.iter().collect();
+    assert_eq!(2, first.len());
+    assert_ne!(first[0], first[1]);
+
+    let set: HashSet<_> = MYTRAIT_REGISTRY.iter().chain(MYTRAIT_REGISTRY.iter()).collect();
+    assert_eq!(2, set.len());
+
+    for item in MYTRAIT_SEND_REGISTRY.iter() {
+        assert_eq!(
+            1,
+            MYTRAIT_REGISTRY
+                .iter()
+                .filter(|other| *other == item)
+                .count()
+        );
+    }
+}
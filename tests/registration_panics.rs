@@ -0,0 +1,53 @@
+trait Plugin {}
+
+struct Gpu;
+
+fn gpu_available() -> bool {
+    panic!("driver {} not loaded", "vk")
+}
+
+#[traitreg::register(guard = gpu_available)]
+impl Plugin for Gpu {}
+
+struct Software;
+
+#[traitreg::register]
+impl Plugin for Software {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+trait Handler {}
+
+impl<F: Fn()> Handler for F {}
+
+fn never() -> bool {
+    panic!("guard panicked")
+}
+
+#[traitreg::register(Handler, guard = never)]
+fn ping() {}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(1, PLUGIN_REGISTRY.len());
+    assert_eq!("Software", PLUGIN_REGISTRY.iter().next().unwrap().name());
+    assert!(HANDLER_REGISTRY.is_empty());
+
+    let mut panics = traitreg::registration_panics();
+    panics.sort_by_key(|panic| panic.name);
+    assert_eq!(2, panics.len());
+
+    assert_eq!("Gpu", panics[0].name);
+    assert_eq!("Plugin", panics[0].trait_name);
+    assert_eq!("driver vk not loaded", panics[0].message);
+
+    assert_eq!("Handler", panics[1].trait_name);
+    assert_eq!("guard panicked", panics[1].message);
+
+    // Panicking registrations are not left as unclaimed either
+    assert!(traitreg::unclaimed_registrations().is_empty());
+}
@@ -0,0 +1,43 @@
+trait MyTrait {
+    fn foo(&self) -> u32;
+}
+
+#[derive(Default)]
+struct MyStruct;
+
+#[traitreg::register(default)]
+impl MyTrait for MyStruct {
+    fn foo(&self) -> u32 {
+        123
+    }
+}
+
+#[derive(Default)]
+struct MyNotSendStruct {
+    value: std::rc::Rc<u32>,
+}
+
+#[traitreg::register(default)]
+impl MyTrait for MyNotSendStruct {
+    fn foo(&self) -> u32 {
+        *self.value
+    }
+}
+
+type MyObject = dyn MyTrait + Send + Sync;
+
+#[traitreg::registry(MyObject = dyn MyTrait + Send + Sync)]
+static MYOBJECT_REGISTRY: () = ();
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(2, MYTRAIT_REGISTRY.iter().count());
+    assert_eq!(1, MYOBJECT_REGISTRY.iter().count());
+
+    let instance: Box<MyObject> = MYOBJECT_REGISTRY.instantiate_all().next().unwrap();
+    let handle = std::thread::spawn(move || instance.foo());
+    assert_eq!(123, handle.join().unwrap());
+}
@@ -30,8 +30,8 @@ impl MyTrait for MyEnum {
 #[test]
 fn main() {
     assert_eq!(2, MYTRAIT_REGISTRY.iter().count());
-    assert_eq!(1, MYTRAIT_REGISTRY.instanciate_all().count());
+    assert_eq!(1, MYTRAIT_REGISTRY.instantiate_all().count());
 
-    let instance = MYTRAIT_REGISTRY.instanciate_all().next().unwrap();
+    let instance = MYTRAIT_REGISTRY.instantiate_all().next().unwrap();
     assert_eq!(instance.foo(), 123);
 }
@@ -0,0 +1,117 @@
+use std::borrow::Cow;
+
+struct Opts {
+    verbose: bool,
+}
+
+trait Plugin {
+    fn describe(&self) -> String;
+}
+
+struct Logger {
+    name: String,
+    verbose: bool,
+}
+
+impl Logger {
+    fn new(name: &str, opts: &Opts) -> Self {
+        Self {
+            name: name.to_string(),
+            verbose: opts.verbose,
+        }
+    }
+}
+
+#[traitreg::register(new, args)]
+impl Plugin for Logger {
+    fn describe(&self) -> String {
+        format!("{} verbose={}", self.name, self.verbose)
+    }
+}
+
+struct Database;
+
+impl Database {
+    fn connect(name: &str, _: &Opts) -> Result<Self, String> {
+        Err(format!("no database named {name}"))
+    }
+}
+
+#[traitreg::register(connect, args)]
+impl Plugin for Database {
+    fn describe(&self) -> String {
+        "database".to_string()
+    }
+}
+
+#[derive(Default)]
+struct Builtin;
+
+#[traitreg::register(default)]
+impl Plugin for Builtin {
+    fn describe(&self) -> String {
+        "builtin".to_string()
+    }
+}
+
+#[traitreg::registry(Plugin, args = (&str, &Opts))]
+static PLUGIN_REGISTRY: () = ();
+
+trait Labelled: Send {
+    fn label(&self) -> &str;
+}
+
+struct Label(String);
+
+impl Label {
+    fn new(label: Cow<'_, str>) -> Self {
+        Self(label.into_owned())
+    }
+}
+
+#[traitreg::register(new, args)]
+impl Labelled for Label {
+    fn label(&self) -> &str {
+        &self.0
+    }
+}
+
+#[traitreg::registry(dyn Labelled + Send, args = (Cow<'_, str>,))]
+static LABELLED_REGISTRY: () = ();
+
+fn get(name: &str) -> &'static traitreg::RegisteredImplWrapper<Box<dyn Plugin>> {
+    PLUGIN_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn main() {
+    let opts = Opts { verbose: true };
+
+    let logger = get("Logger").instantiate_args(("audit", &opts)).unwrap();
+    assert_eq!("audit verbose=true", logger.describe());
+    assert!(get("Logger").has_constructor());
+    assert!(get("Logger").instantiate().is_none());
+
+    assert!(get("Database").instantiate_args(("main", &opts)).is_none());
+
+    assert!(get("Builtin")
+        .instantiate_args(("builtin", &opts))
+        .is_none());
+    assert_eq!("builtin", get("Builtin").instantiate().unwrap().describe());
+}
+
+#[test]
+fn send() {
+    let name = String::from("primary");
+    let label: Box<dyn Labelled + Send> = LABELLED_REGISTRY
+        .iter()
+        .next()
+        .unwrap()
+        .instantiate_args((Cow::Borrowed(name.as_str()),))
+        .unwrap();
+    let handle = std::thread::spawn(move || label.label().to_string());
+    assert_eq!("primary", handle.join().unwrap());
+}
@@ -0,0 +1,42 @@
+trait Codec {
+    fn id(&self) -> u8;
+}
+
+#[derive(Default)]
+struct Gzip;
+
+#[traitreg::register(default)]
+impl Codec for Gzip {
+    fn id(&self) -> u8 {
+        1
+    }
+}
+
+struct Brotli;
+
+#[traitreg::register]
+impl Codec for Brotli {
+    fn id(&self) -> u8 {
+        2
+    }
+}
+
+#[traitreg::registry(dyn Codec + Send, dispatch = codec_dispatch)]
+static CODEC_REGISTRY: () = ();
+
+mod handle {
+    pub trait Filter {}
+
+    #[traitreg::registry(Filter, dispatch = filter_dispatch)]
+    pub static FILTER_REGISTRY: FilterRegistry = FilterRegistry;
+}
+
+#[test]
+fn main() {
+    assert_eq!(Some(1), codec_dispatch("Gzip").map(|codec| codec.id()));
+    assert!(codec_dispatch("Brotli").is_none());
+    assert!(codec_dispatch("Zstd").is_none());
+
+    let _: fn(&str) -> Option<Box<dyn handle::Filter>> = handle::filter_dispatch;
+    assert!(handle::filter_dispatch("Gzip").is_none());
+}
@@ -0,0 +1,71 @@
+struct Env {
+    name: &'static str,
+}
+
+trait Greeter: Send {
+    fn greet(&self) -> String;
+}
+
+struct Hello(&'static str);
+
+fn hello(env: &Env) -> Result<Box<dyn Greeter + Send>, String> {
+    Ok(Box::new(Hello(env.name)))
+}
+
+#[traitreg::register(factory = hello)]
+impl Greeter for Hello {
+    fn greet(&self) -> String {
+        format!("Hello {}", self.0)
+    }
+}
+
+struct Anonymous;
+
+fn anonymous(_: &Env) -> Result<Box<dyn Greeter + Send>, String> {
+    Err("anonymous greetings are disabled".to_string())
+}
+
+#[traitreg::register(factory = anonymous, priority = -1)]
+impl Greeter for Anonymous {
+    fn greet(&self) -> String {
+        "Hello".to_string()
+    }
+}
+
+#[derive(Default)]
+struct Plain;
+
+#[traitreg::register(default)]
+impl Greeter for Plain {
+    fn greet(&self) -> String {
+        "Hi".to_string()
+    }
+}
+
+#[traitreg::registry(dyn Greeter + Send, factory = fn(&Env) -> Result<Box<dyn Greeter + Send>, String>)]
+static GREETER_REGISTRY: () = ();
+
+fn get(name: &str) -> &'static traitreg::RegisteredImplWrapper<Box<dyn Greeter + Send>> {
+    GREETER_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn main() {
+    let env = Env { name: "world" };
+
+    let factory = get("Hello").factory().unwrap();
+    assert_eq!("Hello world", factory(&env).unwrap().greet());
+    assert!(get("Hello").instantiate().is_none());
+
+    let factory = get("Anonymous").factory().unwrap();
+    assert_eq!(
+        "anonymous greetings are disabled",
+        factory(&env).err().unwrap()
+    );
+
+    assert!(get("Plain").factory().is_none());
+    assert_eq!("Hi", get("Plain").instantiate().unwrap().greet());
+}
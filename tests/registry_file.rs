@@ -0,0 +1,59 @@
+#[path = "registry_file/video.rs"]
+mod video;
+
+trait Plugin {}
+
+struct Audio;
+
+#[traitreg::register]
+impl Plugin for Audio {}
+
+struct Midi;
+
+#[traitreg::register]
+impl Plugin for Midi {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+fn names<'a>(
+    impls: impl Iterator<Item = &'a traitreg::RegisteredImplWrapper<Box<dyn Plugin>>>,
+) -> Vec<&'static str> {
+    let mut names: Vec<_> = impls.map(|item| item.name()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        vec!["Audio", "Midi"],
+        names(PLUGIN_REGISTRY.iter_from_file("tests/registry_file.rs"))
+    );
+    assert_eq!(
+        vec!["Video"],
+        names(PLUGIN_REGISTRY.iter_from_file("tests/registry_file/video.rs"))
+    );
+
+    // Trailing path components match, with either separator
+    assert_eq!(
+        vec!["Video"],
+        names(PLUGIN_REGISTRY.iter_from_file("registry_file/video.rs"))
+    );
+    assert_eq!(
+        vec!["Video"],
+        names(PLUGIN_REGISTRY.iter_from_file("registry_file\\video.rs"))
+    );
+    assert_eq!(0, PLUGIN_REGISTRY.iter_from_file("file/video.rs").count());
+    assert_eq!(
+        0,
+        PLUGIN_REGISTRY
+            .iter_from_file("crates/app/tests/registry_file.rs")
+            .count()
+    );
+    assert_eq!(0, PLUGIN_REGISTRY.iter_from_file("").count());
+
+    let video = PLUGIN_REGISTRY.query().file("video.rs").collect();
+    assert_eq!(1, video.len());
+    assert!(video[0].is_from_file("tests/registry_file/video.rs"));
+}
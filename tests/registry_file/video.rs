@@ -0,0 +1,6 @@
+use crate::Plugin;
+
+pub struct Video;
+
+#[traitreg::register]
+impl Plugin for Video {}
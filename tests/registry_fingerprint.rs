@@ -0,0 +1,57 @@
+trait Plugin {}
+
+struct Audio;
+
+#[traitreg::register]
+impl Plugin for Audio {}
+
+struct Video;
+
+#[traitreg::register]
+impl Plugin for Video {}
+
+mod other {
+    pub struct Audio;
+
+    #[traitreg::register]
+    impl super::Plugin for Audio {}
+}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+#[traitreg::registry(Plugin, meta_only)]
+static PLUGIN_META_REGISTRY: () = ();
+
+trait Codec {}
+
+struct Gzip;
+
+#[traitreg::register]
+impl Codec for Gzip {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+trait Unused {}
+
+#[traitreg::registry(Unused)]
+static UNUSED_REGISTRY: () = ();
+
+#[traitreg::registry(Unused, meta_only)]
+static UNUSED_META_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(PLUGIN_REGISTRY.fingerprint(), PLUGIN_REGISTRY.fingerprint());
+    assert_eq!(
+        PLUGIN_REGISTRY.fingerprint(),
+        PLUGIN_META_REGISTRY.fingerprint()
+    );
+    assert_ne!(PLUGIN_REGISTRY.fingerprint(), CODEC_REGISTRY.fingerprint());
+    assert_ne!(PLUGIN_REGISTRY.fingerprint(), UNUSED_REGISTRY.fingerprint());
+    assert_eq!(
+        UNUSED_REGISTRY.fingerprint(),
+        UNUSED_META_REGISTRY.fingerprint()
+    );
+}
@@ -0,0 +1,25 @@
+trait MyTrait {}
+
+#[derive(Default)]
+struct MyStruct;
+
+#[traitreg::register(default)]
+impl MyTrait for MyStruct {}
+
+#[traitreg::registry(MyTrait)]
+static MYTRAIT_REGISTRY: MyTraitRegistry = MyTraitRegistry;
+
+fn count(registry: MyTraitRegistry) -> usize {
+    registry.iter().count()
+}
+
+fn storage(registry: &MyTraitRegistry) -> &'static traitreg::TraitRegStorage<Box<dyn MyTrait>> {
+    registry.storage()
+}
+
+#[test]
+fn main() {
+    assert_eq!(1, count(MYTRAIT_REGISTRY));
+    assert_eq!(1, storage(&MYTRAIT_REGISTRY).instantiate_all().count());
+    assert_eq!("MyTraitRegistry", format!("{:?}", MYTRAIT_REGISTRY));
+}
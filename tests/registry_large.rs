@@ -0,0 +1,33 @@
+// A registry of many implementations, registered by a macro
+
+trait Codec {}
+
+macro_rules! codecs {
+    ($($name:ident),*) => {
+        $(
+            #[derive(Default)]
+            struct $name;
+
+            #[traitreg::register(default)]
+            impl Codec for $name {}
+        )*
+    };
+}
+
+codecs!(Gzip, Brotli, Zstd, Lz4, Snappy, Deflate, Bzip2);
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(7, CODEC_REGISTRY.len());
+    assert_eq!(7, CODEC_REGISTRY.instantiate_all().count());
+
+    let mut names: Vec<_> = CODEC_REGISTRY.iter().map(|item| item.name()).collect();
+    names.sort();
+    assert_eq!(
+        vec!["Brotli", "Bzip2", "Deflate", "Gzip", "Lz4", "Snappy", "Zstd"],
+        names
+    );
+}
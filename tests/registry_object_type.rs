@@ -0,0 +1,88 @@
+trait MyTrait {
+    fn foo(&self) -> u32;
+}
+
+#[derive(Default)]
+struct MyStruct;
+
+#[traitreg::register(default)]
+impl MyTrait for MyStruct {
+    fn foo(&self) -> u32 {
+        123
+    }
+}
+
+#[derive(Default)]
+struct MyNotSyncStruct {
+    value: std::cell::Cell<u32>,
+}
+
+#[traitreg::register(default)]
+impl MyTrait for MyNotSyncStruct {
+    fn foo(&self) -> u32 {
+        self.value.get()
+    }
+}
+
+#[traitreg::registry(dyn MyTrait + Send + 'static)]
+static MYTRAIT_SEND_REGISTRY: () = ();
+
+#[traitreg::registry(dyn MyTrait + Send + Sync, meta_only)]
+static MYTRAIT_SEND_SYNC_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    assert_eq!(2, MYTRAIT_SEND_REGISTRY.iter().count());
+    assert_eq!(1, MYTRAIT_SEND_SYNC_REGISTRY.iter().count());
+
+    let instances: Vec<Box<dyn MyTrait + Send>> = MYTRAIT_SEND_REGISTRY.instantiate_all().collect();
+    let handle = std::thread::spawn(move || instances.iter().map(|i| i.foo()).sum::<u32>());
+    assert_eq!(123, handle.join().unwrap());
+}
+
+trait Codec<T> {
+    fn encode(&self) -> T;
+}
+
+#[derive(Default)]
+struct ByteCodec;
+
+#[traitreg::register(default)]
+impl Codec<u8> for ByteCodec {
+    fn encode(&self) -> u8 {
+        1
+    }
+}
+
+#[derive(Default)]
+struct TextCodec;
+
+#[traitreg::register(default)]
+impl Codec<String> for TextCodec {
+    fn encode(&self) -> String {
+        "text".to_string()
+    }
+}
+
+#[traitreg::registry(dyn Codec<u8> + Send)]
+static BYTE_CODEC_REGISTRY: () = ();
+
+#[traitreg::registry(dyn Codec<String> + Send)]
+static TEXT_CODEC_REGISTRY: () = ();
+
+#[test]
+fn generic_arguments() {
+    assert_eq!(
+        vec!["ByteCodec"],
+        BYTE_CODEC_REGISTRY.iter_names().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["TextCodec"],
+        TEXT_CODEC_REGISTRY.iter_names().collect::<Vec<_>>()
+    );
+
+    let codec: Box<dyn Codec<u8> + Send> = BYTE_CODEC_REGISTRY.instantiate_all().next().unwrap();
+    assert_eq!(1, codec.encode());
+    let codec = TEXT_CODEC_REGISTRY.instantiate_all().next().unwrap();
+    assert_eq!("text", codec.encode());
+}
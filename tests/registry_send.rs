@@ -0,0 +1,56 @@
+use std::rc::Rc;
+
+trait Job {
+    fn run(&self) -> u32;
+}
+
+#[derive(Default)]
+struct Compile;
+
+#[traitreg::register(default)]
+impl Job for Compile {
+    fn run(&self) -> u32 {
+        1
+    }
+}
+
+#[derive(Default)]
+struct Local(Rc<u32>);
+
+#[traitreg::register(default)]
+impl Job for Local {
+    fn run(&self) -> u32 {
+        *self.0
+    }
+}
+
+#[traitreg::registry(Job, send)]
+static JOB_REGISTRY: () = ();
+
+// Already Send, the flag changes nothing
+#[traitreg::registry(dyn Job + Send, send)]
+static SEND_JOB_REGISTRY: () = ();
+
+type SendJob = dyn Job + Send + Sync;
+
+#[traitreg::registry(SendJob = dyn Job + Send + Sync, send)]
+static ALIAS_JOB_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    // Implementations on types which are not Send are left out
+    assert_eq!(1, JOB_REGISTRY.len());
+    assert_eq!(1, SEND_JOB_REGISTRY.len());
+    assert_eq!(1, ALIAS_JOB_REGISTRY.len());
+
+    let jobs: Vec<Box<dyn Job + Send>> = JOB_REGISTRY.instantiate_all().collect();
+    let handles: Vec<_> = jobs
+        .into_iter()
+        .map(|job| std::thread::spawn(move || job.run()))
+        .collect();
+    for handle in handles {
+        assert_eq!(1, handle.join().unwrap());
+    }
+
+    let _: Box<SendJob> = ALIAS_JOB_REGISTRY.instantiate_all().next().unwrap();
+}
@@ -0,0 +1,76 @@
+trait Codec {}
+
+struct Gzip;
+
+#[traitreg::register(tags = ["builtin"])]
+impl Codec for Gzip {}
+
+struct Brotli;
+
+#[traitreg::register(tags = ["builtin", "fast"])]
+impl Codec for Brotli {}
+
+struct Zstd;
+
+#[traitreg::register(tags = ["fast"])]
+impl Codec for Zstd {}
+
+// Not Send, so only in the registry without auto traits
+struct Lz4(std::marker::PhantomData<std::rc::Rc<()>>);
+
+#[traitreg::register]
+impl Codec for Lz4 {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+#[traitreg::registry(dyn Codec + Send)]
+static SEND_CODEC_REGISTRY: () = ();
+
+fn names<Trait>(view: traitreg::query::View<'_, Trait>) -> Vec<&'static str> {
+    let mut names: Vec<_> = view.into_iter().map(|item| item.name()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn main() {
+    let builtin = CODEC_REGISTRY.query().tag("builtin").view();
+    let fast = CODEC_REGISTRY.query().tag("fast").view();
+
+    assert_eq!(
+        vec!["Brotli", "Gzip", "Zstd"],
+        names(builtin.clone().union(&fast))
+    );
+    assert_eq!(vec!["Brotli"], names(builtin.clone().intersection(&fast)));
+    assert_eq!(vec!["Gzip"], names(builtin.clone().difference(&fast)));
+    assert_eq!(
+        vec!["Lz4", "Zstd"],
+        names(CODEC_REGISTRY.view().difference(&builtin))
+    );
+
+    // Views of registries with different auto traits can be combined
+    assert_eq!(
+        vec!["Lz4"],
+        names(
+            CODEC_REGISTRY
+                .view()
+                .difference(&SEND_CODEC_REGISTRY.view())
+        )
+    );
+    assert_eq!(
+        3,
+        SEND_CODEC_REGISTRY
+            .view()
+            .intersection(&CODEC_REGISTRY.view())
+            .len()
+    );
+
+    let union = builtin.union(&fast);
+    assert_eq!(3, union.len());
+    assert!(!union.is_empty());
+    assert!(CODEC_REGISTRY
+        .iter()
+        .filter(|item| item.name() != "Lz4")
+        .all(|item| union.contains(item)));
+}
@@ -0,0 +1,47 @@
+trait Plugin {}
+
+#[derive(Default)]
+struct Audio;
+
+#[traitreg::register(default)]
+impl Plugin for Audio {}
+
+#[derive(Default)]
+struct Video;
+
+#[traitreg::register(default)]
+impl Plugin for Video {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+#[traitreg::registry(Plugin)]
+static OTHER_PLUGIN_REGISTRY: () = ();
+
+#[traitreg::registry(dyn Plugin + Send)]
+static SEND_PLUGIN_REGISTRY: () = ();
+
+#[traitreg::registry(Plugin, sorted)]
+static SORTED_PLUGIN_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    // Registries of the same trait and options are built once
+    assert!(core::ptr::eq(
+        PLUGIN_REGISTRY.as_slice(),
+        OTHER_PLUGIN_REGISTRY.as_slice()
+    ));
+    assert_eq!(2, OTHER_PLUGIN_REGISTRY.instantiate_all().count());
+
+    // Registries constructing a different type, or with different options, are built separately
+    assert_eq!(2, SEND_PLUGIN_REGISTRY.len());
+    assert_eq!(2, SORTED_PLUGIN_REGISTRY.len());
+    assert!(!core::ptr::eq(
+        PLUGIN_REGISTRY.as_slice().as_ptr().cast::<()>(),
+        SEND_PLUGIN_REGISTRY.as_slice().as_ptr().cast::<()>()
+    ));
+    assert!(!core::ptr::eq(
+        PLUGIN_REGISTRY.as_slice(),
+        SORTED_PLUGIN_REGISTRY.as_slice()
+    ));
+}
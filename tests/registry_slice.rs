@@ -0,0 +1,43 @@
+trait Codec {}
+
+#[derive(Default)]
+struct Gzip;
+
+#[traitreg::register(default)]
+impl Codec for Gzip {}
+
+struct Zstd;
+
+#[traitreg::register]
+impl Codec for Zstd {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+// Framework code can hold entries without a lifetime parameter
+struct Selection {
+    codecs: &'static [traitreg::RegisteredImplWrapper<Box<dyn Codec>>],
+    first: &'static traitreg::RegisteredImplWrapper<Box<dyn Codec>>,
+}
+
+fn select() -> Selection {
+    let codecs = CODEC_REGISTRY.as_slice();
+    Selection {
+        codecs,
+        first: &codecs[0],
+    }
+}
+
+#[test]
+fn main() {
+    let selection = select();
+    assert_eq!(2, selection.codecs.len());
+    assert_eq!(selection.first, &selection.codecs[0]);
+
+    let mut names = vec![];
+    for item in CODEC_REGISTRY.storage() {
+        names.push(item.name());
+    }
+    names.sort();
+    assert_eq!(vec!["Gzip", "Zstd"], names);
+}
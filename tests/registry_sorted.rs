@@ -0,0 +1,48 @@
+trait Plugin {}
+
+struct Zeta;
+
+#[traitreg::register]
+impl Plugin for Zeta {}
+
+mod beta {
+    pub struct Alpha;
+
+    #[traitreg::register]
+    impl super::Plugin for Alpha {}
+}
+
+mod alpha {
+    pub struct Omega;
+
+    #[traitreg::register]
+    impl super::Plugin for Omega {}
+
+    pub struct Delta;
+
+    #[traitreg::register]
+    impl super::Plugin for Delta {}
+}
+
+struct Gamma;
+
+#[traitreg::register]
+impl Plugin for Gamma {}
+
+#[traitreg::registry(Plugin, sorted)]
+static PLUGIN_REGISTRY: () = ();
+
+#[traitreg::registry(Plugin, meta_only, sorted)]
+static PLUGIN_META_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let names: Vec<_> = PLUGIN_REGISTRY.iter().map(|item| item.name()).collect();
+    assert_eq!(vec!["Gamma", "Zeta", "Delta", "Omega", "Alpha"], names);
+
+    let meta_names: Vec<_> = PLUGIN_META_REGISTRY
+        .iter()
+        .map(|item| item.name())
+        .collect();
+    assert_eq!(names, meta_names);
+}
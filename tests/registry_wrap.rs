@@ -0,0 +1,75 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+trait Counter {
+    fn increment(&mut self) -> u32;
+}
+
+#[derive(Default)]
+struct Simple(u32);
+
+#[traitreg::register(default)]
+impl Counter for Simple {
+    fn increment(&mut self) -> u32 {
+        self.0 += 1;
+        self.0
+    }
+}
+
+struct Failing;
+
+impl Failing {
+    fn new() -> Result<Self, &'static str> {
+        Err("unavailable")
+    }
+}
+
+#[traitreg::register(new)]
+impl Counter for Failing {
+    fn increment(&mut self) -> u32 {
+        0
+    }
+}
+
+struct Unconstructed;
+
+#[traitreg::register]
+impl Counter for Unconstructed {
+    fn increment(&mut self) -> u32 {
+        0
+    }
+}
+
+#[traitreg::registry(Counter, send, wrap = Arc<Mutex<_>>, dispatch = shared_counter)]
+static SHARED_REGISTRY: () = ();
+
+#[traitreg::registry(Counter, wrap = std::rc::Rc<std::cell::RefCell<_>>)]
+static LOCAL_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let counter: Arc<Mutex<Box<dyn Counter + Send>>> = shared_counter("Simple").unwrap();
+    let handle = {
+        let counter = counter.clone();
+        std::thread::spawn(move || counter.lock().unwrap().increment())
+    };
+    assert_eq!(1, handle.join().unwrap());
+    assert_eq!(2, counter.lock().unwrap().increment());
+
+    assert!(shared_counter("Failing").is_none());
+    assert!(shared_counter("Unconstructed").is_none());
+    assert_eq!(1, SHARED_REGISTRY.instantiate_all().count());
+}
+
+#[test]
+fn local() {
+    let counter: Rc<RefCell<Box<dyn Counter>>> = LOCAL_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Simple")
+        .unwrap()
+        .instantiate()
+        .unwrap();
+    assert_eq!(1, counter.borrow_mut().increment());
+    assert_eq!(3, LOCAL_REGISTRY.len());
+}
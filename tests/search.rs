@@ -0,0 +1,51 @@
+trait Codec {}
+
+struct Json;
+
+#[traitreg::register]
+impl Codec for Json {}
+
+struct JsonCodec;
+
+#[traitreg::register]
+impl Codec for JsonCodec {}
+
+struct FastJson;
+
+#[traitreg::register]
+impl Codec for FastJson {}
+
+struct JavaSerialization;
+
+#[traitreg::register]
+impl Codec for JavaSerialization {}
+
+struct Yaml;
+
+#[traitreg::register]
+impl Codec for Yaml {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+fn search(pattern: &str) -> Vec<&'static str> {
+    CODEC_REGISTRY
+        .search(pattern)
+        .into_iter()
+        .map(|item| item.name())
+        .collect()
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        vec!["Json", "JsonCodec", "FastJson", "JavaSerialization"],
+        search("json")
+    );
+    assert_eq!(vec!["JsonCodec"], search("JSONC"));
+    assert_eq!(vec!["JsonCodec"], search("jsc"));
+    assert_eq!(vec!["JavaSerialization"], search("jser"));
+    assert_eq!(vec!["Yaml"], search("yml"));
+    assert!(search("xml").is_empty());
+    assert_eq!(5, search("").len());
+}
@@ -0,0 +1,74 @@
+use traitreg::select::{SelectionStrategy, Strategy};
+use traitreg::RegisteredImplWrapper;
+
+trait Storage {
+    fn kind(&self) -> &'static str;
+}
+
+#[derive(Default)]
+struct Memory;
+
+#[traitreg::register(default, tags = ["volatile"])]
+impl Storage for Memory {
+    fn kind(&self) -> &'static str {
+        "memory"
+    }
+}
+
+#[derive(Default)]
+struct Disk;
+
+#[traitreg::register(default, priority = 5)]
+impl Storage for Disk {
+    fn kind(&self) -> &'static str {
+        "disk"
+    }
+}
+
+#[derive(Default)]
+struct Network;
+
+#[traitreg::register(default, priority = 5)]
+impl Storage for Network {
+    fn kind(&self) -> &'static str {
+        "network"
+    }
+}
+
+#[traitreg::registry(Storage)]
+static STORAGE_REGISTRY: () = ();
+
+struct Tagged(&'static str);
+
+impl SelectionStrategy for Tagged {
+    fn select<'a, Trait>(
+        &self,
+        impls: &'a [RegisteredImplWrapper<Trait>],
+    ) -> Option<&'a RegisteredImplWrapper<Trait>> {
+        impls.iter().find(|item| item.tags().contains(&self.0))
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        STORAGE_REGISTRY.iter().next(),
+        STORAGE_REGISTRY.select(Strategy::First)
+    );
+
+    // Ties are broken by registry order, like `iter_by_priority`
+    let highest = STORAGE_REGISTRY.select(Strategy::HighestPriority).unwrap();
+    assert_eq!(5, highest.priority());
+    assert_eq!(STORAGE_REGISTRY.iter_by_priority().next(), Some(highest));
+
+    let disk = STORAGE_REGISTRY.select(Strategy::ByName("Disk")).unwrap();
+    assert_eq!("disk", disk.instantiate().unwrap().kind());
+    assert!(STORAGE_REGISTRY.select(Strategy::ByName("Tape")).is_none());
+
+    let memory = STORAGE_REGISTRY.select(Tagged("volatile")).unwrap();
+    assert_eq!("Memory", memory.name());
+
+    let empty = traitreg::dynamic_registry("Unknown");
+    assert!(empty.select(Strategy::First).is_none());
+    assert!(empty.select(Strategy::HighestPriority).is_none());
+}
@@ -0,0 +1,41 @@
+use std::rc::Rc;
+
+// Instances can't leave the thread they were created on, but the registry can be shared
+trait Local {
+    fn value(&self) -> Rc<u32>;
+}
+
+#[derive(Default)]
+struct LocalImpl;
+
+#[traitreg::register(default)]
+impl Local for LocalImpl {
+    fn value(&self) -> Rc<u32> {
+        Rc::new(1)
+    }
+}
+
+#[traitreg::registry(Local)]
+static LOCAL_REGISTRY: () = ();
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+#[test]
+fn main() {
+    let storage: &'static traitreg::TraitRegStorage<Box<dyn Local>> = LOCAL_REGISTRY.storage();
+    assert_send_sync(&LOCAL_REGISTRY);
+    assert_send_sync(storage);
+    assert_send_sync(&storage.snapshot());
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(move || {
+                let item = storage.iter().next().unwrap();
+                *item.instantiate().unwrap().value()
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(1, handle.join().unwrap());
+    }
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "serde")]
+
+trait Codec {}
+
+#[derive(Default)]
+struct Gzip;
+
+#[traitreg::register(default, priority = 2, capabilities = ["streaming"], tags = ["stable"])]
+impl Codec for Gzip {}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let gzip = CODEC_REGISTRY.iter().next().unwrap();
+    let value = serde_json::to_value(gzip).unwrap();
+
+    assert_eq!("Gzip", value["name"]);
+    assert_eq!("Gzip", value["path"]);
+    assert_eq!("impl Codec for Gzip", value["signature"]);
+    assert_eq!("Codec", value["trait_name"]);
+    assert_eq!(true, value["has_constructor"]);
+    assert_eq!(2, value["priority"]);
+    assert_eq!(serde_json::json!(["streaming"]), value["capabilities"]);
+    assert_eq!(serde_json::json!(["stable"]), value["tags"]);
+    assert_eq!(serde_json::json!([]), value["flags"]);
+    assert_eq!("serde", value["module_path"]);
+    assert_eq!("tests/serde.rs", value["file"]);
+    assert_eq!("traitreg", value["crate_name"]);
+    assert_eq!(env!("CARGO_PKG_VERSION"), value["crate_version"]);
+
+    let all = serde_json::to_string(CODEC_REGISTRY.as_slice()).unwrap();
+    assert!(all.starts_with(r#"[{"name":"Gzip","#));
+}
+
+#[test]
+fn manifest() {
+    let data = std::fs::read(std::env::current_exe().unwrap()).unwrap();
+    let entry = traitreg::manifest::entries(&data)
+        .find(|entry| entry.trait_name == "Codec")
+        .unwrap();
+    let value = serde_json::to_value(entry).unwrap();
+    assert_eq!("Gzip", value["name"]);
+    assert_eq!("traitreg", value["crate_name"]);
+}
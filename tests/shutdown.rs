@@ -0,0 +1,59 @@
+use std::sync::Mutex;
+
+static SHUTDOWN: Mutex<Vec<&str>> = Mutex::new(vec![]);
+
+trait Service {}
+
+struct Database;
+
+fn close_database() {
+    SHUTDOWN.lock().unwrap().push("Database");
+}
+
+#[traitreg::register(priority = 10, shutdown = close_database)]
+impl Service for Database {}
+
+struct Server;
+
+fn stop_server() {
+    SHUTDOWN.lock().unwrap().push("Server");
+}
+
+#[traitreg::register(shutdown = stop_server)]
+impl Service for Server {}
+
+struct Cache;
+
+fn flush_cache() {
+    SHUTDOWN.lock().unwrap().push("Cache");
+}
+
+#[traitreg::register(priority = 5, shutdown = flush_cache)]
+impl Service for Cache {}
+
+struct Metrics;
+
+#[traitreg::register(priority = -1)]
+impl Service for Metrics {}
+
+#[traitreg::registry(Service)]
+static SERVICE_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let startup: Vec<_> = SERVICE_REGISTRY
+        .iter_by_priority()
+        .map(|item| item.name())
+        .collect();
+    assert_eq!(vec!["Database", "Cache", "Server", "Metrics"], startup);
+
+    traitreg::shutdown_all();
+    assert_eq!(
+        vec!["Server", "Cache", "Database"],
+        *SHUTDOWN.lock().unwrap()
+    );
+
+    // Hooks only run once
+    traitreg::shutdown_all();
+    assert_eq!(3, SHUTDOWN.lock().unwrap().len());
+}
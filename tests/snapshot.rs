@@ -0,0 +1,58 @@
+trait Codec {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Default)]
+struct Gzip;
+
+#[traitreg::register(default)]
+impl Codec for Gzip {
+    fn name(&self) -> &'static str {
+        "gzip"
+    }
+}
+
+struct Zstd;
+
+#[traitreg::register]
+impl Codec for Zstd {
+    fn name(&self) -> &'static str {
+        "zstd"
+    }
+}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let snapshot = CODEC_REGISTRY.snapshot();
+    assert_eq!(CODEC_REGISTRY.len(), snapshot.len());
+    assert!(!snapshot.is_empty());
+
+    // Entries are copies equal to those in the registry, with working constructors
+    for (item, copy) in CODEC_REGISTRY.iter().zip(&snapshot) {
+        assert_eq!(item, copy);
+        assert_eq!(item.name(), copy.name());
+        assert_eq!(item.has_constructor(), copy.has_constructor());
+        assert!(CODEC_REGISTRY.id_of(copy).is_none());
+    }
+    let gzip = snapshot.iter().find(|item| item.name() == "Gzip").unwrap();
+    assert_eq!("gzip", gzip.instantiate().unwrap().name());
+
+    for (id, item) in CODEC_REGISTRY.iter_with_ids() {
+        assert_eq!(Some(item), snapshot.get_by_id(id));
+    }
+
+    // Clones share the copy, and can be moved to other threads
+    let clone = snapshot.clone();
+    assert!(core::ptr::eq(snapshot.as_slice(), clone.as_slice()));
+    let names = std::thread::spawn(move || {
+        let mut names: Vec<_> = clone.iter().map(|item| item.name()).collect();
+        names.sort();
+        names
+    })
+    .join()
+    .unwrap();
+    assert_eq!(vec!["Gzip", "Zstd"], names);
+}
@@ -0,0 +1,191 @@
+#[traitreg::static_dispatch(CounterEnum, Incrementing, other::Doubling)]
+trait Counter {
+    fn get(&self) -> u32;
+    fn step(&mut self, times: u32);
+    fn add(&self, (a, b): (u32, u32)) -> u32 {
+        a + b + self.get()
+    }
+    fn into_value(self) -> u32;
+}
+
+#[derive(Default)]
+struct Incrementing(u32);
+
+#[traitreg::register(default)]
+impl Counter for Incrementing {
+    fn get(&self) -> u32 {
+        self.0
+    }
+
+    fn step(&mut self, times: u32) {
+        self.0 += times;
+    }
+
+    fn into_value(self) -> u32 {
+        self.0
+    }
+}
+
+mod other {
+    pub struct Doubling(pub u32);
+
+    impl Default for Doubling {
+        fn default() -> Self {
+            Self(1)
+        }
+    }
+
+    #[traitreg::register(default)]
+    impl super::Counter for Doubling {
+        fn get(&self) -> u32 {
+            self.0
+        }
+
+        fn step(&mut self, times: u32) {
+            self.0 <<= times;
+        }
+
+        fn add(&self, (a, b): (u32, u32)) -> u32 {
+            (a + b) * self.0
+        }
+
+        fn into_value(self) -> u32 {
+            self.0
+        }
+    }
+}
+
+#[traitreg::registry(Counter)]
+static COUNTER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let mut counters: Vec<CounterEnum> = vec![
+        Incrementing::default().into(),
+        other::Doubling::default().into(),
+    ];
+
+    for counter in counters.iter_mut() {
+        counter.step(3);
+    }
+
+    assert_eq!(
+        vec![3, 8],
+        counters.iter().map(|c| c.get()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![6, 24],
+        counters.iter().map(|c| c.add((1, 2))).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![3, 8],
+        counters
+            .into_iter()
+            .map(|c| c.into_value())
+            .collect::<Vec<_>>()
+    );
+
+    const _: () = assert!(CounterEnum::COUNT == 2);
+    let _: [u32; CounterEnum::COUNT] = [0; 2];
+    assert_eq!(CounterEnum::COUNT, COUNTER_REGISTRY.len());
+
+    // Every registered implementation is covered by the enum
+    let mut registered: Vec<&str> = COUNTER_REGISTRY.iter().map(|item| item.name()).collect();
+    registered.sort();
+    let mut names = CounterEnum::NAMES.to_vec();
+    names.sort();
+    assert_eq!(registered, names);
+}
+
+mod shapes {
+    #[traitreg::static_dispatch(ShapeEnum, Square, Triangle)]
+    pub trait Shape {
+        fn sides(&self) -> u32;
+    }
+
+    pub struct Square;
+
+    impl Shape for Square {
+        fn sides(&self) -> u32 {
+            4
+        }
+    }
+
+    pub struct Triangle;
+
+    impl Shape for Triangle {
+        fn sides(&self) -> u32 {
+            3
+        }
+    }
+
+    impl Square {
+        pub const ID: u8 = 1;
+    }
+
+    impl Triangle {
+        pub const ID: u8 = 2;
+    }
+}
+
+#[test]
+fn for_each_registered() {
+    let mut names = vec![];
+    traitreg::for_each_registered!(CounterEnum, |T, name| {
+        names.push((name, T::default().into_value()));
+    });
+    assert_eq!(vec![("Incrementing", 0), ("Doubling", 1)], names);
+
+    // Enums in other modules are referred to by path, without the types in scope
+    let mut ids = vec![];
+    traitreg::for_each_registered!(shapes::ShapeEnum, |T| {
+        ids.push(T::ID);
+    });
+    assert_eq!(vec![1, 2], ids);
+
+    use shapes::Shape;
+    assert_eq!(4, shapes::ShapeEnum::from(shapes::Square).sides());
+}
+
+#[traitreg::static_dispatch(CodecEnum, Gzip)]
+trait Codec {
+    fn id(&self) -> u8;
+}
+
+#[derive(Default)]
+struct Gzip;
+
+#[traitreg::register(default)]
+impl Codec for Gzip {
+    fn id(&self) -> u8 {
+        1
+    }
+}
+
+#[derive(Default)]
+struct Zstd;
+
+// Registered, but not listed in `CodecEnum`
+#[traitreg::register(default)]
+impl Codec for Zstd {
+    fn id(&self) -> u8 {
+        2
+    }
+}
+
+#[traitreg::registry(Codec)]
+static CODEC_REGISTRY: () = ();
+
+#[test]
+fn out_of_sync() {
+    // The enum only knows the implementations listed, comparing its names against the registry
+    // finds those which are missing
+    assert_eq!(1, CodecEnum::COUNT);
+    assert_eq!(2, CODEC_REGISTRY.len());
+    let missing: Vec<&str> = CODEC_REGISTRY
+        .iter_names()
+        .filter(|name| !CodecEnum::NAMES.contains(name))
+        .collect();
+    assert_eq!(vec!["Zstd"], missing);
+    assert_eq!(1, CodecEnum::from(Gzip).id());
+}
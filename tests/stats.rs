@@ -0,0 +1,43 @@
+trait Codec {}
+
+#[derive(Default)]
+struct Gzip;
+
+#[traitreg::register(default)]
+impl Codec for Gzip {}
+
+struct Zstd;
+
+#[traitreg::register]
+impl Codec for Zstd {}
+
+trait Format {}
+
+#[derive(Default)]
+struct Json;
+
+#[traitreg::register(default)]
+impl Format for Json {}
+
+#[test]
+fn main() {
+    let stats = traitreg::stats();
+    assert_eq!(3, stats.total());
+    assert_eq!(2, stats.with_constructor());
+    assert_eq!(0, stats.failed());
+
+    assert_eq!(3, stats.registrations_of_crate(env!("CARGO_PKG_NAME")));
+    assert_eq!(0, stats.registrations_of_crate("missing"));
+    assert_eq!(
+        vec![(env!("CARGO_PKG_NAME"), 3)],
+        stats
+            .registrations_by_crate()
+            .iter()
+            .map(|(name, count)| (*name, *count))
+            .collect::<Vec<_>>()
+    );
+
+    assert_eq!(2, stats.registrations_of_trait("Codec"));
+    assert_eq!(1, stats.registrations_of_trait("Format"));
+    assert_eq!(2, stats.registrations_by_trait().len());
+}
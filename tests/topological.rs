@@ -0,0 +1,97 @@
+trait Plugin {}
+
+struct Metrics;
+
+#[traitreg::register(after("Logger", "Config"))]
+impl Plugin for Metrics {}
+
+struct Dashboard;
+
+#[traitreg::register(after("Metrics", "Missing"))]
+impl Plugin for Dashboard {}
+
+struct Logger;
+
+#[traitreg::register(after("Config"))]
+impl Plugin for Logger {}
+
+struct Config;
+
+#[traitreg::register]
+impl Plugin for Config {}
+
+#[traitreg::registry(Plugin, sorted)]
+static PLUGIN_REGISTRY: () = ();
+
+trait Service {}
+
+struct Api;
+
+#[traitreg::register(after("Auth"))]
+impl Service for Api {}
+
+struct Auth;
+
+#[traitreg::register(after("Session"))]
+impl Service for Auth {}
+
+struct Session;
+
+#[traitreg::register(after("Api"))]
+impl Service for Session {}
+
+struct Cache;
+
+#[traitreg::register]
+impl Service for Cache {}
+
+#[traitreg::registry(Service, sorted)]
+static SERVICE_REGISTRY: () = ();
+
+trait Handler {}
+
+impl<F: Fn()> Handler for F {}
+
+#[traitreg::register(Handler, after("ping"))]
+fn pong() {}
+
+#[traitreg::register(Handler)]
+fn ping() {}
+
+#[traitreg::registry(Handler)]
+static HANDLER_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let names: Vec<_> = PLUGIN_REGISTRY
+        .iter_topological()
+        .unwrap()
+        .map(|item| item.name())
+        .collect();
+    assert_eq!(vec!["Config", "Logger", "Metrics", "Dashboard"], names);
+
+    let metrics = PLUGIN_REGISTRY
+        .iter()
+        .find(|item| item.name() == "Metrics")
+        .unwrap();
+    assert_eq!(&["Logger", "Config"], metrics.after());
+
+    let names: Vec<_> = HANDLER_REGISTRY
+        .iter_topological()
+        .unwrap()
+        .map(|item| item.name())
+        .collect();
+    assert_eq!(vec!["ping", "pong"], names);
+}
+
+#[test]
+fn cycle() {
+    let Err(cycle) = SERVICE_REGISTRY.iter_topological() else {
+        panic!("Expected a dependency cycle");
+    };
+    assert_eq!(&["Api", "Auth", "Session", "Api"], cycle.names());
+    assert_eq!(
+        "dependency cycle between implementations: Api -> Auth -> Session -> Api",
+        cycle.to_string()
+    );
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "tower")]
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::task::{Context, Poll, Waker};
+use tower::util::{BoxCloneSyncServiceLayer, MapRequestLayer, MapResponseLayer, ServiceExt};
+use tower::Layer;
+use traitreg::tower::{BoxedLayer, LayerFactory, RegisteredLayers};
+
+trait Plugin: LayerFactory<Vec<&'static str>, Vec<&'static str>, Infallible> {}
+
+#[derive(Default)]
+struct Outer;
+
+impl LayerFactory<Vec<&'static str>, Vec<&'static str>, Infallible> for Outer {
+    fn layer(&self) -> BoxedLayer<Vec<&'static str>, Vec<&'static str>, Infallible> {
+        BoxCloneSyncServiceLayer::new(MapRequestLayer::new(|mut request: Vec<&'static str>| {
+            request.push("outer");
+            request
+        }))
+    }
+}
+
+#[traitreg::register(default, priority = 10)]
+impl Plugin for Outer {}
+
+#[derive(Default)]
+struct Inner;
+
+impl LayerFactory<Vec<&'static str>, Vec<&'static str>, Infallible> for Inner {
+    fn layer(&self) -> BoxedLayer<Vec<&'static str>, Vec<&'static str>, Infallible> {
+        BoxCloneSyncServiceLayer::new(MapResponseLayer::new(|mut response: Vec<&'static str>| {
+            response.push("inner response");
+            response
+        }))
+    }
+}
+
+#[traitreg::register(default)]
+impl Plugin for Inner {}
+
+#[traitreg::registry(Plugin)]
+static PLUGIN_REGISTRY: () = ();
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("Expected future to be ready"),
+    }
+}
+
+#[test]
+fn main() {
+    let layers = RegisteredLayers::new(&PLUGIN_REGISTRY);
+    assert_eq!(2, layers.len());
+
+    let service = layers.layer(tower::service_fn(
+        |mut request: Vec<&'static str>| async move {
+            request.push("service");
+            Ok::<_, Infallible>(request)
+        },
+    ));
+
+    let response = block_on(service.clone().oneshot(vec![])).unwrap();
+    assert_eq!(vec!["outer", "service", "inner response"], response);
+}
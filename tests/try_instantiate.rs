@@ -0,0 +1,87 @@
+use std::error::Error;
+use traitreg::InstantiateError;
+
+trait Storage {}
+
+#[derive(Default)]
+struct Memory;
+
+#[traitreg::register(default)]
+impl Storage for Memory {}
+
+struct Disk;
+
+impl Disk {
+    fn mount() -> Result<Self, &'static str> {
+        Err("no disk")
+    }
+}
+
+#[traitreg::register(mount)]
+impl Storage for Disk {}
+
+struct Remote;
+
+#[traitreg::register]
+impl Storage for Remote {}
+
+#[traitreg::registry(Storage)]
+static STORAGE_REGISTRY: () = ();
+
+fn get(name: &str) -> &'static traitreg::RegisteredImplWrapper<Box<dyn Storage>> {
+    STORAGE_REGISTRY
+        .iter()
+        .find(|item| item.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn main() {
+    assert!(get("Memory").try_instantiate().is_ok());
+
+    let Err(error) = get("Disk").try_instantiate() else {
+        panic!("Expected the constructor to fail");
+    };
+    assert_eq!(
+        "Constructor of implementation 'Disk' of 'Storage' failed: no disk",
+        error.to_string()
+    );
+    assert_eq!("no disk", error.source().unwrap().to_string());
+    assert!(matches!(
+        error,
+        InstantiateError::ConstructorFailed {
+            name: "Disk",
+            trait_name: "Storage",
+            ..
+        }
+    ));
+
+    let Err(error) = get("Remote").try_instantiate() else {
+        panic!("Expected no constructor");
+    };
+    assert_eq!(
+        "Implementation 'Remote' of 'Storage' was not registered with a constructor",
+        error.to_string()
+    );
+    assert!(error.source().is_none());
+    assert!(matches!(
+        error,
+        InstantiateError::NoConstructor {
+            name: "Remote",
+            trait_name: "Storage"
+        }
+    ));
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics() {
+    let memory = get("Memory");
+    let count = memory.instantiation_count();
+    memory.try_instantiate().ok().unwrap();
+    assert_eq!(count + 1, memory.instantiation_count());
+
+    let disk = get("Disk");
+    assert!(disk.try_instantiate().is_err());
+    assert_eq!(0, disk.instantiation_count());
+}
@@ -0,0 +1,55 @@
+mod host {
+    pub trait Plugin {}
+
+    #[traitreg::registry(dyn Plugin + Send)]
+    pub static PLUGIN_REGISTRY: () = ();
+}
+
+mod plugins {
+    // Registrations are matched by the trait name as written
+    pub use crate::host::Plugin as HostPlugin;
+
+    pub struct Claimed;
+
+    #[traitreg::register]
+    impl crate::host::Plugin for Claimed {}
+
+    pub struct Renamed;
+
+    #[traitreg::register]
+    impl HostPlugin for Renamed {}
+
+    pub struct NotSend(core::marker::PhantomData<std::rc::Rc<()>>);
+
+    #[traitreg::register]
+    impl crate::host::Plugin for NotSend {}
+}
+
+trait Unused {}
+
+struct NoRegistry;
+
+#[traitreg::register]
+impl Unused for NoRegistry {}
+
+#[test]
+fn main() {
+    assert_eq!(1, host::PLUGIN_REGISTRY.len());
+
+    let mut unclaimed = traitreg::unclaimed_registrations();
+    unclaimed.sort_by_key(|item| item.name);
+    let names: Vec<_> = unclaimed
+        .iter()
+        .map(|item| (item.name, item.trait_name))
+        .collect();
+    assert_eq!(
+        vec![
+            ("NoRegistry", "Unused"),
+            ("NotSend", "Plugin"),
+            ("Renamed", "HostPlugin")
+        ],
+        names
+    );
+    assert_eq!("unclaimed_registrations::plugins", unclaimed[2].module_path);
+    assert!(unclaimed[2].file.ends_with("unclaimed_registrations.rs"));
+}
@@ -0,0 +1,67 @@
+trait Named {
+    fn name(&self) -> &'static str;
+}
+
+trait Versioned {
+    fn version(&self) -> u32;
+}
+
+trait Plugin: Named + Versioned {}
+
+#[derive(Default)]
+struct Audio;
+
+#[traitreg::register(default)]
+impl Named for Audio {
+    fn name(&self) -> &'static str {
+        "audio"
+    }
+}
+
+impl Versioned for Audio {
+    fn version(&self) -> u32 {
+        2
+    }
+}
+
+#[traitreg::register(default)]
+impl Plugin for Audio {}
+
+#[derive(Default)]
+struct Logger;
+
+#[traitreg::register(default)]
+impl Named for Logger {
+    fn name(&self) -> &'static str {
+        "logger"
+    }
+}
+
+#[traitreg::registry(Plugin, upcast = Named, upcast = Versioned)]
+static PLUGIN_REGISTRY: () = ();
+
+#[traitreg::registry(dyn Plugin + Send + Sync, upcast = Named)]
+static SEND_PLUGIN_REGISTRY: () = ();
+
+#[traitreg::registry(Named)]
+static NAMED_REGISTRY: () = ();
+
+#[test]
+fn main() {
+    let audio = PLUGIN_REGISTRY.iter().next().unwrap();
+    let named: Box<dyn Named> = audio.instantiate_as::<dyn Named>().unwrap();
+    assert_eq!("audio", named.name());
+    let versioned: Box<dyn Versioned> = audio.instantiate_as::<dyn Versioned>().unwrap();
+    assert_eq!(2, versioned.version());
+
+    let audio = SEND_PLUGIN_REGISTRY.iter().next().unwrap();
+    let named: Box<dyn Named + Send + Sync> = audio.instantiate_as().unwrap();
+    assert_eq!("audio", named.name());
+
+    // Entries of the base trait registry which are also in the subtrait registry
+    let plugins: Vec<_> = NAMED_REGISTRY
+        .common_types(&PLUGIN_REGISTRY)
+        .map(|(named, _)| named.name())
+        .collect();
+    assert_eq!(vec!["Audio"], plugins);
+}
@@ -0,0 +1,44 @@
+trait Plugin {}
+
+#[traitreg::registry(Plugin, warn_orphans)]
+static PLUGIN_REGISTRY: () = ();
+
+trait Codec {}
+
+#[traitreg::registry(Codec, warn_orphans)]
+static CODEC_REGISTRY: () = ();
+
+struct Gzip;
+
+#[traitreg::register]
+impl Codec for Gzip {}
+
+trait Unused {}
+
+struct Orphan;
+
+#[traitreg::register]
+impl Unused for Orphan {}
+
+#[test]
+fn main() {
+    assert!(PLUGIN_REGISTRY.is_empty());
+    assert_eq!(1, CODEC_REGISTRY.len());
+
+    // The warnings are printed when the test binary starts, so run it again to capture them
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .arg("--list")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let warnings: Vec<_> = stderr
+        .lines()
+        .filter(|line| line.starts_with("traitreg: warning:"))
+        .collect();
+
+    // Printed once, even though two registries use `warn_orphans`
+    assert_eq!(2, warnings.len(), "{stderr}");
+    assert!(warnings.iter().any(|line| line.contains("`Orphan` implements `Unused`")));
+    assert!(warnings.contains(&"traitreg: warning: registry of `Plugin` has no implementations"));
+}
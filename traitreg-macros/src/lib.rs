@@ -1,6 +1,8 @@
-use quote::quote;
+use quote::{quote, quote_spanned, ToTokens};
 use syn::parse::{Parse, ParseStream};
-use syn::Ident;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Ident, Token};
 
 /// Register an implementation of a trait on a concrete type.
 ///
@@ -32,141 +34,2082 @@ use syn::Ident;
 /// #[traitreg::register(new)]
 /// impl MyTrait for MyOtherType {}
 /// ```
+///
+/// Constructors can also be fallible, returning `Result<Self, E>` for any error which converts
+/// into `Box<dyn Error + Send + Sync>`. Instantiating an implementation whose constructor fails
+/// returns `None`, see `TraitRegStorage::instantiate_first_ok` for falling back to the next
+/// implementation.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// struct MyType;
+/// impl MyType {
+///     fn connect() -> Result<Self, std::io::Error> { Ok(Self) }
+/// }
+///
+/// #[traitreg::register(connect)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// With `auto`, the constructor is detected at compile time. Types with an associated function
+/// `new` taking no arguments, which returns `Self` or `Result<Self, E>`, are constructed with it,
+/// otherwise types which implement `Default` with `default`, and other types are registered without
+/// a constructor.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// #[traitreg::register(auto)]
+/// impl MyTrait for MyType {}
+///
+/// struct MyOtherType;
+///
+/// #[traitreg::register(auto)]
+/// impl MyTrait for MyOtherType {}
+///
+/// #[traitreg::registry(MyTrait)]
+/// static MYTRAIT_REGISTRY: () = ();
+///
+/// assert_eq!(1, MYTRAIT_REGISTRY.instantiate_all().count());
+/// ```
+///
+/// A guard function with the signature `fn() -> bool` can be provided, which is called during
+/// registration, before the first registry is built. If the guard returns `false` the
+/// implementation is not registered.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// fn is_supported() -> bool {
+///     true
+/// }
+///
+/// #[traitreg::register(default, guard = is_supported)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Registration can be limited to when a feature of the current crate is enabled. The impl block
+/// itself is always compiled.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// #[traitreg::register(feature = "extra-codecs")]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// The register attribute can also be applied with `cfg_attr`, so a library can make registering
+/// its implementations optional. When the predicate is false nothing is registered, and no
+/// registration statics are generated.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// #[cfg_attr(feature = "plugins", traitreg::register(default))]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Registrations are submitted before `main()`, in an order which depends on how the program is
+/// linked, and their guards run in that order when the first registry is built. With
+/// `init_priority`, from 101 to 19999, registrations with lower values are submitted first, even
+/// across crates, e.g. so a guard can depend on another registration. The default is 10000.
+/// Constructors are not ordered on apple platforms, so this has no effect there.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// #[traitreg::register(init_priority = 9000)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// A `priority` can be given to order implementations, for example in a
+/// `traitreg::pipeline::Pipeline`. Higher priorities come first, the default is `0`.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// #[traitreg::register(priority = 10)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Capabilities provided by an implementation can be declared with `capabilities`, so hosts can
+/// select only the implementations which provide the capabilities they require, see
+/// `TraitRegStorage::compatible_with`.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// #[traitreg::register(capabilities = ["json", "streaming"])]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Implementations can be tagged with `tags`, for selecting them with `TraitRegStorage::query`.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// #[traitreg::register(tags = ["stable"])]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Implementations can be assigned to an initialization `phase`, so infrastructure is constructed
+/// before the implementations which depend on it, see `TraitRegStorage::instantiate_phases`.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// #[traitreg::register(phase = core)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Implementations can be registered in a `namespace`, so they are only included in registries
+/// declared with the same namespace, see [registry](macro@registry). This keeps subsystems which
+/// each define a trait with the same name, e.g. `Plugin`, out of each other's registries.
+///
+/// ```rust
+/// trait Plugin {}
+/// struct MyType;
+///
+/// #[traitreg::register(namespace = "imagecodecs")]
+/// impl Plugin for MyType {}
+/// ```
+///
+/// Implementations which depend on others can name them with `after`, so they are ordered after
+/// them by `TraitRegStorage::iter_topological`.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// #[traitreg::register(after("LoggerPlugin"))]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Implementations which cannot be used together can name each other with `conflicts_with`, by the
+/// type path as written in their impl block. Registries built with conflicting implementations
+/// handle them with their failure policy, printing an error by default, see
+/// `TraitRegStorage::conflicts`.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// #[traitreg::register(conflicts_with("OtherType"))]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Boolean associated consts of the registered type can be recorded with `flags`, so they can be
+/// checked with `RegisteredImplWrapper::flag` without instantiating the implementation.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// impl MyType {
+///     const SUPPORTS_STREAMING: bool = true;
+/// }
+///
+/// #[traitreg::register(flags(SUPPORTS_STREAMING))]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// More generally, the values of associated consts of any type can be recorded with `expose`, and
+/// read with `RegisteredImplWrapper::exposed`.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// impl MyType {
+///     const NAME: &'static str = "my-type";
+///     const VERSION: (u32, u32) = (1, 2);
+/// }
+///
+/// #[traitreg::register(default, expose(NAME, VERSION))]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// With `any`, the constructor is also stored returning `Box<dyn Any>`, so instances can be
+/// downcast to the registered type without `Any` as a supertrait of the trait.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// #[traitreg::register(default, any)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// A `shutdown` function with the signature `fn()` can be provided, which is called by
+/// `traitreg::shutdown_all` in the reverse order of priority.
+///
+/// ```rust
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// fn close_connections() {}
+///
+/// #[traitreg::register(shutdown = close_connections)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// A `health_check` function with the signature `fn() -> traitreg::health::HealthStatus` can be
+/// provided, which is called by `TraitRegStorage::health_check_all` to report the health of the
+/// implementation without instantiating it.
+///
+/// ```rust
+/// use traitreg::health::HealthStatus;
+///
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// fn check_connection() -> HealthStatus {
+///     HealthStatus::Healthy
+/// }
+///
+/// #[traitreg::register(health_check = check_connection)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Constructors can take arguments with `args`, for registries which declare the constructor
+/// arguments, see [registry](macro@registry). These constructors are only called by
+/// `RegisteredImplWrapper::instantiate_args`.
+///
+/// ```rust
+/// # struct Opts;
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// impl MyType {
+///     fn new(_name: &str, _opts: &Opts) -> Self {
+///         Self
+///     }
+/// }
+///
+/// #[traitreg::register(new, args)]
+/// impl MyTrait for MyType {}
+///
+/// # #[traitreg::registry(MyTrait, args = (&str, &Opts))]
+/// # static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// Types which need asynchronous setup after they are constructed can implement
+/// `traitreg::AsyncInit` and be registered with `async_init`. Their constructors are only called
+/// by `TraitRegStorage::init_all` and `RegisteredImplWrapper::instantiate_async`, which await
+/// the `init` hook before returning the instance.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// impl traitreg::AsyncInit for MyType {
+///     type Error = std::io::Error;
+///
+///     async fn init(&mut self) -> Result<(), Self::Error> {
+///         Ok(())
+///     }
+/// }
+///
+/// #[traitreg::register(default, async_init)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// A `factory` function can be provided for registries which declare a factory signature, see
+/// [registry](macro@registry). A function with another signature is a compile time error.
+///
+/// ```rust
+/// # struct Env;
+/// trait MyTrait {}
+/// struct MyType;
+///
+/// fn create(_env: &Env) -> Box<dyn MyTrait> {
+///     Box::new(MyType)
+/// }
+///
+/// #[traitreg::register(factory = create)]
+/// impl MyTrait for MyType {}
+///
+/// # #[traitreg::registry(MyTrait, factory = fn(&Env) -> Box<dyn MyTrait>)]
+/// # static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// Other attribute macros on the same item, such as `#[async_trait]`, can be written above or
+/// below the register attribute. Attribute macros below it are always expanded first, so the
+/// registration is generated from the expanded item, and an item which another attribute removes
+/// is not registered.
+///
+/// ```rust,ignore
+/// #[traitreg::register(default)]
+/// #[async_trait::async_trait]
+/// impl MyTrait for MyType {
+///     async fn run(&self) {}
+/// }
+/// ```
+///
+/// Each implementation can only be registered once. Registering the same implementation twice is
+/// a compile time error, since the generated items conflict.
+///
+/// Implementations of traits which are not dyn compatible can be registered with `meta_only`.
+/// Only metadata is stored, these implementations can never be instantiated. See
+/// [registry](macro@registry) for creating a registry of these implementations. Registering these
+/// implementations without `meta_only` fails with `error[E0038]: the trait is not dyn compatible`
+/// pointing at the trait in the impl block.
+///
+/// ```rust
+/// trait MyTrait {
+///     fn generic<T>(&self, value: T);
+/// }
+///
+/// struct MyType;
+///
+/// #[traitreg::register(meta_only)]
+/// impl MyTrait for MyType {
+///     fn generic<T>(&self, _value: T) {}
+/// }
+/// ```
+///
+/// With the `di` feature, constructors can take a resolver with the signature
+/// `fn(&traitreg::di::Resolver) -> Self` using `inject`, to resolve their own dependencies. These
+/// constructors are only called through a `traitreg::di::Container`, and can also return
+/// `Result<Self, traitreg::di::ResolveError>` to pass on errors resolving their dependencies.
+///
+/// ```rust,ignore
+/// #[traitreg::register(new, inject)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// The `lifetime` of instances resolved through a `traitreg::di::Container` can be `transient`,
+/// the default, `singleton` or `scoped`.
+///
+/// ```rust,ignore
+/// #[traitreg::register(new, lifetime = singleton)]
+/// impl MyTrait for MyType {}
+/// ```
+///
+/// Free functions can be registered as implementations of a trait which is implemented for
+/// functions, by naming the trait in the attribute. Instances are the function itself, so no
+/// struct is needed for each function. Functions can also be given a `guard`, `feature`,
+/// `priority`, `init_priority`, `capabilities`, `tags`, `phase`, `namespace`, `after`,
+/// `conflicts_with`, `shutdown` and `health_check`.
+///
+/// ```rust
+/// trait Handler {
+///     fn handle(&self, request: &str) -> String;
+/// }
+///
+/// impl<F: Fn(&str) -> String> Handler for F {
+///     fn handle(&self, request: &str) -> String {
+///         self(request)
+///     }
+/// }
+///
+/// #[traitreg::register(Handler, priority = 1)]
+/// fn echo(request: &str) -> String {
+///     request.to_string()
+/// }
+///
+/// #[traitreg::registry(Handler)]
+/// static HANDLER_REGISTRY: () = ();
+///
+/// let handler = HANDLER_REGISTRY.iter().next().unwrap().instantiate().unwrap();
+/// assert_eq!("hi", handler.handle("hi"));
+/// ```
+#[proc_macro_attribute]
+pub fn register(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    // Attribute macros below this one would expand after the registration is generated, and could
+    // change or remove the item, so expand them first. An attribute which was already deferred is
+    // marked, so it is only deferred once.
+    let attr = match strip_deferred_marker(&attr) {
+        Some(attr) => attr,
+        None => match defer_register_attr(&attr, &item) {
+            Some(deferred) => return deferred,
+            None => attr,
+        },
+    };
+
+    // Functions are registered through an adapter, see `register_fn`
+    if let Ok(item_fn) = syn::parse::<syn::ItemFn>(item.clone()) {
+        let register_fn_attr = syn::parse_macro_input!(attr as RegisterFnAttribute);
+        return register_fn(register_fn_attr, item_fn);
+    }
+
+    // Read custom / default constructor and other options from attribute if they exist
+    let register_attr = syn::parse_macro_input!(attr as RegisterAttribute);
+    let item_clone = item.clone();
+    let item_impl = syn::parse_macro_input!(item as RegisterItem).item;
+
+    // The original item comes first, followed by the registration
+    match impl_registration(register_attr, item_impl) {
+        Ok(registration) => {
+            let mut result = item_clone;
+            result.extend(proc_macro::TokenStream::from(registration));
+            result
+        }
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+// Registration of an implementation, without the impl block itself
+fn impl_registration(
+    register_attr: RegisterAttribute,
+    item_impl: syn::ItemImpl,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let misuse = |options: &[&str], message: &str| {
+        let span = register_attr
+            .option_span(options)
+            .unwrap_or_else(proc_macro2::Span::call_site);
+        Err(syn::Error::new(span, message))
+    };
+
+    if register_attr.meta_only && register_attr.constructor_fn_ident.is_some() {
+        return misuse(
+            &["meta_only"],
+            "Cannot register a constructor for a meta_only implementation.",
+        );
+    }
+
+    if register_attr.inject && register_attr.constructor_fn_ident.is_none() {
+        return misuse(&["inject"], "Cannot inject dependencies without a constructor.");
+    }
+
+    if register_attr.args && register_attr.constructor_fn_ident.is_none() {
+        return misuse(&["args"], "Cannot pass arguments without a constructor.");
+    }
+
+    if register_attr.args && register_attr.inject {
+        return misuse(
+            &["args"],
+            "Cannot pass arguments to a constructor which injects dependencies.",
+        );
+    }
+
+    if register_attr.any
+        && (register_attr.constructor_fn_ident.is_none()
+            || register_attr.inject
+            || register_attr.args)
+    {
+        return misuse(
+            &["any"],
+            "Cannot instantiate as Any without a constructor which takes no arguments.",
+        );
+    }
+
+    if register_attr.auto
+        && (register_attr.constructor_fn_ident.is_some()
+            || register_attr.meta_only
+            || register_attr.inject
+            || register_attr.args
+            || register_attr.any
+            || register_attr.async_init)
+    {
+        return misuse(
+            &["auto"],
+            "Cannot detect the constructor with auto together with a registered constructor, \
+            meta_only, inject, args, any or async_init.",
+        );
+    }
+
+    if register_attr.async_init && register_attr.constructor_fn_ident.is_none() {
+        return misuse(
+            &["async_init"],
+            "Cannot initialize asynchronously without a constructor.",
+        );
+    }
+
+    if register_attr.async_init
+        && (register_attr.inject || register_attr.args || register_attr.any)
+    {
+        return misuse(
+            &["async_init"],
+            "Implementations initialized asynchronously can only be instantiated by init_all, \
+            they cannot be injected, take arguments or be instantiated as Any.",
+        );
+    }
+
+    let option_consts = option_consts(&register_attr);
+
+    let any_const = register_attr
+        .constructor_fn_ident
+        .as_ref()
+        .filter(|_| register_attr.any)
+        .map(|ident| {
+            quote! {
+                const INSTANTIATE_ANY: Option<traitreg::__private::InstantiateAny> =
+                    Some(|| {
+                        <_ as traitreg::__private::Constructed<Self>>::into_result(Self::#ident())
+                            .ok()
+                            .map(|instance| Box::new(instance) as _)
+                    });
+            }
+        });
+
+    let type_path = get_self_type_path(&item_impl.self_ty);
+
+    // With `auto`, the constructor is `new` if the type has one which takes no arguments,
+    // otherwise `Default`, see `traitreg::__private::NoNew` and `traitreg::__private::AutoNewProbe`
+    let auto_scope = quote! {
+        trait __TraitRegAutoNew: Sized {
+            fn new() -> traitreg::__private::NoNew<Self> {
+                traitreg::__private::NoNew(core::marker::PhantomData)
+            }
+        }
+        impl<T> __TraitRegAutoNew for T {}
+        use traitreg::__private::{NoDefault as _, ProbeNew as _, ProbeNoNew as _};
+    };
+    let auto_new = |self_type: &proc_macro2::TokenStream| {
+        quote! {
+            (&traitreg::__private::AutoNewProbe::<#self_type, _>(
+                <#self_type>::new,
+                core::marker::PhantomData,
+            ))
+            .__new()
+        }
+    };
+    let auto_constructor = register_attr.auto.then(|| {
+        let new = auto_new(&quote! { Self });
+        quote! {{
+            #auto_scope
+            traitreg::__private::AutoConstructed::<Self>::into_auto(
+                #new,
+                <traitreg::__private::AutoDefault<Self>>::DEFAULT,
+            )
+        }}
+    });
+
+    let registered_constructor = register_attr.constructor_fn_ident.is_some();
+    let emplace_constructor = register_attr.constructor_fn_ident.clone();
+    let has_constructor = if register_attr.auto {
+        let auto_new_type_path = auto_new(&type_path.to_token_stream());
+        quote! {{
+            #auto_scope
+            traitreg::__private::has_auto_constructor::<#type_path, _, _>(
+                &|| #auto_new_type_path,
+                <traitreg::__private::AutoDefault<#type_path>>::DEFAULT,
+            )
+        }}
+    } else {
+        quote! { #registered_constructor }
+    };
+
+    // Constructors taking a resolver are only called by the DI container, see `traitreg::di`
+    // Constructors taking arguments are only called by `instantiate_args`
+    let args_fn_ident = register_attr
+        .constructor_fn_ident
+        .clone()
+        .filter(|_| register_attr.args);
+    // Constructors of types initialized asynchronously are only called by `init_all`
+    let async_init_fn_ident = register_attr
+        .constructor_fn_ident
+        .clone()
+        .filter(|_| register_attr.async_init);
+    let (constructor_fn_call_str, inject_fn_call_str) = match register_attr.constructor_fn_ident {
+        Some(_) if register_attr.args || register_attr.async_init => (quote! { None }, None),
+        Some(ident) if register_attr.inject => (
+            quote! { None },
+            Some(quote! {
+                traitreg::di::__IntoResolved::<Self>::__into_resolved(Self::#ident(resolver))
+                    .map(|instance| Box::new(instance) as _)
+            }),
+        ),
+        Some(ident) => (
+            quote! {
+                Some(
+                    <_ as traitreg::__private::Constructed<Self>>::into_result(Self::#ident())
+                        .map(|instance| Box::new(instance) as _)
+                )
+            },
+            None,
+        ),
+        None => match auto_constructor {
+            Some(ref auto_constructor) => (
+                quote! {
+                    #auto_constructor.map(|result| result.map(|instance| Box::new(instance) as _))
+                },
+                None,
+            ),
+            None => (quote! { None }, None),
+        },
+    };
+
+    // Registering the same implementation twice would fail with conflicting implementations of
+    // the generated items, so report duplicate attributes directly
+    if let Some(duplicate) = item_impl.attrs.iter().find(|attr| is_register_attr(attr)) {
+        return Err(syn::Error::new_spanned(
+            duplicate,
+            "Implementation is already registered, remove the duplicate register attribute.",
+        ));
+    }
+
+    // Any cfg attributes on the impl block also apply to the generated registration
+    let cfg_attrs: Vec<&syn::Attribute> = item_impl
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect();
+    let mut registration_cfg = quote! { #(#cfg_attrs)* };
+    if let Some(feature) = register_attr.feature {
+        registration_cfg.extend(quote! { #[cfg(feature = #feature)] });
+    }
+
+    let (trait_not, trait_path, _) = item_impl
+        .trait_
+        .expect("Can only register an implementation of a trait, 'impl <Trait> for <Type>'.");
+    assert!(
+        trait_not.is_none(),
+        "Cannot register inverted impl trait: 'impl !Trait for Type'."
+    );
+
+    let trait_ident = &trait_path
+        .segments
+        .last()
+        .expect("Expected trait in impl block to have an identifier.")
+        .ident;
+    let trait_name = format!("{trait_ident}");
+
+    // Trait objects of traits with associated types name them, e.g.
+    // `dyn Handler<Output = Response>`, so the bindings are taken from the impl block
+    let bindings: Vec<proc_macro2::TokenStream> = item_impl
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::ImplItem::Type(item) if item.generics.params.is_empty() => {
+                let ident = &item.ident;
+                let ty = &item.ty;
+                Some(quote! { #ident = #ty })
+            }
+            _ => None,
+        })
+        .collect();
+    let object_trait = bind_associated_types(&trait_path, &bindings);
+    let bindings_const = (!bindings.is_empty() || has_bindings(&trait_path)).then(|| {
+        quote! {
+            const BINDINGS: Option<fn() -> core::any::TypeId> =
+                Some(core::any::TypeId::of::<Box<dyn #object_trait>>);
+        }
+    });
+
+    let type_segment = type_path
+        .segments
+        .last()
+        .expect("Expected type in impl block to have an identifier.");
+    let type_name = format!(
+        "{}{}",
+        type_segment.ident,
+        type_segment.arguments.to_token_stream()
+    )
+    .replace(' ', "");
+
+    // The impl block header as written, including generic parameters and bounds
+    let impl_generics = &item_impl.generics;
+    let where_clause = &impl_generics.where_clause;
+    let self_ty = &item_impl.self_ty;
+    let signature =
+        signature_string(quote! { impl #impl_generics #trait_path for #self_ty #where_clause });
+
+    // Generated identifiers include the full type and trait paths, so implementations for types or
+    // traits with the same name from different modules do not conflict
+    let registration_ident = registration_ident(&[
+        &type_path.to_token_stream().to_string(),
+        &trait_path.to_token_stream().to_string(),
+    ]);
+
+    // Implementations registered as meta_only use a marker type, since the type itself could be
+    // registered as meta_only for multiple traits
+    let (instance_type, registered_type, meta_only_marker) = if register_attr.meta_only {
+        let marker_ident =
+            syn::parse_str::<syn::Ident>(format!("{}__MetaOnly", registration_ident).as_ref())
+                .expect("Unable to create identifier");
+        let marker = quote! {
+            #registration_cfg
+            #[allow(non_camel_case_types)]
+            struct #marker_ident;
+        };
+        (quote! { traitreg::MetaOnly }, quote! { #marker_ident }, marker)
+    } else {
+        // Spanned to the trait in the impl block, so errors for traits which are not dyn compatible
+        // point at the users code rather than the macro invocation
+        let instance_type = quote_spanned! {trait_path.span()=> Box<dyn #object_trait> };
+        (instance_type, quote! { #type_path }, quote! {})
+    };
+
+    // Constructs the type into memory provided by an arena, see `traitreg::arena`
+    let emplace_const = emplace_constructor
+        .filter(|_| !register_attr.inject && !register_attr.args && !register_attr.async_init)
+        .map(|ident| {
+            quote! {
+                <_ as traitreg::__private::Constructed<Self>>::into_result(Self::#ident())
+            }
+        })
+        .or_else(|| {
+            auto_constructor.map(|auto_constructor| {
+                quote! {
+                    match #auto_constructor {
+                        Some(result) => result,
+                        None => return false,
+                    }
+                }
+            })
+        })
+        .map(|constructed| {
+            quote! {
+                const EMPLACE: Option<traitreg::__private::Emplace> = if #has_constructor {
+                    Some(traitreg::__private::Emplace {
+                        layout: core::alloc::Layout::new::<Self>(),
+                        write: |ptr, object| unsafe {
+                            let ptr = ptr as *mut Self;
+                            match #constructed {
+                                Ok(instance) => {
+                                    ptr.write(instance);
+                                    *(object as *mut *mut dyn #object_trait) = ptr;
+                                    true
+                                }
+                                Err(_) => false,
+                            }
+                        },
+                    })
+                } else {
+                    None
+                };
+            }
+        });
+
+    // Coerced to the signature declared by the registry, so a function with another signature is a
+    // compile time error
+    let factory_const = register_attr.factory_fn.as_ref().map(|factory_fn| {
+        let factory = quote_spanned! {factory_fn.span()=>
+            <dyn #object_trait as traitreg::Factory>::__erase(#factory_fn)
+        };
+        quote! {
+            const FACTORY: Option<fn() -> traitreg::__private::ErasedFactory> = Some(|| #factory);
+        }
+    });
+
+    // Coerced to the arguments declared by the registry, like `factory`
+    let args_const = args_fn_ident.map(|ident| {
+        let constructor = quote_spanned! {ident.span()=>
+            <dyn #object_trait as traitreg::ConstructorArgs>::__erase(|args| {
+                Some(
+                    <_ as traitreg::__private::Constructed<Self>>::into_result(
+                        traitreg::__private::CallArgs::call_args(&Self::#ident, args),
+                    )
+                    .map(|instance| Box::new(instance) as _),
+                )
+            })
+        };
+        quote! {
+            const INSTANTIATE_ARGS: Option<fn() -> traitreg::__private::ErasedFactory> =
+                Some(|| #constructor);
+        }
+    });
+
+    let async_init_const = async_init_fn_ident.map(|ident| {
+        quote! {
+            const INIT_ASYNC: Option<traitreg::__private::InitAsync<#instance_type>> =
+                Some(|| {
+                    Box::pin(async {
+                        let mut instance =
+                            <_ as traitreg::__private::Constructed<Self>>::into_result(Self::#ident())?;
+                        <Self as traitreg::AsyncInit>::init(&mut instance)
+                            .await
+                            .map_err(traitreg::ConstructError::new)?;
+                        let instance: #instance_type = Box::new(instance);
+                        Ok::<_, traitreg::ConstructError>(instance)
+                    })
+                });
+        }
+    });
+
+    let inject_const = inject_fn_call_str.map(|inject_fn_call_str| {
+        quote! {
+            const INJECT: Option<traitreg::di::__InjectFn<#instance_type>> =
+                Some(|resolver| { #inject_fn_call_str });
+        }
+    });
+
+    // Associated consts of the registered type, not the trait, which would not be dyn compatible
+    let flags_const = register_attr.flags.as_ref().map(|flags| {
+        let names = flags.iter().map(|flag| flag.to_string());
+        quote! {
+            const FLAGS: &'static [(&'static str, bool)] = &[#((#names, <#type_path>::#flags)),*];
+        }
+    });
+
+    let exposed_const = register_attr.exposed.as_ref().map(|exposed| {
+        let names = exposed.iter().map(|name| name.to_string());
+        quote! {
+            const EXPOSED: &'static [traitreg::__private::Exposed] =
+                &[#((#names, &<#type_path>::#exposed)),*];
+        }
+    });
+
+    let lifetime_const = register_attr.lifetime.map(|lifetime| {
+        quote! {
+            const LIFETIME: traitreg::di::Lifetime = traitreg::di::Lifetime::#lifetime;
+        }
+    });
+
+    let register_call = quote! {
+        traitreg::__private::register_impl::<#instance_type, #registered_type>(registry);
+    };
+    let register_call = if let Some(guard_fn) = register_attr.guard_fn {
+        quote! {
+            if #guard_fn() {
+                #register_call
+            }
+        }
+    } else {
+        register_call
+    };
+
+    let registration_static = registration_static(
+        &registration_ident,
+        &registration_cfg,
+        &register_call,
+        &Registered {
+            type_name: &type_name,
+            type_path: type_path.to_token_stream(),
+            trait_name: &trait_name,
+            has_constructor: has_constructor.clone(),
+        },
+        register_attr.init_priority,
+    );
+
+    Ok(quote! {
+        #meta_only_marker
+
+        #registration_cfg
+        unsafe impl traitreg::__private::RegisteredImpl<#instance_type> for #registered_type {
+            const INSTANTIATE: fn() -> Option<Result<#instance_type, traitreg::ConstructError>> = || { #constructor_fn_call_str };
+            const HAS_CONSTRUCTOR: bool = #has_constructor;
+            const NAME: &'static str = #type_name;
+            const PATH: &'static str = stringify!(#type_path);
+            const SIGNATURE: &'static str = #signature;
+            const FILE: &'static str = core::file!() ;
+            const MODULE_PATH: &'static str = core::module_path!();
+            const CRATE_NAME: &'static str = env!("CARGO_PKG_NAME");
+            const CRATE_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+            const TRAIT_NAME: &'static str = #trait_name;
+            const AUTO_TRAITS: fn() -> traitreg::__private::AutoTraits = || {
+                use traitreg::__private::{ProbeNotSend, ProbeNotSync, ProbeSend, ProbeSync};
+                let probe = traitreg::__private::AutoTraitProbe::<#type_path>(core::marker::PhantomData);
+                traitreg::__private::AutoTraits {
+                    send: (&probe).__is_send(),
+                    sync: (&probe).__is_sync(),
+                }
+            };
+            const TYPE_ID: fn() -> core::any::TypeId = core::any::TypeId::of::<#type_path>;
+            #option_consts
+            #any_const
+            #emplace_const
+            #factory_const
+            #args_const
+            #async_init_const
+            #bindings_const
+            #flags_const
+            #exposed_const
+            #inject_const
+            #lifetime_const
+        }
+
+        #registration_static
+    })
+}
+
+/// Create a registry of implementations of a trait
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[traitreg::registry(MyTrait)]
+/// static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// Registries of traits which are not dyn compatible can be created with `meta_only`. These
+/// contain metadata for all registered implementations but can never instantiate them.
+///
+/// ```rust
+/// trait MyTrait {
+///     fn generic<T>(&self, value: T);
+/// }
+///
+/// #[traitreg::registry(MyTrait, meta_only)]
+/// static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// A free function instantiating implementations by name can be generated with `dispatch`, giving a
+/// simple entry point which does not use the storage API.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// #[traitreg::register(default)]
+/// impl MyTrait for MyType {}
+///
+/// #[traitreg::registry(MyTrait, dispatch = my_trait_dispatch)]
+/// static MYTRAIT_REGISTRY: () = ();
+///
+/// assert!(my_trait_dispatch("MyType").is_some());
+/// assert!(my_trait_dispatch("OtherType").is_none());
+/// ```
+///
+/// Instances can be upcast to trait objects of supertraits with `upcast`, which implements
+/// `traitreg::Upcast` for the trait object type. The registry trait must be defined in the current
+/// crate, and only one registry of each trait object type can use `upcast` for a supertrait.
+///
+/// ```rust
+/// trait Base {
+///     fn name(&self) -> &'static str;
+/// }
+///
+/// trait MyTrait: Base {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// impl Base for MyType {
+///     fn name(&self) -> &'static str {
+///         "MyType"
+///     }
+/// }
+///
+/// #[traitreg::register(default)]
+/// impl MyTrait for MyType {}
+///
+/// #[traitreg::registry(MyTrait, upcast = Base)]
+/// static MYTRAIT_REGISTRY: () = ();
+///
+/// let reg = MYTRAIT_REGISTRY.iter().next().unwrap();
+/// let base: Box<dyn Base> = reg.instantiate_as::<dyn Base>().unwrap();
+/// assert_eq!("MyType", base.name());
+/// ```
+///
+/// The trait object type can be specified in full, including auto traits. Only implementations on
+/// types which implement these auto traits are included.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[traitreg::registry(dyn MyTrait + Send + 'static)]
+/// static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// Generic traits are given their arguments and traits with associated types are given their
+/// bindings, as in the trait object type, e.g. `Codec<u8>`. Only implementations with the same
+/// arguments and associated types are included, which must be `'static`.
+///
+/// ```rust
+/// trait Handler {
+///     type Output;
+/// }
+///
+/// #[derive(Default)]
+/// struct MyHandler;
+///
+/// #[traitreg::register(default)]
+/// impl Handler for MyHandler {
+///     type Output = String;
+/// }
+///
+/// #[traitreg::registry(Handler<Output = String>)]
+/// static HANDLER_REGISTRY: () = ();
+///
+/// let handler: Box<dyn Handler<Output = String>> =
+///     HANDLER_REGISTRY.instantiate_all().next().unwrap();
+/// ```
+///
+/// `send` is a shorthand for adding `Send`, so instances can be moved to other threads, for example
+/// into `tokio::spawn`.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// #[traitreg::register(default)]
+/// impl MyTrait for MyType {}
+///
+/// #[traitreg::registry(MyTrait, send)]
+/// static MYTRAIT_REGISTRY: () = ();
+///
+/// let instance: Box<dyn MyTrait + Send> = MYTRAIT_REGISTRY.instantiate_all().next().unwrap();
+/// std::thread::spawn(move || drop(instance)).join().unwrap();
+/// ```
+///
+/// The visibility and attributes of the static are kept, so a library can expose a registry to the
+/// crates which depend on it. Implementations registered in any crate linked into the binary are
+/// included.
+///
+/// ```rust
+/// pub trait MyTrait {}
+///
+/// /// All implementations of `MyTrait`
+/// #[traitreg::registry(MyTrait)]
+/// pub static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// Giving the static a type creates a named handle type for the registry, which can be documented
+/// and passed around by value, rather than a hidden type.
+///
+/// ```rust
+/// pub trait MyTrait {}
+///
+/// #[traitreg::registry(MyTrait)]
+/// pub static MYTRAIT_REGISTRY: MyTraitRegistry = MyTraitRegistry;
+///
+/// fn count(registry: MyTraitRegistry) -> usize {
+///     registry.iter().count()
+/// }
+/// ```
+///
+/// Registries can be created for an alias of a trait object, given the definition of the alias,
+/// which is checked against the alias at compile time. Only implementations on types which
+/// implement the auto traits in the alias are included. For a type alias:
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// type MyObject = dyn MyTrait + Send + Sync;
+///
+/// #[traitreg::registry(MyObject = dyn MyTrait + Send + Sync)]
+/// static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// Or for a trait alias:
+///
+/// ```rust,ignore
+/// #![feature(trait_alias)]
+/// trait MyTrait {}
+///
+/// trait MyAlias = MyTrait + Send + Sync;
+///
+/// #[traitreg::registry(MyAlias = MyTrait + Send + Sync)]
+/// static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// Instances can be wrapped in the types the application uses for them with `wrap`, where `_` is
+/// the instance type, `Box<dyn MyTrait>`. Each wrapper is constructed with its `new` function.
+///
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+///
+/// trait MyTrait {
+///     fn increment(&mut self) -> u32;
+/// }
+///
+/// #[derive(Default)]
+/// struct MyType(u32);
+///
+/// #[traitreg::register(default)]
+/// impl MyTrait for MyType {
+///     fn increment(&mut self) -> u32 {
+///         self.0 += 1;
+///         self.0
+///     }
+/// }
+///
+/// #[traitreg::registry(MyTrait, send, wrap = Arc<Mutex<_>>)]
+/// static MYTRAIT_REGISTRY: () = ();
+///
+/// let instance: Arc<Mutex<Box<dyn MyTrait + Send>>> =
+///     MYTRAIT_REGISTRY.iter().next().unwrap().instantiate().unwrap();
+/// assert_eq!(1, instance.lock().unwrap().increment());
+/// ```
+///
+/// Implementations which need more than a constructor can be registered with a factory function,
+/// with a signature declared by the registry with `factory`. The signature is checked against the
+/// function given to `factory` on [register](macro@register), and the factories are returned by
+/// `RegisteredImplWrapper::factory`. As for `upcast`, the registry trait must be defined in the
+/// current crate, and only one registry of each trait can declare a factory signature.
+///
+/// ```rust
+/// struct Env {
+///     verbose: bool,
+/// }
+///
+/// trait MyTrait {
+///     fn verbose(&self) -> bool;
+/// }
+///
+/// struct MyType(bool);
+///
+/// fn create(env: &Env) -> Result<Box<dyn MyTrait>, String> {
+///     Ok(Box::new(MyType(env.verbose)))
+/// }
+///
+/// #[traitreg::register(factory = create)]
+/// impl MyTrait for MyType {
+///     fn verbose(&self) -> bool {
+///         self.0
+///     }
+/// }
+///
+/// #[traitreg::registry(MyTrait, factory = fn(&Env) -> Result<Box<dyn MyTrait>, String>)]
+/// static MYTRAIT_REGISTRY: () = ();
+///
+/// let factory = MYTRAIT_REGISTRY.iter().next().unwrap().factory().unwrap();
+/// assert!(factory(&Env { verbose: true }).unwrap().verbose());
+/// ```
+///
+/// The arguments of constructors registered with `args` are declared as a tuple with `args`.
+/// Instances are constructed with `RegisteredImplWrapper::instantiate_args`, and constructors with
+/// other arguments are a compile time error. The same restrictions as for `factory` apply.
+///
+/// ```rust
+/// struct Opts {
+///     verbose: bool,
+/// }
+///
+/// trait MyTrait {
+///     fn name(&self) -> &str;
+/// }
+///
+/// struct MyType(String);
+///
+/// impl MyType {
+///     fn new(name: &str, _opts: &Opts) -> Self {
+///         Self(name.to_string())
+///     }
+/// }
+///
+/// #[traitreg::register(new, args)]
+/// impl MyTrait for MyType {
+///     fn name(&self) -> &str {
+///         &self.0
+///     }
+/// }
+///
+/// #[traitreg::registry(MyTrait, args = (&str, &Opts))]
+/// static MYTRAIT_REGISTRY: () = ();
+///
+/// let opts = Opts { verbose: false };
+/// let reg = MYTRAIT_REGISTRY.iter().next().unwrap();
+/// assert_eq!("first", reg.instantiate_args(("first", &opts)).unwrap().name());
+/// ```
+///
+/// Registrations which are not in any registry, and registries with no implementations, are
+/// usually a mistake, such as a crate which is not linked in or a trait which is imported under
+/// another name. With `warn_orphans`, a warning is printed to stderr for each of them before
+/// `main()` starts. See also `traitreg::unclaimed_registrations`.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[traitreg::registry(MyTrait, warn_orphans)]
+/// static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// The order of implementations in a registry depends on how the program is linked, and can
+/// change between platforms or builds. With `sorted`, implementations are sorted by crate, module
+/// and type path instead, so the order only changes when the registered implementations do.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[traitreg::registry(MyTrait, sorted)]
+/// static MYTRAIT_REGISTRY: () = ();
+/// ```
+///
+/// With `eager`, every implementation with a constructor is instantiated when the registry is
+/// built, before `main()` starts, so there is no latency on first use for applications which need
+/// all of them anyway. Implementations which fail to construct are left out. The instances are
+/// returned by `TraitRegStorage::instances`, which requires them to be `Sync`. Constructors must
+/// not use other registries, which might not be built yet.
+///
+/// ```rust
+/// trait MyTrait: Sync {}
+///
+/// #[derive(Default)]
+/// struct MyType;
+///
+/// #[traitreg::register(default)]
+/// impl MyTrait for MyType {}
+///
+/// #[traitreg::registry(MyTrait, eager)]
+/// static MYTRAIT_REGISTRY: () = ();
+///
+/// assert_eq!(1, MYTRAIT_REGISTRY.instances().len());
+/// ```
+///
+/// Implementations registered in a `namespace` are only included in registries declared with the
+/// same namespace, and registries without a namespace only include implementations without one.
+/// Independent subsystems which each define a trait with the same name can use a namespace each,
+/// so their implementations never end up in each other's registries.
+///
+/// ```rust
+/// trait Plugin {}
+/// struct MyType;
+///
+/// #[traitreg::register(namespace = "imagecodecs")]
+/// impl Plugin for MyType {}
+///
+/// #[traitreg::registry(Plugin, namespace = "imagecodecs")]
+/// static IMAGECODECS_REGISTRY: () = ();
+///
+/// #[traitreg::registry(Plugin)]
+/// static PLUGIN_REGISTRY: () = ();
+///
+/// assert_eq!(1, IMAGECODECS_REGISTRY.len());
+/// assert!(PLUGIN_REGISTRY.is_empty());
+/// ```
+///
+/// Registrations which panic while being added, for example in a `guard`, are left out of the
+/// registry. `on_failure` sets what else happens when the registry is built, `log` to print an
+/// error to stderr, `skip` to only record the failure, or `panic` to abort the process before
+/// `main()`. Without it the global policy is used, see `traitreg::set_failure_policy`.
+///
+/// ```rust
+/// trait MyTrait {}
+///
+/// #[traitreg::registry(MyTrait, on_failure = skip)]
+/// static MYTRAIT_REGISTRY: () = ();
+/// ```
 #[proc_macro_attribute]
-pub fn register(
+pub fn registry(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    // Read custom / default constructor from attribute if it exists
-    let constructor_fn = if attr.is_empty() {
-        None
+    let registry_attr = syn::parse_macro_input!(attr as RegistryAttribute);
+    let registry_item = syn::parse_macro_input!(item as RegistryItem);
+
+    let trait_ident = registry_attr.trait_ident;
+    let object_type = registry_attr.object_type;
+    let item = registry_item.item;
+
+    let (instance_type, storage_new_fn) = if registry_attr.meta_only {
+        (quote! { traitreg::MetaOnly }, quote! { build_meta_only_registry })
     } else {
-        Some(syn::parse_macro_input!(attr as RegisterAttribute))
+        (quote! { Box<#object_type> }, quote! { build_registry::<Box<#object_type>> })
+    };
+
+    let AutoTraits { send, sync } = registry_attr.auto_traits;
+    let sorted = registry_attr.sorted;
+    let eager = registry_attr.eager;
+    let namespace = match &registry_attr.namespace {
+        Some(namespace) => quote! { Some(#namespace) },
+        None => quote! { None },
+    };
+    // Registrations with other generic arguments or associated types are left out, see
+    // `RegisteredImpl::BINDINGS`
+    let bindings = match &registry_attr.bindings {
+        Some(bindings) => quote! { Some(core::any::TypeId::of::<Box<dyn #bindings>>()) },
+        None => quote! { None },
     };
 
-    let has_constructor = constructor_fn.is_some();
-    let has_constructor = quote! { #has_constructor };
+    // Options which need constructors, which a meta_only registry does not have
+    if registry_attr.meta_only {
+        for (option, message) in [
+            ("wrap", "Cannot wrap the instances of a meta_only registry."),
+            ("factory", "Cannot declare a factory signature for a meta_only registry."),
+            ("args", "Cannot declare constructor arguments for a meta_only registry."),
+            ("eager", "Cannot construct the instances of a meta_only registry."),
+            ("dispatch", "Cannot generate a dispatch function for a meta_only registry."),
+        ] {
+            if let Some(ident) = registry_attr.options.iter().find(|ident| *ident == option) {
+                return syn::Error::new(ident.span(), message)
+                    .to_compile_error()
+                    .into();
+            }
+        }
+    }
+
+    // Implemented for the trait object used by registrations, and for the trait object of the
+    // registry if it has auto traits
+    let registered_object = match &registry_attr.bindings {
+        Some(bindings) => quote! { dyn #bindings },
+        None => quote! { dyn #trait_ident },
+    };
+    let object_types = [
+        Some(registered_object),
+        (send || sync).then(|| object_type.clone()),
+    ];
+    let object_types = object_types.iter().flatten().collect::<Vec<_>>();
 
-    let constructor_fn_call_str = if let Some(cfn) = constructor_fn {
-        let ident = cfn.constructor_fn_ident;
+    // Registrations are matched on the trait object an alias is defined as, and their instances
+    // are converted to the alias, so check that the alias is that trait object
+    let alias_check = registry_attr
+        .restated
+        .as_ref()
+        .filter(|_| !registry_attr.meta_only)
+        .map(|restated| {
+            quote_spanned! {restated.span()=>
+                const _: fn(Box<#restated>) -> Box<#object_type> = |object| object;
+                const _: fn(Box<#object_type>) -> Box<#restated> = |object| object;
+            }
+        });
+    let factory_impls = registry_attr.factory.as_ref().map(|factory| {
         quote! {
-            Some(Box::new(Self::#ident()))
+            #(
+                impl traitreg::Factory for #object_types {
+                    type Fn = #factory;
+
+                    fn __erase(factory: Self::Fn) -> traitreg::__private::ErasedFactory {
+                        unsafe { core::mem::transmute::<#factory, traitreg::__private::ErasedFactory>(factory) }
+                    }
+
+                    unsafe fn __restore(factory: traitreg::__private::ErasedFactory) -> Self::Fn {
+                        core::mem::transmute::<traitreg::__private::ErasedFactory, #factory>(factory)
+                    }
+                }
+            )*
         }
-    } else {
+    });
+
+    // The arguments are a generic associated type, with elided lifetimes named
+    let args_impls = registry_attr.args.as_ref().map(|args| {
+        let lifetime = quote! { '__args };
+        let args = name_elided_lifetimes(args.to_token_stream(), &lifetime);
         quote! {
-            None
+            #(
+                impl traitreg::ConstructorArgs for #object_types {
+                    type Args<#lifetime> = #args;
+
+                    fn __erase(
+                        constructor: traitreg::__private::ArgsConstructor<Self>,
+                    ) -> traitreg::__private::ErasedFactory {
+                        unsafe {
+                            core::mem::transmute::<
+                                traitreg::__private::ArgsConstructor<Self>,
+                                traitreg::__private::ErasedFactory,
+                            >(constructor)
+                        }
+                    }
+
+                    unsafe fn __call(
+                        constructor: traitreg::__private::ErasedFactory,
+                        args: Self::Args<'_>,
+                    ) -> Option<Result<Box<Self>, traitreg::ConstructError>> {
+                        let constructor = core::mem::transmute::<
+                            traitreg::__private::ErasedFactory,
+                            traitreg::__private::ArgsConstructor<Self>,
+                        >(constructor);
+                        constructor(args)
+                    }
+                }
+            )*
         }
-    };
+    });
 
-    let item_clone = item.clone();
+    // Instances of registries declared with `wrap` are constructed by a marker type, which wraps
+    // the boxed trait object
+    let (instance_type, storage_new_fn, wrap_marker) = match registry_attr.wrap {
+        Some(ref wrap) => {
+            let (wrapped_type, wrapped) =
+                match wrap_instance(wrap, &instance_type, quote! { instance }) {
+                    Ok(Some(wrapped)) => wrapped,
+                    Ok(None) => {
+                        return syn::Error::new(
+                            wrap.span(),
+                            "Expected the wrapper type to contain `_`, which is replaced by the \
+                            instance type.",
+                        )
+                        .to_compile_error()
+                        .into()
+                    }
+                    Err(error) => return error.to_compile_error().into(),
+                };
+            let marker_ident =
+                syn::parse_str::<syn::Ident>(format!("{}__Wrap", item.ident).as_ref())
+                    .expect("Unable to create identifier");
+            let marker = quote! {
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                struct #marker_ident;
 
-    let parsed_item = syn::parse_macro_input!(item as RegisterItem);
-    let item_impl = parsed_item.item;
+                impl traitreg::__private::Wrap for #marker_ident {
+                    type Instance = #instance_type;
+                    type Wrapped = #wrapped_type;
 
-    let (trait_not, trait_path, _) = item_impl
-        .trait_
-        .expect("Can only register an implementation of a trait, 'impl <Trait> for <Type>'.");
-    assert!(
-        trait_not.is_none(),
-        "Cannot register inverted impl trait: 'impl !Trait for Type'."
-    );
+                    fn wrap(instance: #instance_type) -> #wrapped_type {
+                        #wrapped
+                    }
+                }
+            };
+            (
+                wrapped_type,
+                quote! { build_wrapped_registry::<#marker_ident> },
+                marker,
+            )
+        }
+        None => (instance_type, storage_new_fn, quote! {}),
+    };
+
+    let on_failure = match registry_attr.on_failure {
+        Some(policy) => quote! { Some(traitreg::FailurePolicy::#policy) },
+        None => quote! { None },
+    };
 
-    let trait_ident = trait_path
-        .require_ident()
-        .expect("Expected trait in impl block to have an identifier.");
     let trait_name = format!("{trait_ident}");
+    let item_attrs = item.attrs;
+    let item_vis = item.vis;
+    let item_ident = item.ident;
+    let storage_ident = syn::parse_str::<syn::Ident>(format!("{}__STORAGE", item_ident).as_ref())
+        .expect("Unable to create identifier");
 
-    let type_path = get_self_type_path(&item_impl.self_ty);
-    let type_ident = type_path
-        .require_ident()
-        .expect("Expected type in impl block to have an identifier.");
-    let type_name = format!("{type_ident}");
+    // A named handle type is created when the static has a type other than `()` or `_`, otherwise
+    // the wrapper type is hidden
+    let (wrapper_struct_ident, wrapper_struct_attrs) = match *item.ty {
+        syn::Type::Tuple(ref tuple) if !tuple.elems.is_empty() => {
+            return syn::Error::new_spanned(
+                tuple,
+                "Expected registry static to have type (), _ or the name of a handle type.",
+            )
+            .to_compile_error()
+            .into()
+        }
+        syn::Type::Tuple(_) | syn::Type::Infer(_) => (
+            syn::parse_str::<syn::Ident>(format!("{}__TraitReg", item_ident).as_ref())
+                .expect("Unable to create identifier"),
+            quote! { #[doc(hidden)] },
+        ),
+        syn::Type::Path(ref type_path) if type_path.qself.is_none() => {
+            let handle_ident = match type_path.path.require_ident() {
+                Ok(ident) => ident.clone(),
+                Err(error) => return error.to_compile_error().into(),
+            };
+            let doc = format!(
+                "Registry of implementations of `{trait_name}`, see [`{item_ident}`]. Dereferences \
+                to [`traitreg::TraitRegStorage`]."
+            );
+            let handle_attrs = quote! {
+                #[doc = #doc]
+                #[derive(Clone, Copy, Debug)]
+            };
+            (handle_ident, handle_attrs)
+        }
+        ref ty => {
+            return syn::Error::new_spanned(
+                ty,
+                "Expected registry static to have type (), _ or the name of a handle type.",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
 
-    let register_static_ident =
-        syn::parse_str::<syn::Ident>(format!("{}_{}__Register", type_ident, trait_ident).as_ref())
+    let dispatch_fn = registry_attr.dispatch_fn.map(|dispatch_fn_ident| {
+        let doc = format!(
+            "Instantiate the registered implementation of `{trait_name}` with the given name, see \
+            [`{item_ident}`]."
+        );
+        quote! {
+            #[doc = #doc]
+            #[allow(unused)]
+            #item_vis fn #dispatch_fn_ident(name: &str) -> Option<#instance_type> {
+                #item_ident.iter().find(|item| item.name() == name)?.instantiate()
+            }
+        }
+    });
+
+    let auto_trait_bounds = [
+        send.then(|| quote! { + Send }),
+        sync.then(|| quote! { + Sync }),
+    ];
+    let upcast_impls = registry_attr.upcast.iter().map(|base| {
+        quote! {
+            impl traitreg::Upcast<dyn #base #(#auto_trait_bounds)*> for #object_type {
+                fn upcast(self: Box<Self>) -> Box<dyn #base #(#auto_trait_bounds)*> {
+                    self
+                }
+            }
+        }
+    });
+
+    // Registries are built before the checks run, so the check of every registry sees all of them
+    let warn_orphans = registry_attr.warn_orphans.then(|| {
+        let warn_static_ident =
+            syn::parse_str::<syn::Ident>(format!("{}__WarnOrphans", item_ident).as_ref())
+                .expect("Unable to create identifier");
+        quote! {
+            #[used]
+            #[cfg_attr(any(target_os = "linux", target_os = "android"), link_section = ".init_array.30000")]
+            #[cfg_attr(target_os = "freebsd", link_section = ".init_array.30000")]
+            #[cfg_attr(target_os = "netbsd", link_section = ".init_array.30000")]
+            #[cfg_attr(target_os = "openbsd", link_section = ".init_array.30000")]
+            #[cfg_attr(target_os = "dragonfly", link_section = ".init_array.30000")]
+            #[cfg_attr(target_os = "illumos", link_section = ".init_array.30000")]
+            #[cfg_attr(target_os = "haiku", link_section = ".init_array.30000")]
+            #[cfg_attr(target_vendor = "apple", link_section = "__DATA,__mod_init_func")]
+            #[cfg_attr(windows, link_section = ".CRT$XCV")]
+            static #warn_static_ident: extern fn() = {
+                extern fn warn_orphans() {
+                    traitreg::__private::warn_orphans();
+                }
+                warn_orphans
+            };
+        }
+    });
+
+    let build_static_ident =
+        syn::parse_str::<syn::Ident>(format!("{}__Build", item_ident).as_ref())
+            .expect("Unable to create identifier");
+    let build_static_fn_ident =
+        syn::parse_str::<syn::Ident>(format!("{}__BuildFn", item_ident).as_ref())
             .expect("Unable to create identifier");
-    let register_static_fn_ident = syn::parse_str::<syn::Ident>(
-        format!("{}_{}__RegisterFn", type_ident, trait_ident).as_ref(),
-    )
-    .expect("Unable to create identifier");
 
-    let mut result: proc_macro::TokenStream = quote! {
-        impl traitreg::RegisteredImpl<Box<dyn #trait_path>> for #type_path {
-            const INSTANCIATE: fn() -> Option<Box<dyn #trait_path>> = || { #constructor_fn_call_str };
-            const HAS_CONSTRUCTOR: bool = #has_constructor;
-            const NAME: &'static str = #type_name;
-            const PATH: &'static str = stringify!(#type_path);
-            const FILE: &'static str = core::file!() ;
-            const MODULE_PATH: &'static str = core::module_path!();
-            const TRAIT_NAME: &'static str = #trait_name;
+    quote! {
+        static mut #storage_ident: Option<traitreg::TraitRegStorage<#instance_type>> = None;
+
+        #(#item_attrs)*
+        #item_vis static #item_ident: #wrapper_struct_ident = #wrapper_struct_ident {};
+
+        #wrapper_struct_attrs
+        #item_vis struct #wrapper_struct_ident;
+
+        impl #wrapper_struct_ident {
+            /// The registry storage
+            #[allow(unused)]
+            #item_vis fn storage(&self) -> &'static traitreg::TraitRegStorage<#instance_type> {
+                unsafe {
+                    #storage_ident.as_ref().unwrap()
+                }
+            }
+        }
+
+        impl ::core::ops::Deref for #wrapper_struct_ident {
+            type Target = traitreg::TraitRegStorage<#instance_type>;
+            fn deref(&self) -> &'static traitreg::TraitRegStorage<#instance_type> {
+                self.storage()
+            }
         }
 
+        #alias_check
+
+        #wrap_marker
+
+        #factory_impls
+
+        #args_impls
+
+        #dispatch_fn
+
+        #(#upcast_impls)*
+
+        #warn_orphans
+
         #[used]
-        #[cfg_attr(any(target_os = "linux", target_os = "android"), link_section = ".init_array.10000")]
-        #[cfg_attr(target_os = "freebsd", link_section = ".init_array.10000")]
-        #[cfg_attr(target_os = "netbsd", link_section = ".init_array.10000")]
-        #[cfg_attr(target_os = "openbsd", link_section = ".init_array.10000")]
-        #[cfg_attr(target_os = "dragonfly", link_section = ".init_array.10000")]
-        #[cfg_attr(target_os = "illumos", link_section = ".init_array.10000")]
-        #[cfg_attr(target_os = "haiku", link_section = ".init_array.10000")]
+        #[cfg_attr(any(target_os = "linux", target_os = "android"), link_section = ".init_array.20000")]
+        #[cfg_attr(target_os = "freebsd", link_section = ".init_array.20000")]
+        #[cfg_attr(target_os = "netbsd", link_section = ".init_array.20000")]
+        #[cfg_attr(target_os = "openbsd", link_section = ".init_array.20000")]
+        #[cfg_attr(target_os = "dragonfly", link_section = ".init_array.20000")]
+        #[cfg_attr(target_os = "illumos", link_section = ".init_array.20000")]
+        #[cfg_attr(target_os = "haiku", link_section = ".init_array.20000")]
         #[cfg_attr(target_vendor = "apple", link_section = "__DATA,__mod_init_func")]
-        #[cfg_attr(windows, link_section = ".CRT$XCT")]
-        static #register_static_ident: extern fn() = {
-            extern fn #register_static_fn_ident() {
-                traitreg::__register_impl::<Box<dyn #trait_path>, #type_path>();
+        #[cfg_attr(windows, link_section = ".CRT$XCU")]
+        static #build_static_ident: extern fn() = {
+            extern fn #build_static_fn_ident() {
+                let storage = traitreg::__private::#storage_new_fn(
+                    #trait_name,
+                    #namespace,
+                    traitreg::__private::AutoTraits { send: #send, sync: #sync },
+                    #bindings,
+                    #on_failure,
+                    #sorted,
+                    #eager,
+                );
+
+                unsafe {
+                    #storage_ident = Some(storage)
+                }
             }
-            #register_static_fn_ident
+            #build_static_fn_ident
+        };
+    }.into()
+}
+
+/// Generate an enum over concrete implementations of a trait, which implements the trait by
+/// forwarding each method with a `match`. This gives static dispatch for performance critical
+/// code, alongside the dynamic registry.
+///
+/// Procedural macros cannot see the other registrations in a crate, so the implementations are
+/// listed after the name of the generated enum. The list is not checked against the registrations
+/// at compile time, an implementation which is registered but not listed is left out of the enum,
+/// its `COUNT` and [for_each_registered!](macro@for_each_registered). The list must be kept in
+/// sync with the registrations by hand, so compare the `NAMES` constant of the enum against a
+/// registry in a test to catch implementations which are missing.
+///
+/// ```rust
+/// #[traitreg::static_dispatch(ShapeEnum, Square, Circle)]
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// struct Square(f64);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// struct Circle(f64);
+///
+/// impl Shape for Circle {
+///     fn area(&self) -> f64 {
+///         3.0 * self.0 * self.0
+///     }
+/// }
+///
+/// let shapes: Vec<ShapeEnum> = vec![Square(2.0).into(), Circle(1.0).into()];
+/// assert_eq!(7.0, shapes.iter().map(|shape| shape.area()).sum::<f64>());
+/// assert_eq!(&["Square", "Circle"], ShapeEnum::NAMES);
+/// ```
+///
+/// The implementations listed can be iterated over at compile time with
+/// [for_each_registered!](macro@for_each_registered), and counted with the `COUNT` constant of the
+/// enum, e.g. to size arrays or in const assertions. Registrations are collected when the program
+/// starts, so the number of implementations in a registry is only known at runtime, see
+/// `TraitRegStorage::len`, and `COUNT` is only the number listed.
+///
+/// Only methods with a `self`, `&self` or `&mut self` receiver can be forwarded. Traits with
+/// associated functions without a receiver, associated types or associated constants are not
+/// supported.
+#[proc_macro_attribute]
+pub fn static_dispatch(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let dispatch_attr = syn::parse_macro_input!(attr as StaticDispatchAttribute);
+    let item_trait = syn::parse_macro_input!(item as syn::ItemTrait);
+
+    let enum_ident = &dispatch_attr.enum_ident;
+    let trait_ident = &item_trait.ident;
+    let vis = &item_trait.vis;
+
+    if !item_trait.generics.params.is_empty() {
+        return syn::Error::new(
+            item_trait.generics.span(),
+            "Cannot generate static dispatch for a generic trait.",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let variants: Vec<&Ident> = dispatch_attr
+        .types
+        .iter()
+        .map(|type_path| {
+            &type_path
+                .segments
+                .last()
+                .expect("Expected type to have an identifier.")
+                .ident
+        })
+        .collect();
+    let types = &dispatch_attr.types;
+    let names: Vec<String> = variants.iter().map(|variant| variant.to_string()).collect();
+
+    // Each implementation is reachable from the enum through its index, so the generated
+    // `for_each_registered!` macro does not depend on the types being in scope where it is used
+    let indices: Vec<usize> = (0..types.len()).collect();
+    let count = types.len();
+    let for_each_macro_ident = quote::format_ident!("{}__for_each", enum_ident);
+
+    let mut methods = vec![];
+    for trait_item in &item_trait.items {
+        let syn::TraitItem::Fn(method) = trait_item else {
+            return syn::Error::new(
+                trait_item.span(),
+                "Only methods can be statically dispatched.",
+            )
+            .to_compile_error()
+            .into();
+        };
+
+        let Some(syn::FnArg::Receiver(receiver)) = method.sig.inputs.first() else {
+            return syn::Error::new(
+                method.sig.ident.span(),
+                "Only methods with a receiver can be statically dispatched.",
+            )
+            .to_compile_error()
+            .into();
+        };
+        if receiver.colon_token.is_some() {
+            return syn::Error::new(
+                receiver.span(),
+                "Only `self`, `&self` and `&mut self` receivers can be statically dispatched.",
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        // Arguments are renamed, since they may be patterns rather than identifiers
+        let mut sig: syn::Signature = syn::parse2(method.sig.to_token_stream())
+            .expect("Unable to parse method signature");
+        let mut args = vec![];
+        for (index, input) in sig.inputs.iter_mut().skip(1).enumerate() {
+            if let syn::FnArg::Typed(pat_type) = input {
+                let arg = quote::format_ident!("arg{}", index);
+                *pat_type.pat = syn::parse_quote! { #arg };
+                args.push(arg);
+            }
+        }
+
+        let method_ident = &sig.ident;
+        let await_call = sig.asyncness.as_ref().map(|_| quote! { .await });
+        let call = quote! { #trait_ident::#method_ident(inner, #(#args),*) #await_call };
+        let call = if sig.unsafety.is_some() {
+            quote! { unsafe { #call } }
+        } else {
+            call
+        };
+
+        methods.push(quote! {
+            #sig {
+                match self {
+                    #(Self::#variants(inner) => #call,)*
+                }
+            }
+        });
+    }
+
+    quote! {
+        #item_trait
+
+        #vis enum #enum_ident {
+            #(#variants(#types),)*
+        }
+
+        impl #enum_ident {
+            /// Names of the implementations in the enum
+            pub const NAMES: &'static [&'static str] = &[#(#names),*];
+
+            /// Number of implementations listed in the enum, which is not checked against the
+            /// registrations, see `NAMES`
+            pub const COUNT: usize = #count;
+        }
+
+        #(
+            impl traitreg::__private::StaticDispatchVariant<#indices> for #enum_ident {
+                type Type = #types;
+            }
+        )*
+
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #for_each_macro_ident {
+            ($enum:path, $type:ident, $name:pat, $body:block) => {
+                #(
+                    {
+                        #[allow(unused)]
+                        type $type = <$enum as traitreg::__private::StaticDispatchVariant<#indices>>::Type;
+                        #[allow(unused)]
+                        let $name: &'static str = #names;
+                        $body
+                    }
+                )*
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use #for_each_macro_ident;
+
+        #(
+            impl From<#types> for #enum_ident {
+                fn from(inner: #types) -> Self {
+                    Self::#variants(inner)
+                }
+            }
+        )*
+
+        impl #trait_ident for #enum_ident {
+            #(#methods)*
+        }
+    }
+    .into()
+}
+
+/// Expand a block once for each implementation in an enum generated by
+/// [static_dispatch](macro@static_dispatch) in the current crate, with a type alias for the
+/// implementation and its name. Each expansion is checked at compile time against the concrete
+/// type, and the block can use local variables. Only the implementations listed in the enum are
+/// expanded, which must be kept in sync with the registrations, see
+/// [static_dispatch](macro@static_dispatch).
+///
+/// ```rust
+/// #[traitreg::static_dispatch(ShapeEnum, Square, Circle)]
+/// trait Shape {
+///     fn sides(&self) -> u32;
+/// }
+///
+/// #[derive(Default)]
+/// struct Square;
+///
+/// impl Shape for Square {
+///     fn sides(&self) -> u32 {
+///         4
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct Circle;
+///
+/// impl Shape for Circle {
+///     fn sides(&self) -> u32 {
+///         0
+///     }
+/// }
+///
+/// let mut sides = vec![];
+/// traitreg::for_each_registered!(ShapeEnum, |T, name| {
+///     sides.push((name, T::default().sides()));
+/// });
+/// assert_eq!(vec![("Square", 4), ("Circle", 0)], sides);
+/// ```
+///
+/// Procedural macros cannot see the other registrations in a crate graph, so only the
+/// implementations listed in the enum are included. Enums in other modules of the crate can be
+/// referred to by path, e.g. `for_each_registered!(shapes::ShapeEnum, |T, name| { .. })`.
+#[proc_macro]
+pub fn for_each_registered(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ForEachRegistered {
+        enum_path,
+        type_ident,
+        name_ident,
+        body,
+    } = syn::parse_macro_input!(item as ForEachRegistered);
+
+    // The macro generated by `static_dispatch` is named after the enum
+    let mut macro_path: syn::Path = syn::parse2(enum_path.to_token_stream())
+        .expect("Unable to parse enum path");
+    let macro_segment = macro_path
+        .segments
+        .last_mut()
+        .expect("Expected enum to have an identifier.");
+    macro_segment.ident = quote::format_ident!("{}__for_each", macro_segment.ident);
+
+    let name_ident = name_ident.unwrap_or_else(|| Ident::new("_", proc_macro2::Span::call_site()));
+
+    quote! {
+        #macro_path!(#enum_path, #type_ident, #name_ident, #body)
+    }
+    .into()
+}
+
+/// Register a list of types which implement a trait, all with the same options, as if each impl
+/// block had a [register](macro@register) attribute. The options are given in brackets after the
+/// trait, and can be left out.
+///
+/// ```rust
+/// trait Codec {}
+///
+/// #[derive(Default)]
+/// struct Gzip;
+/// impl Codec for Gzip {}
+///
+/// #[derive(Default)]
+/// struct Zstd;
+/// impl Codec for Zstd {}
+///
+/// traitreg::register_many!(Codec[default, tags = ["compression"]]: Gzip, Zstd);
+///
+/// #[traitreg::registry(Codec)]
+/// static CODEC_REGISTRY: () = ();
+///
+/// assert_eq!(2, CODEC_REGISTRY.instantiate_all().count());
+/// ```
+///
+/// The impl blocks are not seen by the macro, so traits with associated types cannot be registered
+/// this way.
+#[proc_macro]
+pub fn register_many(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let RegisterMany {
+        trait_path,
+        options,
+        types,
+    } = syn::parse_macro_input!(item as RegisterMany);
+
+    let registrations = types.iter().map(|ty| {
+        let register_attr = syn::parse2::<RegisterAttribute>(options.clone())?;
+        let item_impl: syn::ItemImpl = syn::parse_quote! { impl #trait_path for #ty {} };
+        impl_registration(register_attr, item_impl)
+    });
+
+    let mut result = proc_macro2::TokenStream::new();
+    for registration in registrations {
+        match registration {
+            Ok(registration) => result.extend(registration),
+            Err(error) => return error.to_compile_error().into(),
+        }
+    }
+    result.into()
+}
+
+// Trait, options and types of `register_many!`, e.g. `MyTrait[new]: TypeA, TypeB`
+struct RegisterMany {
+    trait_path: syn::Path,
+    options: proc_macro2::TokenStream,
+    types: Punctuated<syn::Type, Token![,]>,
+}
+
+impl Parse for RegisterMany {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let trait_path = input.parse()?;
+        let options = if input.peek(syn::token::Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+            content.parse()?
+        } else {
+            proc_macro2::TokenStream::new()
         };
-    }.into();
+        input.parse::<Token![:]>()?;
+        let types = Punctuated::parse_terminated(input)?;
+
+        Ok(Self {
+            trait_path,
+            options,
+            types,
+        })
+    }
+}
+
+/// Submit a value to the [collection](macro@collection) of its type. The value can be any constant
+/// expression, or a static, and its type must be `Sync`.
+///
+/// ```rust
+/// struct Setting(&'static str, u32);
+///
+/// traitreg::submit!(Setting("retries", 3) as Setting);
+///
+/// #[traitreg::collection(Setting)]
+/// static SETTINGS: () = ();
+///
+/// assert_eq!(3, SETTINGS.iter().find(|setting| setting.0 == "retries").unwrap().1);
+/// ```
+#[proc_macro]
+pub fn submit(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let expr = syn::parse_macro_input!(item as syn::Expr);
+    let syn::Expr::Cast(cast) = expr else {
+        return syn::Error::new_spanned(
+            expr,
+            "Expected a value and its type, e.g. `submit!(VALUE as Type)`.",
+        )
+        .to_compile_error()
+        .into();
+    };
+    let value = cast.expr;
+    let value_type = cast.ty;
 
-    result.extend(item_clone.clone());
+    quote! {
+        const _: () = {
+            static VALUE: &'static #value_type = &#value;
 
-    result
+            #[used]
+            #[cfg_attr(any(target_os = "linux", target_os = "android"), link_section = ".init_array.10000")]
+            #[cfg_attr(target_os = "freebsd", link_section = ".init_array.10000")]
+            #[cfg_attr(target_os = "netbsd", link_section = ".init_array.10000")]
+            #[cfg_attr(target_os = "openbsd", link_section = ".init_array.10000")]
+            #[cfg_attr(target_os = "dragonfly", link_section = ".init_array.10000")]
+            #[cfg_attr(target_os = "illumos", link_section = ".init_array.10000")]
+            #[cfg_attr(target_os = "haiku", link_section = ".init_array.10000")]
+            #[cfg_attr(target_vendor = "apple", link_section = "__DATA,__mod_init_func")]
+            #[cfg_attr(windows, link_section = ".CRT$XCT")]
+            static SUBMIT: extern fn() = {
+                extern fn submit() {
+                    fn value() -> &'static dyn core::any::Any {
+                        VALUE
+                    }
+                    static SUBMISSION: traitreg::__private::Submission =
+                        traitreg::__private::Submission::new(value);
+                    SUBMISSION.submit();
+                }
+                submit
+            };
+        };
+    }
+    .into()
 }
 
-/// Create a registry of implementations of a trait
+/// Create a collection of the values of a type submitted with [submit!](macro@submit), from any
+/// crate linked into the binary. The static dereferences to a `traitreg::collection::Collection`.
 ///
 /// ```rust
-/// trait MyTrait {}
+/// struct Command {
+///     name: &'static str,
+/// }
 ///
-/// #[traitreg::registry(MyTrait)]
-/// static MYTRAIT_REGISTRY: () = ();
+/// traitreg::submit!(Command { name: "build" } as Command);
+/// traitreg::submit!(Command { name: "test" } as Command);
+///
+/// #[traitreg::collection(Command)]
+/// static COMMANDS: () = ();
+///
+/// let mut names: Vec<_> = COMMANDS.iter().map(|command| command.name).collect();
+/// names.sort();
+/// assert_eq!(vec!["build", "test"], names);
 /// ```
 #[proc_macro_attribute]
-pub fn registry(
+pub fn collection(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let registry_attr = syn::parse_macro_input!(attr as RegistryAttribute);
-    let registry_item = syn::parse_macro_input!(item as RegistryItem);
-
-    let trait_ident = registry_attr.trait_ident;
-    let item = registry_item.item;
+    let value_type = syn::parse_macro_input!(attr as syn::Type);
+    let item = syn::parse_macro_input!(item as RegistryItem).item;
 
-    let trait_name = format!("{trait_ident}");
+    let item_attrs = item.attrs;
+    let item_vis = item.vis;
     let item_ident = item.ident;
+    if !matches!(*item.ty, syn::Type::Tuple(ref tuple) if tuple.elems.is_empty())
+        && !matches!(*item.ty, syn::Type::Infer(_))
+    {
+        return syn::Error::new_spanned(
+            &item.ty,
+            "Expected collection static to have type () or _.",
+        )
+        .to_compile_error()
+        .into();
+    }
+
     let storage_ident = syn::parse_str::<syn::Ident>(format!("{}__STORAGE", item_ident).as_ref())
         .expect("Unable to create identifier");
     let wrapper_struct_ident =
-        syn::parse_str::<syn::Ident>(format!("{}__TraitReg", item_ident).as_ref())
+        syn::parse_str::<syn::Ident>(format!("{}__Collection", item_ident).as_ref())
             .expect("Unable to create identifier");
     let build_static_ident =
         syn::parse_str::<syn::Ident>(format!("{}__Build", item_ident).as_ref())
             .expect("Unable to create identifier");
-    let build_static_fn_ident =
-        syn::parse_str::<syn::Ident>(format!("{}__BuildFn", item_ident).as_ref())
-            .expect("Unable to create identifier");
 
     quote! {
-        static mut #storage_ident: Option<traitreg::TraitRegStorage<Box<dyn #trait_ident>>> = None;
+        static mut #storage_ident: Option<traitreg::collection::Collection<#value_type>> = None;
 
-        static #item_ident: #wrapper_struct_ident = #wrapper_struct_ident {};
+        #(#item_attrs)*
+        #item_vis static #item_ident: #wrapper_struct_ident = #wrapper_struct_ident {};
 
-        struct #wrapper_struct_ident;
+        #[doc(hidden)]
+        #item_vis struct #wrapper_struct_ident;
 
         impl ::core::ops::Deref for #wrapper_struct_ident {
-            type Target = traitreg::TraitRegStorage<Box<dyn #trait_ident>>;
-            fn deref(&self) -> &'static traitreg::TraitRegStorage<Box<dyn #trait_ident>> {
+            type Target = traitreg::collection::Collection<#value_type>;
+            fn deref(&self) -> &'static traitreg::collection::Collection<#value_type> {
                 unsafe {
                     #storage_ident.as_ref().unwrap()
                 }
@@ -184,27 +2127,201 @@ pub fn registry(
         #[cfg_attr(target_vendor = "apple", link_section = "__DATA,__mod_init_func")]
         #[cfg_attr(windows, link_section = ".CRT$XCU")]
         static #build_static_ident: extern fn() = {
-            extern fn #build_static_fn_ident() {
-                let storage = traitreg::TraitRegStorage::<Box<dyn #trait_ident>>::__new(#trait_name);
-
+            extern fn build() {
                 unsafe {
-                    #storage_ident = Some(storage)
+                    #storage_ident = Some(traitreg::__private::build_collection())
                 }
             }
-            #build_static_fn_ident
+            build
         };
-    }.into()
+    }
+    .into()
 }
 
-#[derive(Debug)]
+// Parse a list of string literals, e.g. `["a", "b"]`
+fn parse_str_list(input: ParseStream) -> syn::Result<Vec<syn::LitStr>> {
+    let content;
+    syn::bracketed!(content in input);
+    let list = Punctuated::<syn::LitStr, Token![,]>::parse_terminated(&content)?;
+    Ok(list.into_iter().collect())
+}
+
+#[derive(Default)]
 struct RegisterAttribute {
-    constructor_fn_ident: Ident,
+    constructor_fn_ident: Option<Ident>,
+    guard_fn: Option<syn::Path>,
+    shutdown_fn: Option<syn::Path>,
+    health_check_fn: Option<syn::Path>,
+    factory_fn: Option<syn::Path>,
+    feature: Option<syn::LitStr>,
+    meta_only: bool,
+    inject: bool,
+    args: bool,
+    any: bool,
+    async_init: bool,
+    auto: bool,
+    lifetime: Option<Ident>,
+    priority: Option<syn::Expr>,
+    init_priority: Option<u16>,
+    capabilities: Option<Vec<syn::LitStr>>,
+    tags: Option<Vec<syn::LitStr>>,
+    phase: Option<Ident>,
+    namespace: Option<syn::LitStr>,
+    after: Option<Vec<syn::LitStr>>,
+    conflicts_with: Option<Vec<syn::LitStr>>,
+    flags: Option<Vec<Ident>>,
+    exposed: Option<Vec<Ident>>,
+    // Options as written, so misuse errors can point at them
+    options: Vec<Ident>,
+}
+
+impl RegisterAttribute {
+    // The span of the first of the options given, for reporting misuse
+    fn option_span(&self, options: &[&str]) -> Option<proc_macro2::Span> {
+        self.options
+            .iter()
+            .find(|option| options.iter().any(|name| *option == name))
+            .map(Ident::span)
+    }
 }
 
 impl Parse for RegisterAttribute {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut result = Self::default();
+
+        while !input.is_empty() {
+            let ident = Ident::parse(input)?;
+            result.options.push(ident.clone());
+
+            if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+
+                match ident.to_string().as_str() {
+                    "guard" => result.guard_fn = Some(input.parse()?),
+                    "shutdown" => result.shutdown_fn = Some(input.parse()?),
+                    "health_check" => result.health_check_fn = Some(input.parse()?),
+                    "factory" => result.factory_fn = Some(input.parse()?),
+                    "feature" => result.feature = Some(input.parse()?),
+                    "priority" => result.priority = Some(input.parse()?),
+                    "init_priority" => {
+                        let init_priority = syn::LitInt::parse(input)?;
+                        match init_priority.base10_parse::<u16>() {
+                            Ok(value @ 101..=19999) => result.init_priority = Some(value),
+                            _ => {
+                                return Err(syn::Error::new(
+                                    init_priority.span(),
+                                    "Expected an init_priority from 101 to 19999, registries are \
+                                    built at 20000.",
+                                ))
+                            }
+                        }
+                    }
+                    "capabilities" => result.capabilities = Some(parse_str_list(input)?),
+                    "tags" => result.tags = Some(parse_str_list(input)?),
+                    "phase" => result.phase = Some(input.parse()?),
+                    "namespace" => result.namespace = Some(input.parse()?),
+                    "lifetime" => {
+                        let lifetime = Ident::parse(input)?;
+                        let variant = match lifetime.to_string().as_str() {
+                            "transient" => "Transient",
+                            "singleton" => "Singleton",
+                            "scoped" => "Scoped",
+                            _ => {
+                                return Err(syn::Error::new(
+                                    lifetime.span(),
+                                    format!(
+                                        "Unknown lifetime '{lifetime}', expected transient, \
+                                        singleton or scoped."
+                                    ),
+                                ))
+                            }
+                        };
+                        result.lifetime = Some(Ident::new(variant, lifetime.span()));
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            format!("Unknown register option '{ident}'."),
+                        ))
+                    }
+                }
+            } else if input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in input);
+                match ident.to_string().as_str() {
+                    "flags" => {
+                        let flags = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                        result.flags = Some(flags.into_iter().collect());
+                    }
+                    "expose" => {
+                        let exposed = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                        result.exposed = Some(exposed.into_iter().collect());
+                    }
+                    "after" => {
+                        let after =
+                            Punctuated::<syn::LitStr, Token![,]>::parse_terminated(&content)?;
+                        result.after = Some(after.into_iter().collect());
+                    }
+                    "conflicts_with" => {
+                        let conflicts_with =
+                            Punctuated::<syn::LitStr, Token![,]>::parse_terminated(&content)?;
+                        result.conflicts_with = Some(conflicts_with.into_iter().collect());
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            format!("Unknown register option '{ident}'."),
+                        ))
+                    }
+                }
+            } else if ident == "meta_only" {
+                result.meta_only = true;
+            } else if ident == "inject" {
+                result.inject = true;
+            } else if ident == "args" {
+                result.args = true;
+            } else if ident == "any" {
+                result.any = true;
+            } else if ident == "async_init" {
+                result.async_init = true;
+            } else if ident == "auto" {
+                result.auto = true;
+            } else if result.constructor_fn_ident.is_some() {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "Only one constructor can be registered.",
+                ));
+            } else {
+                result.constructor_fn_ident = Some(ident);
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+// Trait and options of a registered function, e.g. `#[register(Handler, priority = 1)]`
+struct RegisterFnAttribute {
+    trait_path: syn::Path,
+    options: RegisterAttribute,
+}
+
+impl Parse for RegisterFnAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let trait_path = input.parse()?;
+        let options = if input.is_empty() {
+            RegisterAttribute::default()
+        } else {
+            input.parse::<Token![,]>()?;
+            input.parse()?
+        };
         Ok(Self {
-            constructor_fn_ident: Ident::parse(input)?,
+            trait_path,
+            options,
         })
     }
 }
@@ -221,15 +2338,439 @@ impl Parse for RegisterItem {
     }
 }
 
-#[derive(Debug)]
 struct RegistryAttribute {
     trait_ident: Ident,
+    object_type: proc_macro2::TokenStream,
+    auto_traits: AutoTraits,
+    // The trait with its generic arguments and associated type bindings, if it has any
+    bindings: Option<proc_macro2::TokenStream>,
+    // The trait object an alias is defined as, which the alias is checked against
+    restated: Option<proc_macro2::TokenStream>,
+    meta_only: bool,
+    dispatch_fn: Option<Ident>,
+    upcast: Vec<syn::Path>,
+    warn_orphans: bool,
+    sorted: bool,
+    eager: bool,
+    namespace: Option<syn::LitStr>,
+    on_failure: Option<Ident>,
+    send: bool,
+    wrap: Option<syn::Type>,
+    factory: Option<syn::TypeBareFn>,
+    args: Option<syn::TypeTuple>,
+    // Options as written, so misuse errors can point at them
+    options: Vec<Ident>,
 }
 
 impl Parse for RegistryAttribute {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![dyn]) {
+            // Trait object, e.g. `dyn MyTrait + Send + 'static`
+            let object = syn::TypeTraitObject::parse(input)?;
+            let (trait_ident, auto_traits, bindings) = parse_object_bounds(object.bounds.iter())?;
+            return Self::parse_options(
+                input,
+                Self {
+                    trait_ident,
+                    object_type: quote! { #object },
+                    auto_traits,
+                    bindings,
+                    restated: None,
+                    meta_only: false,
+                    dispatch_fn: None,
+                    upcast: vec![],
+                    warn_orphans: false,
+                    sorted: false,
+                    eager: false,
+                    namespace: None,
+                    on_failure: None,
+                    send: false,
+                    wrap: None,
+                    factory: None,
+                    args: None,
+                    options: vec![],
+                },
+            )?
+            .add_send(false);
+        }
+
+        let name = Ident::parse(input)?;
+        let alias = input.peek(Token![=]);
+
+        let (trait_ident, object_type, auto_traits, bindings, restated) = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+
+            if input.peek(Token![dyn]) {
+                // Type alias, e.g. `MyObject = dyn MyTrait + Send`
+                let object = syn::TypeTraitObject::parse(input)?;
+                let (trait_ident, auto_traits, bindings) =
+                    parse_object_bounds(object.bounds.iter())?;
+                (trait_ident, quote! { #name }, auto_traits, bindings, Some(quote! { #object }))
+            } else {
+                // Trait alias, e.g. `MyAlias = MyTrait + Send`
+                let bounds = Punctuated::<syn::TypeParamBound, Token![+]>::parse_separated_nonempty(
+                    input,
+                )?;
+                let (trait_ident, auto_traits, bindings) = parse_object_bounds(bounds.iter())?;
+                let restated = Some(quote! { dyn #bounds });
+                (trait_ident, quote! { dyn #name }, auto_traits, bindings, restated)
+            }
+        } else if input.peek(Token![<]) {
+            // Trait with generic arguments or associated type bindings, e.g. `Codec<u8>` or
+            // `Handler<Output = Response>`
+            let arguments = syn::AngleBracketedGenericArguments::parse(input)?;
+            let trait_path: syn::Path = syn::parse2(quote! { #name #arguments })?;
+            let bindings = has_bindings(&trait_path).then(|| trait_path.to_token_stream());
+            (name, quote! { dyn #trait_path }, AutoTraits::default(), bindings, None)
+        } else {
+            (name.clone(), quote! { dyn #name }, AutoTraits::default(), None, None)
+        };
+
+        Self::parse_options(
+            input,
+            Self {
+                trait_ident,
+                object_type,
+                auto_traits,
+                bindings,
+                restated,
+                meta_only: false,
+                dispatch_fn: None,
+                upcast: vec![],
+                warn_orphans: false,
+                sorted: false,
+                eager: false,
+                namespace: None,
+                on_failure: None,
+                send: false,
+                wrap: None,
+                factory: None,
+                args: None,
+                options: vec![],
+            },
+        )?
+        .add_send(alias)
+    }
+}
+
+impl RegistryAttribute {
+    fn parse_options(input: ParseStream, mut result: Self) -> syn::Result<Self> {
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let ident = Ident::parse(input)?;
+            result.options.push(ident.clone());
+            match ident.to_string().as_str() {
+                "meta_only" => result.meta_only = true,
+                "warn_orphans" => result.warn_orphans = true,
+                "sorted" => result.sorted = true,
+                "eager" => result.eager = true,
+                "send" => result.send = true,
+                "dispatch" => {
+                    input.parse::<Token![=]>()?;
+                    result.dispatch_fn = Some(input.parse()?);
+                }
+                "upcast" => {
+                    input.parse::<Token![=]>()?;
+                    result.upcast.push(input.parse()?);
+                }
+                "wrap" => {
+                    input.parse::<Token![=]>()?;
+                    result.wrap = Some(input.parse()?);
+                }
+                "factory" => {
+                    input.parse::<Token![=]>()?;
+                    result.factory = Some(input.parse()?);
+                }
+                "args" => {
+                    input.parse::<Token![=]>()?;
+                    result.args = Some(input.parse()?);
+                }
+                "namespace" => {
+                    input.parse::<Token![=]>()?;
+                    result.namespace = Some(input.parse()?);
+                }
+                "on_failure" => {
+                    input.parse::<Token![=]>()?;
+                    let policy = Ident::parse(input)?;
+                    let variant = match policy.to_string().as_str() {
+                        "panic" => "Panic",
+                        "skip" => "Skip",
+                        "log" => "Log",
+                        _ => {
+                            return Err(syn::Error::new(
+                                policy.span(),
+                                format!(
+                                    "Unknown failure policy '{policy}', expected panic, skip or \
+                                    log."
+                                ),
+                            ))
+                        }
+                    };
+                    result.on_failure = Some(Ident::new(variant, policy.span()));
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!("Unknown registry option '{ident}'."),
+                    ))
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Add `Send` to the trait object for the `send` option, an alias must already include it
+    fn add_send(mut self, alias: bool) -> syn::Result<Self> {
+        if !self.send || self.auto_traits.send {
+            return Ok(self);
+        }
+
+        if alias {
+            return Err(syn::Error::new(
+                self.trait_ident.span(),
+                "The send option requires the alias to include Send.",
+            ));
+        }
+
+        let object_type = self.object_type;
+        self.object_type = quote! { #object_type + Send };
+        self.auto_traits.send = true;
+        Ok(self)
+    }
+}
+
+// Add associated type bindings, e.g. `Output = Response`, to the generic arguments of a trait
+fn bind_associated_types(
+    trait_path: &syn::Path,
+    bindings: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    if bindings.is_empty() {
+        return trait_path.to_token_stream();
+    }
+
+    let mut trait_path: syn::Path =
+        syn::parse2(trait_path.to_token_stream()).expect("Unable to parse trait path");
+    let segment = trait_path
+        .segments
+        .last_mut()
+        .expect("Expected trait in impl block to have an identifier.");
+    match &mut segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) => {
+            for binding in bindings {
+                arguments.args.push(
+                    syn::parse2(binding.clone()).expect("Unable to parse associated type binding"),
+                );
+            }
+        }
+        arguments => {
+            *arguments = syn::PathArguments::AngleBracketed(syn::parse_quote! { <#(#bindings),*> });
+        }
+    }
+    trait_path.to_token_stream()
+}
+
+/// Give every elided lifetime in a type, references without a lifetime and `'_`, a name
+fn name_elided_lifetimes(
+    tokens: proc_macro2::TokenStream,
+    lifetime: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    use proc_macro2::TokenTree;
+
+    let mut result = proc_macro2::TokenStream::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => {
+                let mut named = proc_macro2::Group::new(
+                    group.delimiter(),
+                    name_elided_lifetimes(group.stream(), lifetime),
+                );
+                named.set_span(group.span());
+                result.extend([TokenTree::Group(named)]);
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '&' => {
+                result.extend([TokenTree::Punct(punct)]);
+                if !matches!(tokens.peek(), Some(TokenTree::Punct(next)) if next.as_char() == '\'') {
+                    result.extend(lifetime.clone());
+                }
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                match tokens.peek() {
+                    Some(TokenTree::Ident(ident)) if ident == "_" => {
+                        tokens.next();
+                        result.extend(lifetime.clone());
+                    }
+                    _ => result.extend([TokenTree::Punct(punct)]),
+                }
+            }
+            token => result.extend([token]),
+        }
+    }
+    result
+}
+
+/// Construct the `wrap` type of a registry around an instance, with `_` in the type replaced by the
+/// instance type. Each type containing `_` is constructed with its `new` function. Returns `None`
+/// if the type does not contain `_`.
+fn wrap_instance(
+    wrap: &syn::Type,
+    instance_type: &proc_macro2::TokenStream,
+    instance: proc_macro2::TokenStream,
+) -> syn::Result<Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)>> {
+    match wrap {
+        syn::Type::Infer(_) => Ok(Some((instance_type.clone(), instance))),
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            // syn types are not `Clone` without the `clone-impls` feature
+            let mut path: syn::Path = syn::parse2(type_path.path.to_token_stream())?;
+            let Some(syn::PathArguments::AngleBracketed(arguments)) =
+                path.segments.last_mut().map(|segment| &mut segment.arguments)
+            else {
+                return Ok(None);
+            };
+
+            let mut inner = None;
+            for argument in arguments.args.iter_mut() {
+                let syn::GenericArgument::Type(ty) = argument else {
+                    continue;
+                };
+                let Some((inner_type, wrapped)) = wrap_instance(ty, instance_type, instance.clone())?
+                else {
+                    continue;
+                };
+                if inner.is_some() {
+                    return Err(syn::Error::new(
+                        ty.span(),
+                        "Expected the wrapper type to contain `_` only once.",
+                    ));
+                }
+                *ty = syn::parse2(inner_type)?;
+                inner = Some(wrapped);
+            }
+
+            Ok(inner.map(|wrapped| (quote! { #path }, quote! { <#path>::new(#wrapped) })))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[derive(Default)]
+struct AutoTraits {
+    send: bool,
+    sync: bool,
+}
+
+/// Split the bounds of a trait object into the trait and any auto traits
+fn parse_object_bounds<'a>(
+    bounds: impl Iterator<Item = &'a syn::TypeParamBound>,
+) -> syn::Result<(Ident, AutoTraits, Option<proc_macro2::TokenStream>)> {
+    let mut trait_ident: Option<Ident> = None;
+    let mut auto_traits = AutoTraits::default();
+    let mut bindings = None;
+
+    for bound in bounds {
+        match bound {
+            syn::TypeParamBound::Trait(trait_bound) => {
+                let ident = &trait_bound
+                    .path
+                    .segments
+                    .last()
+                    .ok_or_else(|| syn::Error::new(trait_bound.span(), "Expected a trait."))?
+                    .ident;
+
+                if ident == "Send" {
+                    auto_traits.send = true;
+                } else if ident == "Sync" {
+                    auto_traits.sync = true;
+                } else if trait_ident.is_some() {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "Expected only one trait other than auto traits.",
+                    ));
+                } else {
+                    trait_ident = Some(ident.clone());
+                    bindings = has_bindings(&trait_bound.path)
+                        .then(|| trait_bound.path.to_token_stream());
+                }
+            }
+            syn::TypeParamBound::Lifetime(lifetime) if lifetime.ident == "static" => {}
+            _ => {
+                return Err(syn::Error::new(
+                    bound.span(),
+                    "Expected a trait, auto traits or a 'static lifetime.",
+                ))
+            }
+        }
+    }
+
+    let trait_ident = trait_ident.ok_or_else(|| {
+        syn::Error::new(proc_macro2::Span::call_site(), "Expected a trait.")
+    })?;
+
+    Ok((trait_ident, auto_traits, bindings))
+}
+
+// Does a trait path have generic arguments or bind associated types, e.g. `Codec<u8>` or
+// `Handler<Output = Response>`, so registrations must be matched on the whole trait object type.
+// Lifetime arguments alone are left out, the type id of the trait object needs them to be 'static.
+fn has_bindings(trait_path: &syn::Path) -> bool {
+    trait_path.segments.last().is_some_and(|segment| match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .any(|argument| !matches!(argument, syn::GenericArgument::Lifetime(_))),
+        _ => false,
+    })
+}
+
+struct StaticDispatchAttribute {
+    enum_ident: Ident,
+    types: Vec<syn::Path>,
+}
+
+impl Parse for StaticDispatchAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let enum_ident = Ident::parse(input)?;
+        input.parse::<Token![,]>()?;
+        let types = Punctuated::<syn::Path, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect();
+
+        Ok(Self { enum_ident, types })
+    }
+}
+
+struct ForEachRegistered {
+    enum_path: syn::Path,
+    type_ident: Ident,
+    name_ident: Option<Ident>,
+    body: syn::Block,
+}
+
+impl Parse for ForEachRegistered {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let enum_path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        input.parse::<Token![|]>()?;
+        let type_ident = input.parse()?;
+        let name_ident = if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        input.parse::<Token![|]>()?;
+        let body = input.parse()?;
+
         Ok(Self {
-            trait_ident: Ident::parse(input)?,
+            enum_path,
+            type_ident,
+            name_ident,
+            body,
         })
     }
 }
@@ -246,15 +2787,485 @@ impl Parse for RegistryItem {
     }
 }
 
+// Register a free function as an implementation of a trait with a blanket implementation for
+// functions, e.g. `impl<F: Fn(Request) -> Response> Handler for F`. Instances are the function
+// pointer, and a marker type stands in for the registered type.
+fn register_fn(
+    register_fn_attr: RegisterFnAttribute,
+    item_fn: syn::ItemFn,
+) -> proc_macro::TokenStream {
+    let RegisterFnAttribute {
+        trait_path,
+        options: register_attr,
+    } = register_fn_attr;
+
+    let unsupported = register_attr
+        .constructor_fn_ident
+        .as_ref()
+        .map(Ident::span)
+        .or_else(|| {
+            register_attr.option_span(&[
+                "meta_only",
+                "inject",
+                "any",
+                "lifetime",
+                "factory",
+                "args",
+                "async_init",
+                "auto",
+                "flags",
+                "expose",
+            ])
+        });
+    if let Some(span) = unsupported {
+        return syn::Error::new(
+            span,
+            "Registered functions are their own constructor, only the guard, feature, priority, \
+            init_priority, capabilities, tags, phase, namespace, after, conflicts_with, \
+            shutdown and health_check options can be used.",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let signature = &item_fn.sig;
+    if !signature.generics.params.is_empty() || signature.asyncness.is_some() {
+        return syn::Error::new_spanned(
+            signature,
+            "Can only register functions which are not generic or async.",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if let Some(receiver) = signature.receiver() {
+        return syn::Error::new_spanned(receiver, "Cannot register a method, only free functions.")
+            .to_compile_error()
+            .into();
+    }
+    let input_types = signature
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => Some(&pat_type.ty),
+            syn::FnArg::Receiver(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let output = &signature.output;
+    let fn_ident = &signature.ident;
+    let fn_pointer_type = quote! { fn(#(#input_types),*) #output };
+
+    let cfg_attrs: Vec<&syn::Attribute> = item_fn
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect();
+    let mut registration_cfg = quote! { #(#cfg_attrs)* };
+    if let Some(feature) = &register_attr.feature {
+        registration_cfg.extend(quote! { #[cfg(feature = #feature)] });
+    }
+
+    let trait_ident = &trait_path
+        .segments
+        .last()
+        .expect("Expected trait to have an identifier.")
+        .ident;
+    let trait_name = format!("{trait_ident}");
+    let type_name = format!("{fn_ident}");
+    let signature_str = signature_string(signature.to_token_stream());
+
+    let registration_ident = registration_ident(&[
+        &fn_ident.to_string(),
+        "fn",
+        &trait_path.to_token_stream().to_string(),
+    ]);
+    let marker_ident = syn::parse_str::<syn::Ident>(format!("{}__Fn", registration_ident).as_ref())
+        .expect("Unable to create identifier");
+    let instance_type = quote_spanned! {trait_path.span()=> Box<dyn #trait_path> };
+
+    let option_consts = option_consts(&register_attr);
+
+    let register_call = quote! {
+        traitreg::__private::register_impl::<#instance_type, #marker_ident>(registry);
+    };
+    let register_call = if let Some(guard_fn) = &register_attr.guard_fn {
+        quote! {
+            if #guard_fn() {
+                #register_call
+            }
+        }
+    } else {
+        register_call
+    };
+
+    let registration_static = registration_static(
+        &registration_ident,
+        &registration_cfg,
+        &register_call,
+        &Registered {
+            type_name: &type_name,
+            type_path: fn_ident.to_token_stream(),
+            trait_name: &trait_name,
+            has_constructor: quote! { true },
+        },
+        register_attr.init_priority,
+    );
+
+    quote! {
+        #item_fn
+
+        #registration_cfg
+        #[allow(non_camel_case_types)]
+        struct #marker_ident;
+
+        #registration_cfg
+        unsafe impl traitreg::__private::RegisteredImpl<#instance_type> for #marker_ident {
+            const INSTANTIATE: fn() -> Option<Result<#instance_type, traitreg::ConstructError>> =
+                || Some(Ok(Box::new(#fn_ident as #fn_pointer_type)));
+            const HAS_CONSTRUCTOR: bool = true;
+            const NAME: &'static str = #type_name;
+            const PATH: &'static str = stringify!(#fn_ident);
+            const SIGNATURE: &'static str = #signature_str;
+            const FILE: &'static str = core::file!();
+            const MODULE_PATH: &'static str = core::module_path!();
+            const CRATE_NAME: &'static str = env!("CARGO_PKG_NAME");
+            const CRATE_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+            const TRAIT_NAME: &'static str = #trait_name;
+            // Function pointers are always `Send` and `Sync`
+            const AUTO_TRAITS: fn() -> traitreg::__private::AutoTraits =
+                || traitreg::__private::AutoTraits { send: true, sync: true };
+            const TYPE_ID: fn() -> core::any::TypeId = core::any::TypeId::of::<#marker_ident>;
+            const EMPLACE: Option<traitreg::__private::Emplace> = Some(traitreg::__private::Emplace {
+                layout: core::alloc::Layout::new::<#fn_pointer_type>(),
+                write: |ptr, object| unsafe {
+                    let ptr = ptr as *mut #fn_pointer_type;
+                    ptr.write(#fn_ident);
+                    *(object as *mut *mut dyn #trait_path) = ptr;
+                    true
+                },
+            });
+            #option_consts
+        }
+
+        #registration_static
+    }
+    .into()
+}
+
+// Constants of a registration for options which apply to any registered item
+fn option_consts(register_attr: &RegisterAttribute) -> proc_macro2::TokenStream {
+    let priority_const = register_attr.priority.as_ref().map(|priority| {
+        quote! {
+            const PRIORITY: i32 = #priority;
+        }
+    });
+
+    let capabilities_const = register_attr.capabilities.as_ref().map(|capabilities| {
+        quote! {
+            const CAPABILITIES: &'static [&'static str] = &[#(#capabilities),*];
+        }
+    });
+
+    let tags_const = register_attr.tags.as_ref().map(|tags| {
+        quote! {
+            const TAGS: &'static [&'static str] = &[#(#tags),*];
+        }
+    });
+
+    let phase_const = register_attr.phase.as_ref().map(|phase| {
+        let phase = phase.to_string();
+        quote! {
+            const PHASE: Option<&'static str> = Some(#phase);
+        }
+    });
+
+    let namespace_const = register_attr.namespace.as_ref().map(|namespace| {
+        quote! {
+            const NAMESPACE: Option<&'static str> = Some(#namespace);
+        }
+    });
+
+    let after_const = register_attr.after.as_ref().map(|after| {
+        quote! {
+            const AFTER: &'static [&'static str] = &[#(#after),*];
+        }
+    });
+
+    let conflicts_with_const = register_attr.conflicts_with.as_ref().map(|conflicts_with| {
+        quote! {
+            const CONFLICTS_WITH: &'static [&'static str] = &[#(#conflicts_with),*];
+        }
+    });
+
+    let shutdown_const = register_attr.shutdown_fn.as_ref().map(|shutdown_fn| {
+        quote! {
+            const SHUTDOWN: Option<fn()> = Some(#shutdown_fn);
+        }
+    });
+
+    let health_check_const = register_attr.health_check_fn.as_ref().map(|health_check_fn| {
+        quote! {
+            const HEALTH_CHECK: Option<fn() -> traitreg::health::HealthStatus> =
+                Some(#health_check_fn);
+        }
+    });
+
+    quote! {
+        #priority_const
+        #capabilities_const
+        #tags_const
+        #phase_const
+        #namespace_const
+        #after_const
+        #conflicts_with_const
+        #shutdown_const
+        #health_check_const
+    }
+}
+
+// Registered item, as recorded in the registration and its manifest record
+struct Registered<'a> {
+    type_name: &'a str,
+    type_path: proc_macro2::TokenStream,
+    trait_name: &'a str,
+    // Constant expression
+    has_constructor: proc_macro2::TokenStream,
+}
+
+// Static called before `main()` which submits a registration, and its manifest record
+fn registration_static(
+    registration_ident: &str,
+    registration_cfg: &proc_macro2::TokenStream,
+    register_call: &proc_macro2::TokenStream,
+    registered: &Registered,
+    init_priority: Option<u16>,
+) -> proc_macro2::TokenStream {
+    let Registered {
+        type_name,
+        type_path,
+        trait_name,
+        has_constructor,
+    } = registered;
+    // Constructors in sections with lower numbers run first, registries are built at 20000
+    let init_priority = init_priority.unwrap_or(10000);
+    let init_array_section = format!(".init_array.{init_priority:05}");
+    let crt_section = format!(".CRT$XCT{init_priority:05}");
+
+    let register_static_ident =
+        syn::parse_str::<syn::Ident>(format!("{}__Register", registration_ident).as_ref())
+            .expect("Unable to create identifier");
+    let register_static_fn_ident =
+        syn::parse_str::<syn::Ident>(format!("{}__RegisterFn", registration_ident).as_ref())
+            .expect("Unable to create identifier");
+
+    quote! {
+        #registration_cfg
+        #[used]
+        #[cfg_attr(any(target_os = "linux", target_os = "android"), link_section = #init_array_section)]
+        #[cfg_attr(target_os = "freebsd", link_section = #init_array_section)]
+        #[cfg_attr(target_os = "netbsd", link_section = #init_array_section)]
+        #[cfg_attr(target_os = "openbsd", link_section = #init_array_section)]
+        #[cfg_attr(target_os = "dragonfly", link_section = #init_array_section)]
+        #[cfg_attr(target_os = "illumos", link_section = #init_array_section)]
+        #[cfg_attr(target_os = "haiku", link_section = #init_array_section)]
+        #[cfg_attr(target_vendor = "apple", link_section = "__DATA,__mod_init_func")]
+        #[cfg_attr(windows, link_section = #crt_section)]
+        static #register_static_ident: extern fn() = {
+            extern fn #register_static_fn_ident() {
+                // Registrations are submitted without locking, and added to the registry when the
+                // first registry is built
+                fn register(registry: &mut Vec<traitreg::RegisteredImplWrapper<Box<u32>>>) {
+                    #register_call
+                }
+                static REGISTRATION: traitreg::__private::Registration =
+                    traitreg::__private::Registration::new(
+                        register,
+                        #type_name,
+                        stringify!(#type_path),
+                        #trait_name,
+                    );
+                REGISTRATION.submit();
+
+                // Manifest of the registration, which can be read from the binary without running
+                // it, see `traitreg::manifest`
+                const MANIFEST: &str = concat!(
+                    "traitreg-manifest\x1f",
+                    #type_name, "\x1f",
+                    stringify!(#type_path), "\x1f",
+                    #trait_name, "\x1f",
+                    core::module_path!(), "\x1f",
+                    core::env!("CARGO_PKG_NAME"), "\x1f",
+                    core::env!("CARGO_PKG_VERSION"), "\x1f",
+                );
+                #[used]
+                #[cfg_attr(any(target_os = "linux", target_os = "android"), link_section = "traitreg_manifest")]
+                #[cfg_attr(target_os = "freebsd", link_section = "traitreg_manifest")]
+                #[cfg_attr(target_os = "netbsd", link_section = "traitreg_manifest")]
+                #[cfg_attr(target_os = "openbsd", link_section = "traitreg_manifest")]
+                #[cfg_attr(target_os = "dragonfly", link_section = "traitreg_manifest")]
+                #[cfg_attr(target_os = "illumos", link_section = "traitreg_manifest")]
+                #[cfg_attr(target_os = "haiku", link_section = "traitreg_manifest")]
+                #[cfg_attr(target_vendor = "apple", link_section = "__DATA,__traitreg")]
+                #[cfg_attr(windows, link_section = ".trgman")]
+                static MANIFEST_BYTES: [u8; MANIFEST.len() + 2] =
+                    traitreg::__private::manifest_bytes(MANIFEST, #has_constructor);
+                // Referenced so the linker does not discard the manifest
+                core::hint::black_box(&MANIFEST_BYTES);
+            }
+            #register_static_fn_ident
+        };
+    }
+}
+
+// Re-emit an item with the register attribute after any other attribute macros on the item, so
+// those are expanded first. Returns `None` if there are none. The re-emitted attribute starts with
+// `__deferred`, see `strip_deferred_marker`.
+fn defer_register_attr(
+    attr: &proc_macro::TokenStream,
+    item: &proc_macro::TokenStream,
+) -> Option<proc_macro::TokenStream> {
+    let mut item = syn::parse::<syn::Item>(item.clone()).ok()?;
+    let attrs = match &mut item {
+        syn::Item::Impl(item_impl) => &mut item_impl.attrs,
+        syn::Item::Fn(item_fn) => &mut item_fn.attrs,
+        _ => return None,
+    };
+    if !attrs.iter().any(is_attribute_macro) {
+        return None;
+    }
+
+    let attr = proc_macro2::TokenStream::from(attr.clone());
+    attrs.push(syn::parse_quote! { #[traitreg::register(__deferred, #attr)] });
+    Some(item.into_token_stream().into())
+}
+
+// The arguments of a register attribute re-emitted by `defer_register_attr` without the
+// `__deferred` marker, or `None` if the attribute was not deferred. Attributes which cannot be
+// told apart from attribute macros, such as inert built-in attributes, stay on the item after the
+// register attribute, so it must not be deferred again.
+fn strip_deferred_marker(attr: &proc_macro::TokenStream) -> Option<proc_macro::TokenStream> {
+    let mut tokens = proc_macro2::TokenStream::from(attr.clone()).into_iter();
+    match tokens.next() {
+        Some(proc_macro2::TokenTree::Ident(ident)) if ident == "__deferred" => {}
+        _ => return None,
+    }
+    // Skip the comma after the marker
+    tokens.next();
+    Some(proc_macro2::TokenStream::from_iter(tokens).into())
+}
+
+// Could an attribute be an attribute macro, rather than a built-in or tool attribute
+fn is_attribute_macro(attr: &syn::Attribute) -> bool {
+    const BUILT_IN: &[&str] = &[
+        "allow",
+        "cfg",
+        "cfg_attr",
+        "clippy",
+        "cold",
+        "deny",
+        "deprecated",
+        "diagnostic",
+        "doc",
+        "expect",
+        "export_name",
+        "forbid",
+        "inline",
+        "link_section",
+        "must_use",
+        "no_mangle",
+        "rustfmt",
+        "target_feature",
+        "track_caller",
+        "unsafe",
+        "warn",
+    ];
+
+    let Some(first) = attr.path().segments.first() else {
+        return false;
+    };
+    matches!(attr.style, syn::AttrStyle::Outer)
+        && !is_register_attr(attr)
+        && !BUILT_IN.contains(&first.ident.to_string().as_str())
+}
+
+/// Is the attribute `#[traitreg::register]` or an imported `#[register]`
+fn is_register_attr(attr: &syn::Attribute) -> bool {
+    let segments: Vec<String> = attr
+        .path()
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    matches!(
+        segments.iter().map(String::as_str).collect::<Vec<_>>()[..],
+        ["traitreg", "register"] | ["register"]
+    )
+}
+
+// Identifier for the items generated for a registration, from the tokens of the paths involved.
+// Tokens such as `<`, `::` and `r#` are replaced in the identifier, so a hash of the original
+// tokens is appended to keep identifiers from different paths distinct, e.g. for `Codec<u8>` and
+// `Codec_u8_`, which is common for impls generated by `macro_rules!` macros.
+fn registration_ident(parts: &[&str]) -> String {
+    // FNV-1a
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in parts.join("\0").bytes().filter(|byte| !byte.is_ascii_whitespace()) {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x01000193);
+    }
+
+    let fragments: Vec<String> = parts.iter().map(|part| to_ident_fragment(part)).collect();
+    format!("{}_{hash:08x}", fragments.join("_"))
+}
+
+/// Replace any characters in a name which are not valid in an identifier, e.g. `Block<16>` becomes
+/// `Block_16_`
+fn to_ident_fragment(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+// Tokens as a string spaced like rustfmt would for simple signatures, `impl<T: Codec> Codec for
+// Wrapper<T>` rather than `impl < T : Codec > Codec for Wrapper < T >`. Long token streams are
+// printed over multiple lines, so whitespace is collapsed first.
+fn signature_string(tokens: proc_macro2::TokenStream) -> String {
+    let mut signature = tokens
+        .to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    for (spaced, unspaced) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (" :", ":"),
+        ("& ", "&"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+    ] {
+        signature = signature.replace(spaced, unspaced);
+    }
+    signature
+}
+
 fn get_self_type_path(self_ty: &syn::Type) -> &syn::Path {
-    if let syn::Type::Path(type_path) = self_ty {
-        return &type_path.path;
+    match self_ty {
+        syn::Type::Path(type_path) => return &type_path.path,
+        // Types passed to a `macro_rules!` macro as `$type:ty` are wrapped in an invisible group
+        syn::Type::Group(group) => return get_self_type_path(&group.elem),
+        _ => {}
     }
 
     let error_type = match self_ty {
         syn::Type::Array(_) => "n array",
         syn::Type::BareFn(_) => " function",
-        syn::Type::Group(_) => " group",
         syn::Type::ImplTrait(_) => " trait impl",
         syn::Type::Infer(_) => "n inferred type (_)",
         syn::Type::Macro(_) => " macro",